@@ -0,0 +1,69 @@
+//! The `ToLinearMap` derive macro. Use it through the `derive` feature of `linear-map`, which
+//! re-exports it as `linear_map::fields::ToLinearMap`.
+
+#![deny(missing_docs)]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Error, Fields};
+
+/// Derives `linear_map::fields::ToLinearMap` for a struct with named fields.
+///
+/// Every field's type must implement `linear_map::fields::Field`.
+#[proc_macro_derive(ToLinearMap)]
+pub fn derive_to_linear_map(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident,
+                                               "ToLinearMap requires named fields")),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "ToLinearMap only supports structs")),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let idents: Vec<_> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+    let names: Vec<_> = idents.iter().map(|i| i.unraw().to_string()).collect();
+    let count = idents.len();
+
+    Ok(quote! {
+        impl #impl_generics ::linear_map::fields::ToLinearMap for #name #ty_generics #where_clause {
+            fn to_linear_map(&self)
+                -> ::linear_map::LinearMap<&'static str, ::linear_map::fields::FieldValue> {
+                let mut map = ::linear_map::LinearMap::with_capacity(#count);
+                #(
+                    map.insert(#names, ::linear_map::fields::Field::to_field(&self.#idents));
+                )*
+                map
+            }
+
+            fn from_linear_map(
+                mut map: ::linear_map::LinearMap<&str, ::linear_map::fields::FieldValue>)
+                -> ::std::result::Result<Self, ::linear_map::errors::FieldError> {
+                ::std::result::Result::Ok(#name {
+                    #(
+                        #idents: {
+                            let value = map.remove(#names)
+                                .ok_or(::linear_map::errors::FieldError::Missing(#names))?;
+                            ::linear_map::fields::Field::from_field(value)
+                                .ok_or(::linear_map::errors::FieldError::Invalid(#names))?
+                        },
+                    )*
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,46 @@
+/* C interface to linear-map, built with the `ffi` feature. See the `ffi` module documentation. */
+
+#ifndef LINEAR_MAP_H
+#define LINEAR_MAP_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A map from uint64_t to uint64_t. */
+typedef struct LinearMapU64 LinearMapU64;
+
+LinearMapU64 *linear_map_u64_new(void);
+void linear_map_u64_free(LinearMapU64 *map);
+size_t linear_map_u64_len(const LinearMapU64 *map);
+/* Returns true if the key was present; its previous value is written to old_value if non-null. */
+bool linear_map_u64_insert(LinearMapU64 *map, uint64_t key, uint64_t value, uint64_t *old_value);
+/* Returns true if the key is present; its value is written to value if non-null. */
+bool linear_map_u64_get(const LinearMapU64 *map, uint64_t key, uint64_t *value);
+/* Returns true if the key was present; its value is written to value if non-null. */
+bool linear_map_u64_remove(LinearMapU64 *map, uint64_t key, uint64_t *value);
+void linear_map_u64_clear(LinearMapU64 *map);
+
+/* A map from nul-terminated strings to nul-terminated strings, which it copies. */
+typedef struct LinearMapCStr LinearMapCStr;
+
+LinearMapCStr *linear_map_cstr_new(void);
+void linear_map_cstr_free(LinearMapCStr *map);
+size_t linear_map_cstr_len(const LinearMapCStr *map);
+/* Returns true if the key was present and its value replaced. */
+bool linear_map_cstr_insert(LinearMapCStr *map, const char *key, const char *value);
+/* Returns the value, owned by the map and valid until it is next modified, or NULL. */
+const char *linear_map_cstr_get(const LinearMapCStr *map, const char *key);
+/* Returns true if the key was present. */
+bool linear_map_cstr_remove(LinearMapCStr *map, const char *key);
+void linear_map_cstr_clear(LinearMapCStr *map);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,257 @@
+//! A map that switches from linear to binary search once it grows large.
+//!
+//! See the [`AdaptiveLinearMap`](struct.AdaptiveLinearMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::mem;
+use std::ops::Deref;
+
+use super::{LinearMap, IntoIter, Iter, IterMut, ValuesMut};
+
+/// The length past which an [`AdaptiveLinearMap`](struct.AdaptiveLinearMap.html) created with
+/// `new` sorts itself.
+pub const DEFAULT_THRESHOLD: usize = 32;
+
+/// A map implemented by searching linearly in a vector while small, and by binary search once it
+/// grows past a threshold.
+///
+/// While the map holds at most `threshold` entries, it behaves like a `LinearMap`: entries are
+/// kept in insertion order (up to removals) and searched linearly. The first insertion that takes
+/// it past the threshold sorts the entries by key; from then on the map keeps them sorted and
+/// lookups run in `O(log n)` time, while insertions and removals shift entries and remain
+/// `O(n)`. The map stays sorted until it is cleared, so shrinking back below the threshold does
+/// not cause it to switch modes repeatedly.
+///
+/// The map dereferences to the underlying `LinearMap`, so `len`, `iter` and the other read-only
+/// methods are available. Iteration order is arbitrary; in sorted mode it happens to be ascending
+/// key order. Lookups, insertions and removals are defined here, so that they use the current
+/// mode.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::adaptive::AdaptiveLinearMap;
+///
+/// let mut map = AdaptiveLinearMap::with_threshold(4);
+/// for i in (0..4).rev() {
+///     map.insert(i, i * 10);
+/// }
+/// assert!(!map.is_sorted());
+///
+/// map.insert(4, 40);
+/// assert!(map.is_sorted());
+/// assert_eq!(map.get(&3), Some(&30));
+/// ```
+pub struct AdaptiveLinearMap<K, V> {
+    map: LinearMap<K, V>,
+    threshold: usize,
+    sorted: bool,
+}
+
+impl<K: Ord, V> AdaptiveLinearMap<K, V> {
+    /// Creates an empty map that sorts itself past
+    /// [`DEFAULT_THRESHOLD`](constant.DEFAULT_THRESHOLD.html) entries. This method does not
+    /// allocate.
+    pub fn new() -> Self {
+        Self::with_threshold(DEFAULT_THRESHOLD)
+    }
+
+    /// Creates an empty map that sorts itself once it holds more than `threshold` entries. This
+    /// method does not allocate.
+    pub fn with_threshold(threshold: usize) -> Self {
+        AdaptiveLinearMap { map: LinearMap::new(), threshold, sorted: false }
+    }
+
+    /// Returns the length past which the map sorts itself.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns true if the map is in sorted mode, using binary search for lookups.
+    pub fn is_sorted(&self) -> bool {
+        self.sorted
+    }
+
+    /// Like [`LinearMap::clear`](../struct.LinearMap.html#method.clear), also returning the map
+    /// to linear mode.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.sorted = false;
+    }
+
+    /// Like [`LinearMap::retain`](../struct.LinearMap.html#method.retain), keeping the remaining
+    /// entries in order.
+    pub fn retain<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        self.entries_mut().retain_mut(|(k, v)| keep_fn(k, v));
+    }
+
+    /// Like [`LinearMap::iter_mut`](../struct.LinearMap.html#method.iter_mut).
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Like [`LinearMap::values_mut`](../struct.LinearMap.html#method.values_mut).
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.map.values_mut()
+    }
+
+    /// Searches for the key, returning its position if found. In sorted mode, the error holds the
+    /// position where the key would be inserted; in linear mode it is unspecified.
+    fn search<Q: ?Sized + Ord>(&self, key: &Q) -> Result<usize, usize> where K: Borrow<Q> {
+        if self.sorted {
+            self.map.storage.binary_search_by(|(k, _)| k.borrow().cmp(key))
+        } else {
+            self.map.storage.iter().position(|(k, _)| k.borrow() == key).ok_or(self.map.len())
+        }
+    }
+
+    /// Like [`LinearMap::get`](../struct.LinearMap.html#method.get), using binary search in
+    /// sorted mode.
+    ///
+    /// The given key may be any borrowed form of the map's key type, but `Ord` on the borrowed
+    /// form *must* match that of the key type.
+    pub fn get<Q: ?Sized + Ord>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        self.search(key).ok().map(|i| &self.map.storage[i].1)
+    }
+
+    /// Like [`LinearMap::get_mut`](../struct.LinearMap.html#method.get_mut), using binary search
+    /// in sorted mode.
+    pub fn get_mut<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+        match self.search(key) {
+            Ok(i) => Some(&mut self.map.storage[i].1),
+            Err(_) => None,
+        }
+    }
+
+    /// Like [`LinearMap::contains_key`](../struct.LinearMap.html#method.contains_key), using
+    /// binary search in sorted mode.
+    pub fn contains_key<Q: ?Sized + Ord>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.search(key).is_ok()
+    }
+
+    /// Like [`LinearMap::insert`](../struct.LinearMap.html#method.insert), sorting the map if
+    /// this takes it past its threshold.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(i) => return Some(mem::replace(&mut self.map.storage[i].1, value)),
+            Err(i) if self.sorted => self.entries_mut().insert(i, (key, value)),
+            Err(_) => {
+                let threshold = self.threshold;
+                let entries = self.entries_mut();
+                entries.push((key, value));
+                if entries.len() > threshold {
+                    entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+                    self.sorted = true;
+                }
+            }
+        }
+        None
+    }
+
+    /// Like [`LinearMap::remove`](../struct.LinearMap.html#method.remove), keeping the entries
+    /// in order in sorted mode.
+    pub fn remove<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        match self.search(key) {
+            Ok(i) if self.sorted => Some(self.entries_mut().remove(i).1),
+            Ok(i) => Some(self.entries_mut().swap_remove(i).1),
+            Err(_) => None,
+        }
+    }
+}
+
+impl<K, V> AdaptiveLinearMap<K, V> {
+    /// Gives access to the entries for changes that insert, remove or reorder them.
+    fn entries_mut(&mut self) -> &mut Vec<(K, V)> {
+        self.map.invalidate_positions();
+        &mut self.map.storage
+    }
+}
+
+impl<K, V> Deref for AdaptiveLinearMap<K, V> {
+    type Target = LinearMap<K, V>;
+
+    fn deref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> AsRef<LinearMap<K, V>> for AdaptiveLinearMap<K, V> {
+    fn as_ref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for AdaptiveLinearMap<K, V> {
+    fn clone(&self) -> Self {
+        AdaptiveLinearMap {
+            map: self.map.clone(),
+            threshold: self.threshold,
+            sorted: self.sorted,
+        }
+    }
+}
+
+impl<K: Ord + Debug, V: Debug> Debug for AdaptiveLinearMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: Ord, V> Default for AdaptiveLinearMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for AdaptiveLinearMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K: Ord, V> iter::FromIterator<(K, V)> for AdaptiveLinearMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+/// Compares the maps' entries; their thresholds and modes are ignored.
+impl<K: Ord, V: PartialEq> PartialEq for AdaptiveLinearMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Ord, V: Eq> Eq for AdaptiveLinearMap<K, V> {}
+
+impl<K: Ord, V> IntoIterator for AdaptiveLinearMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a AdaptiveLinearMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a mut AdaptiveLinearMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,106 @@
+//! A map storing at most one value of each type.
+//!
+//! See the [`LinearAnyMap`](struct.LinearAnyMap.html) type for details.
+
+use std::any::{Any, TypeId};
+use std::fmt::{self, Debug};
+
+use super::LinearMap;
+
+/// A map keyed by type, storing at most one value of each type and searching linearly in a
+/// vector.
+///
+/// Values are boxed as `dyn Any` and keyed by their `TypeId`. This suits small extension or
+/// context bags holding a handful of types, where comparing a few `TypeId`s is cheaper than
+/// hashing them.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::any::LinearAnyMap;
+///
+/// struct RequestId(u64);
+///
+/// let mut extensions = LinearAnyMap::new();
+/// extensions.insert(RequestId(7));
+/// extensions.insert("user name");
+///
+/// assert_eq!(extensions.get::<RequestId>().map(|id| id.0), Some(7));
+/// assert_eq!(extensions.get::<&str>(), Some(&"user name"));
+/// assert!(extensions.get::<u32>().is_none());
+/// ```
+#[derive(Default)]
+pub struct LinearAnyMap {
+    map: LinearMap<TypeId, Box<dyn Any>>,
+}
+
+impl LinearAnyMap {
+    /// Creates an empty map. This method does not allocate.
+    pub const fn new() -> Self {
+        LinearAnyMap { map: LinearMap::new() }
+    }
+
+    /// Creates an empty map with room for the given number of types.
+    pub fn with_capacity(capacity: usize) -> Self {
+        LinearAnyMap { map: LinearMap::with_capacity(capacity) }
+    }
+
+    /// Returns the number of values in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Clears the map, removing all values. Keeps the allocated memory for reuse.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns a reference to the value of type `T`, if the map contains one.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.map.get(&TypeId::of::<T>()).and_then(|v| v.downcast_ref())
+    }
+
+    /// Returns a mutable reference to the value of type `T`, if the map contains one.
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.map.get_mut(&TypeId::of::<T>()).and_then(|v| v.downcast_mut())
+    }
+
+    /// Checks if the map contains a value of type `T`.
+    pub fn contains<T: Any>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Inserts a value, replacing and returning the previous value of the same type.
+    pub fn insert<T: Any>(&mut self, value: T) -> Option<T> {
+        self.map.insert(TypeId::of::<T>(), Box::new(value)).map(unbox)
+    }
+
+    /// Returns a mutable reference to the value of type `T`, inserting the result of `default`
+    /// first if the map contains none.
+    pub fn get_or_insert_with<T: Any, F: FnOnce() -> T>(&mut self, default: F) -> &mut T {
+        self.map.entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(default()))
+            .downcast_mut()
+            .expect("value stored under the wrong TypeId")
+    }
+
+    /// Removes the value of type `T` and returns it, if the map contains one.
+    pub fn remove<T: Any>(&mut self) -> Option<T> {
+        self.map.remove(&TypeId::of::<T>()).map(unbox)
+    }
+}
+
+fn unbox<T: Any>(value: Box<dyn Any>) -> T {
+    *value.downcast().expect("value stored under the wrong TypeId")
+}
+
+impl Debug for LinearAnyMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LinearAnyMap").field("len", &self.len()).finish()
+    }
+}
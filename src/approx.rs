@@ -0,0 +1,134 @@
+//! Lookups matching keys within a tolerance, for maps keyed by floats, integers or times.
+//!
+//! Comparing floats for exact equality is fragile: a key computed as `0.1 + 0.2` is not found
+//! under `0.3`. The methods added here to `LinearMap` instead find the first entry, in iteration
+//! order, whose key lies within a given distance of the searched key, as determined by the
+//! [`Tolerance`](trait.Tolerance.html) trait.
+//!
+//! # Example
+//!
+//! ```
+//! use linear_map::LinearMap;
+//!
+//! let mut calibration = LinearMap::new();
+//! calibration.insert(0.3, "low");
+//! calibration.insert(0.9, "high");
+//!
+//! assert_eq!(calibration.get(&(0.1 + 0.2)), None);
+//! assert_eq!(calibration.get_approx(&(0.1 + 0.2), 1e-9), Some(&"low"));
+//! ```
+
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{Entry, LinearMap, OccupiedEntry, VacantEntry};
+
+/// A key type whose values can be compared within a tolerance.
+pub trait Tolerance {
+    /// The type of the maximal distance between two matching values.
+    type Epsilon;
+
+    /// Returns true if `self` and `other` are at most `epsilon` apart.
+    fn within(&self, other: &Self, epsilon: &Self::Epsilon) -> bool;
+}
+
+macro_rules! impl_float_tolerance {($($t:ty)*) => {$(
+    impl Tolerance for $t {
+        type Epsilon = $t;
+
+        fn within(&self, other: &$t, epsilon: &$t) -> bool {
+            (self - other).abs() <= *epsilon
+        }
+    }
+)*}}
+impl_float_tolerance!{f32 f64}
+
+macro_rules! impl_int_tolerance {($($t:ty => $u:ty),*) => {$(
+    impl Tolerance for $t {
+        type Epsilon = $u;
+
+        fn within(&self, other: &$t, epsilon: &$u) -> bool {
+            self.abs_diff(*other) <= *epsilon
+        }
+    }
+)*}}
+impl_int_tolerance!{
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize,
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize
+}
+
+impl Tolerance for Duration {
+    type Epsilon = Duration;
+
+    fn within(&self, other: &Duration, epsilon: &Duration) -> bool {
+        self.abs_diff(*other) <= *epsilon
+    }
+}
+
+impl Tolerance for Instant {
+    type Epsilon = Duration;
+
+    fn within(&self, other: &Instant, epsilon: &Duration) -> bool {
+        let distance = if self > other { *self - *other } else { *other - *self };
+        distance <= *epsilon
+    }
+}
+
+impl Tolerance for SystemTime {
+    type Epsilon = Duration;
+
+    fn within(&self, other: &SystemTime, epsilon: &Duration) -> bool {
+        let distance = match self.duration_since(*other) {
+            Ok(d) => d,
+            Err(e) => e.duration(),
+        };
+        distance <= *epsilon
+    }
+}
+
+impl<K: Tolerance + PartialEq, V> LinearMap<K, V> {
+    // An equal key always matches, even for a negative or NaN `epsilon` that nothing is within;
+    // otherwise `entry_approx` would insert a duplicate of a key already in the map.
+    fn position_approx(&self, key: &K, epsilon: &K::Epsilon) -> Option<usize> {
+        self.storage.iter().position(|(k, _)| k == key || k.within(key, epsilon))
+    }
+
+    /// Returns a reference to the value of the first entry whose key is within `epsilon` of the
+    /// given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_approx(&self, key: &K, epsilon: K::Epsilon) -> Option<&V> {
+        self.get_key_value_approx(key, epsilon).map(|(_, v)| v)
+    }
+
+    /// Returns references to the key and value of the first entry whose key is within `epsilon`
+    /// of the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_key_value_approx(&self, key: &K, epsilon: K::Epsilon) -> Option<(&K, &V)> {
+        self.position_approx(key, &epsilon).map(|i| {
+            let (k, v) = &self.storage[i];
+            (k, v)
+        })
+    }
+
+    /// Returns a mutable reference to the value of the first entry whose key is within `epsilon`
+    /// of the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_approx_mut(&mut self, key: &K, epsilon: K::Epsilon) -> Option<&mut V> {
+        let i = self.position_approx(key, &epsilon)?;
+        Some(&mut self.storage[i].1)
+    }
+
+    /// Returns the entry of the first key within `epsilon` of the given key, or a vacant entry
+    /// for the given key if there is none.
+    ///
+    /// Inserting keys only through this method, always with the same tolerance, keeps them all
+    /// more than `epsilon` apart.
+    pub fn entry_approx(&mut self, key: K, epsilon: K::Epsilon) -> Entry<'_, K, V> {
+        match self.position_approx(&key, &epsilon) {
+            Some(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            None => Entry::Vacant(VacantEntry { map: self, key }),
+        }
+    }
+}
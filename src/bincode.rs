@@ -0,0 +1,130 @@
+//! Optional implementations of bincode 2's native `Encode` and `Decode` traits.
+//!
+//! Maps and sets are encoded like bincode encodes `HashMap` and `HashSet`: a length followed by
+//! the entries, in the order the map or set stores them. This is also the encoding of a `Vec` of
+//! pairs or of items, so data can move between these types. These implementations do not go
+//! through serde and work with bincode's `alloc` feature alone.
+//!
+//! Decoding inserts entries one by one, so a key that appears more than once in the input keeps
+//! the last value encoded for it.
+//!
+//! Each decoded key is compared against all keys decoded before it, so decoding takes time
+//! quadratic in the number of entries. When decoding untrusted input, bound its size with a
+//! configuration such as `bincode::config::standard().with_limit::<N>()`.
+
+extern crate bincode;
+
+use std::convert::TryFrom;
+use std::mem;
+
+use self::bincode::{BorrowDecode, Decode, Encode};
+use self::bincode::de::{BorrowDecoder, Decoder};
+use self::bincode::enc::Encoder;
+use self::bincode::error::{DecodeError, EncodeError};
+
+use super::LinearMap;
+use super::set::LinearSet;
+
+// Lengths are encoded as `u64`, like bincode's own containers.
+fn encode_len<E: Encoder>(encoder: &mut E, len: usize) -> Result<(), EncodeError> {
+    (len as u64).encode(encoder)
+}
+
+fn decode_len<D: Decoder>(decoder: &mut D) -> Result<usize, DecodeError> {
+    let len = u64::decode(decoder)?;
+    usize::try_from(len).map_err(|_| DecodeError::OutsideUsizeRange(len))
+}
+
+impl<K: Encode, V: Encode> Encode for LinearMap<K, V> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encode_len(encoder, self.len())?;
+        for (k, v) in self {
+            k.encode(encoder)?;
+            v.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Context, K, V> Decode<Context> for LinearMap<K, V>
+    where K: Decode<Context> + PartialEq,
+          V: Decode<Context>,
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = decode_len(decoder)?;
+        decoder.claim_container_read::<(K, V)>(len)?;
+
+        let mut map = LinearMap::with_capacity(len);
+        for _ in 0..len {
+            // The claim above already covers the entry's in-memory size.
+            decoder.unclaim_bytes_read(mem::size_of::<(K, V)>());
+            let k = K::decode(decoder)?;
+            let v = V::decode(decoder)?;
+            map.insert(k, v);
+        }
+        Ok(map)
+    }
+}
+
+impl<'de, Context, K, V> BorrowDecode<'de, Context> for LinearMap<K, V>
+    where K: BorrowDecode<'de, Context> + PartialEq,
+          V: BorrowDecode<'de, Context>,
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D)
+        -> Result<Self, DecodeError> {
+        let len = decode_len(decoder)?;
+        decoder.claim_container_read::<(K, V)>(len)?;
+
+        let mut map = LinearMap::with_capacity(len);
+        for _ in 0..len {
+            decoder.unclaim_bytes_read(mem::size_of::<(K, V)>());
+            let k = K::borrow_decode(decoder)?;
+            let v = V::borrow_decode(decoder)?;
+            map.insert(k, v);
+        }
+        Ok(map)
+    }
+}
+
+impl<T: Encode> Encode for LinearSet<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encode_len(encoder, self.map.len())?;
+        for item in self.map.keys() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Context, T> Decode<Context> for LinearSet<T>
+    where T: Decode<Context> + Eq,
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = decode_len(decoder)?;
+        decoder.claim_container_read::<T>(len)?;
+
+        let mut set = LinearSet::with_capacity(len);
+        for _ in 0..len {
+            decoder.unclaim_bytes_read(mem::size_of::<T>());
+            set.insert(T::decode(decoder)?);
+        }
+        Ok(set)
+    }
+}
+
+impl<'de, Context, T> BorrowDecode<'de, Context> for LinearSet<T>
+    where T: BorrowDecode<'de, Context> + Eq,
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D)
+        -> Result<Self, DecodeError> {
+        let len = decode_len(decoder)?;
+        decoder.claim_container_read::<T>(len)?;
+
+        let mut set = LinearSet::with_capacity(len);
+        for _ in 0..len {
+            decoder.unclaim_bytes_read(mem::size_of::<T>());
+            set.insert(T::borrow_decode(decoder)?);
+        }
+        Ok(set)
+    }
+}
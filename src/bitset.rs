@@ -0,0 +1,246 @@
+//! A map with integer keys that answers most negative lookups from a bitmask.
+//!
+//! See the [`BitsetLinearMap`](struct.BitsetLinearMap.html) type for details.
+
+use std::fmt::{self, Debug};
+use std::iter;
+
+use super::{LinearMap, IntoIter, Iter, IterMut, Keys, Values, ValuesMut};
+
+/// An integer key type that can be tracked in the presence mask of a
+/// [`BitsetLinearMap`](struct.BitsetLinearMap.html).
+pub trait BitKey: Eq {
+    /// Returns the bit of the 128-bit presence mask this key maps to.
+    ///
+    /// Must return a value below 128, and equal keys must map to equal bits.
+    fn bit(&self) -> u32;
+}
+
+macro_rules! impl_bit_key {($($t:ty)*) => {$(
+    impl BitKey for $t {
+        fn bit(&self) -> u32 {
+            (*self as u128 % 128) as u32
+        }
+    }
+)*}}
+impl_bit_key!{u8 u16 u32 u64 u128 usize}
+
+/// A map implemented by searching linearly in a vector, with a presence bitmask over its keys.
+///
+/// Every key sets one bit of a 128-bit mask kept alongside the entries. Lookups first test that
+/// bit, so a key whose bit is clear is known to be absent in `O(1)` time and only lookups of
+/// present keys (or of keys sharing a bit with a present key) scan the vector. Keys below 128
+/// each have their own bit, making the filter exact for them.
+///
+/// Insertion cost is unchanged; removals rescan the remaining keys to maintain the mask.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::bitset::BitsetLinearMap;
+///
+/// let mut map = BitsetLinearMap::new();
+/// map.insert(3u8, "three");
+/// map.insert(17, "seventeen");
+/// assert!(map.contains_key(&3));
+/// assert!(!map.contains_key(&4)); // answered from the mask alone
+/// ```
+pub struct BitsetLinearMap<K, V> {
+    map: LinearMap<K, V>,
+    mask: u128,
+}
+
+impl<K: BitKey, V> BitsetLinearMap<K, V> {
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        BitsetLinearMap { map: LinearMap::new(), mask: 0 }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        BitsetLinearMap { map: LinearMap::with_capacity(capacity), mask: 0 }
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Clears the map, removing all elements. Keeps the allocated memory for
+    /// reuse.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.mask = 0;
+    }
+
+    /// Scan through the map and keep those key-value pairs where the
+    /// closure returns `true`.
+    ///
+    /// The order the elements are visited is not specified.
+    pub fn retain<F>(&mut self, keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        self.map.retain(keep_fn);
+        self.mask = self.map.keys().fold(0, |mask, k| mask | 1 << k.bit());
+    }
+
+    /// Returns an iterator yielding references to the map's keys and their corresponding values in
+    /// arbitrary order.
+    ///
+    /// The iterator's item type is `(&K, &V)`.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// Returns an iterator yielding references to the map's keys and mutable references to their
+    /// corresponding values in arbitrary order.
+    ///
+    /// The iterator's item type is `(&K, &mut V)`.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Returns an iterator yielding references to the map's keys in arbitrary order.
+    ///
+    /// The iterator's item type is `&K`.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator yielding references to the map's values in arbitrary order.
+    ///
+    /// The iterator's item type is `&V`.
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.map.values()
+    }
+
+    /// Returns an iterator yielding mutable references to the map's values in arbitrary order.
+    ///
+    /// The iterator's item type is `&mut V`.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.map.values_mut()
+    }
+
+    fn may_contain(&self, key: &K) -> bool {
+        self.mask & 1 << key.bit() != 0
+    }
+
+    /// Returns a reference to the value corresponding to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        if self.may_contain(key) { self.map.get(key) } else { None }
+    }
+
+    /// Returns a mutable reference to the value corresponding to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if self.may_contain(key) { self.map.get_mut(key) } else { None }
+    }
+
+    /// Checks if the map contains the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// Returns `None` if the map did not contain the given key. Otherwise the key's value is
+    /// replaced with the given value, and the old value is returned.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.mask |= 1 << key.bit();
+        self.map.insert(key, value)
+    }
+
+    /// Removes the given key from the map and returns its corresponding value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if !self.may_contain(key) {
+            return None;
+        }
+        let value = self.map.remove(key)?;
+        let bit = key.bit();
+        if self.map.keys().all(|k| k.bit() != bit) {
+            self.mask &= !(1 << bit);
+        }
+        Some(value)
+    }
+}
+
+impl<K: BitKey + Clone, V: Clone> Clone for BitsetLinearMap<K, V> {
+    fn clone(&self) -> Self {
+        BitsetLinearMap { map: self.map.clone(), mask: self.mask }
+    }
+}
+
+impl<K: BitKey + Debug, V: Debug> Debug for BitsetLinearMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: BitKey, V> Default for BitsetLinearMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: BitKey, V> Extend<(K, V)> for BitsetLinearMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K: BitKey, V> iter::FromIterator<(K, V)> for BitsetLinearMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+impl<K: BitKey, V: PartialEq> PartialEq for BitsetLinearMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.mask == other.mask && self.map == other.map
+    }
+}
+
+impl<K: BitKey, V: Eq> Eq for BitsetLinearMap<K, V> {}
+
+impl<K: BitKey, V> IntoIterator for BitsetLinearMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K: BitKey, V> IntoIterator for &'a BitsetLinearMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K: BitKey, V> IntoIterator for &'a mut BitsetLinearMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
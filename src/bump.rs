@@ -0,0 +1,188 @@
+//! A map whose entries are allocated from a [`bumpalo`][bumpalo] arena.
+//!
+//! See the [`BumpLinearMap`](struct.BumpLinearMap.html) type for details.
+//!
+//! [bumpalo]: https://docs.rs/bumpalo
+
+extern crate bumpalo;
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::mem;
+
+pub use self::bumpalo::Bump;
+use self::bumpalo::collections::Vec;
+
+use super::{Iter, IterMut, Keys, Values, ValuesMut};
+
+/// A map implemented by searching linearly in a vector allocated from a bump arena.
+///
+/// `LinearMap` itself cannot take an allocator parameter on stable Rust, so this is a separate
+/// type storing a `bumpalo::collections::Vec`. It behaves like
+/// [`LinearMap`](../struct.LinearMap.html) and covers its core API; convert with
+/// `iter().collect()` where the rest is needed. Creating and dropping such maps never touches
+/// the global allocator, which suits short-lived maps built and thrown away in bulk, e.g. once
+/// per frame. Memory is only reclaimed when the arena is reset or dropped.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::bump::{Bump, BumpLinearMap};
+///
+/// let bump = Bump::new();
+/// let mut map = BumpLinearMap::new_in(&bump);
+/// map.insert("a", 1);
+/// map.insert("b", 2);
+/// assert_eq!(map.get("a"), Some(&1));
+/// ```
+pub struct BumpLinearMap<'bump, K, V> {
+    storage: Vec<'bump, (K, V)>,
+}
+
+impl<'bump, K, V> BumpLinearMap<'bump, K, V> {
+    /// Creates an empty map in the given arena. This method does not allocate.
+    pub fn new_in(bump: &'bump Bump) -> Self {
+        BumpLinearMap { storage: Vec::new_in(bump) }
+    }
+
+    /// Creates an empty map in the given arena with the given initial capacity.
+    pub fn with_capacity_in(capacity: usize, bump: &'bump Bump) -> Self {
+        BumpLinearMap { storage: Vec::with_capacity_in(capacity, bump) }
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.storage.capacity()
+    }
+
+    /// Like [`LinearMap::reserve`](../struct.LinearMap.html#method.reserve), allocating from the
+    /// arena.
+    pub fn reserve(&mut self, additional: usize) {
+        self.storage.reserve(additional);
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    /// Like [`LinearMap::clear`](../struct.LinearMap.html#method.clear).
+    pub fn clear(&mut self) {
+        self.storage.clear();
+    }
+
+    /// Like [`LinearMap::retain`](../struct.LinearMap.html#method.retain).
+    pub fn retain<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        self.storage.retain_mut(|(k, v)| keep_fn(k, v));
+    }
+
+    /// Like [`LinearMap::iter`](../struct.LinearMap.html#method.iter).
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.storage.iter())
+    }
+
+    /// Like [`LinearMap::iter_mut`](../struct.LinearMap.html#method.iter_mut).
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(self.storage.iter_mut())
+    }
+
+    /// Like [`LinearMap::keys`](../struct.LinearMap.html#method.keys).
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.iter())
+    }
+
+    /// Like [`LinearMap::values`](../struct.LinearMap.html#method.values).
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(self.iter())
+    }
+
+    /// Like [`LinearMap::values_mut`](../struct.LinearMap.html#method.values_mut).
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(self.iter_mut())
+    }
+}
+
+impl<'bump, K: PartialEq, V> BumpLinearMap<'bump, K, V> {
+    fn position<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<usize> where K: Borrow<Q> {
+        self.storage.iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    /// Like [`LinearMap::get`](../struct.LinearMap.html#method.get).
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        self.position(key).map(|i| &self.storage[i].1)
+    }
+
+    /// Like [`LinearMap::get_mut`](../struct.LinearMap.html#method.get_mut).
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V>
+    where K: Borrow<Q> {
+        let i = self.position(key)?;
+        Some(&mut self.storage[i].1)
+    }
+
+    /// Like [`LinearMap::contains_key`](../struct.LinearMap.html#method.contains_key).
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.position(key).is_some()
+    }
+
+    /// Like [`LinearMap::insert`](../struct.LinearMap.html#method.insert), allocating from the
+    /// arena.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.position(&key) {
+            Some(i) => Some(mem::replace(&mut self.storage[i].1, value)),
+            None => {
+                self.storage.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Like [`LinearMap::remove`](../struct.LinearMap.html#method.remove).
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let i = self.position(key)?;
+        Some(self.storage.swap_remove(i).1)
+    }
+}
+
+impl<'bump, K: Debug, V: Debug> Debug for BumpLinearMap<'bump, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'bump, K: PartialEq, V> Extend<(K, V)> for BumpLinearMap<'bump, K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<'bump, K: PartialEq, V: PartialEq> PartialEq for BumpLinearMap<'bump, K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<'bump, K: Eq, V: Eq> Eq for BumpLinearMap<'bump, K, V> {}
+
+impl<'a, 'bump, K, V> IntoIterator for &'a BumpLinearMap<'bump, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, 'bump, K, V> IntoIterator for &'a mut BumpLinearMap<'bump, K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,319 @@
+//! A string-keyed map storing all of its keys in a single buffer.
+//!
+//! See the [`CompactLinearMap`](struct.CompactLinearMap.html) type for details.
+
+use std::fmt::{self, Debug};
+use std::iter;
+use std::mem;
+use std::ops::Range;
+use std::slice;
+
+/// A map with string keys, implemented by searching linearly in a vector, whose keys are packed
+/// into one contiguous buffer.
+///
+/// Each entry records the range of its key's bytes in the buffer instead of owning a `String`,
+/// so inserting a key does not allocate unless the buffer has to grow, and scanning the keys
+/// touches a single allocation. Keys are given as `&str` and returned as `&str`.
+///
+/// Removing an entry closes the gap its key leaves in the buffer, which shifts the bytes of the
+/// keys stored after it.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::compact::CompactLinearMap;
+///
+/// let mut headers = CompactLinearMap::new();
+/// headers.insert("content-type", "text/html");
+/// headers.insert("content-length", "42");
+/// assert_eq!(headers.get("content-length"), Some(&"42"));
+/// assert_eq!(headers.keys().collect::<Vec<_>>(), ["content-type", "content-length"]);
+/// ```
+pub struct CompactLinearMap<V> {
+    keys: String,
+    entries: Vec<(Range<usize>, V)>,
+}
+
+impl<V> CompactLinearMap<V> {
+    /// Creates an empty map. This method does not allocate.
+    pub const fn new() -> Self {
+        CompactLinearMap { keys: String::new(), entries: Vec::new() }
+    }
+
+    /// Creates an empty map with room for `entries` entries whose keys total `key_bytes` bytes.
+    pub fn with_capacity(entries: usize, key_bytes: usize) -> Self {
+        CompactLinearMap {
+            keys: String::with_capacity(key_bytes),
+            entries: Vec::with_capacity(entries),
+        }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the total length in bytes of the map's keys.
+    pub fn key_bytes(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Clears the map, removing all elements. Keeps the allocated memory for
+    /// reuse.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.entries.clear();
+    }
+
+    /// Shrinks the capacity of the map and of its key buffer as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.keys.shrink_to_fit();
+        self.entries.shrink_to_fit();
+    }
+
+    /// Returns an iterator yielding the map's keys and references to their corresponding values
+    /// in arbitrary order.
+    ///
+    /// The iterator's item type is `(&str, &V)`.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter { keys: &self.keys, iter: self.entries.iter() }
+    }
+
+    /// Returns an iterator yielding the map's keys and mutable references to their corresponding
+    /// values in arbitrary order.
+    ///
+    /// The iterator's item type is `(&str, &mut V)`.
+    pub fn iter_mut(&mut self) -> IterMut<'_, V> {
+        IterMut { keys: &self.keys, iter: self.entries.iter_mut() }
+    }
+
+    /// Returns an iterator yielding the map's keys in arbitrary order.
+    ///
+    /// The iterator's item type is `&str`.
+    pub fn keys(&self) -> Keys<'_, V> {
+        Keys { iter: self.iter() }
+    }
+
+    /// Returns an iterator yielding references to the map's values in arbitrary order.
+    ///
+    /// The iterator's item type is `&V`.
+    pub fn values(&self) -> Values<'_, V> {
+        Values { iter: self.iter() }
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        let keys = self.keys.as_bytes();
+        let key = key.as_bytes();
+        self.entries.iter().position(|(r, _)| r.len() == key.len() && keys[r.clone()] == *key)
+    }
+
+    /// Returns a reference to the value corresponding to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.position(key).map(|i| &self.entries[i].1)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        match self.position(key) {
+            Some(i) => Some(&mut self.entries[i].1),
+            None => None,
+        }
+    }
+
+    /// Checks if the map contains the given key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map, copying the key into the key buffer.
+    ///
+    /// Returns `None` if the map did not contain the given key. Otherwise the key's value is
+    /// replaced with the given value, and the old value is returned.
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        if let Some(i) = self.position(key) {
+            return Some(mem::replace(&mut self.entries[i].1, value));
+        }
+        let start = self.keys.len();
+        self.keys.push_str(key);
+        self.entries.push((start..self.keys.len(), value));
+        None
+    }
+
+    /// Removes the given key from the map and returns its corresponding value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        let i = self.position(key)?;
+        let (range, value) = self.entries.swap_remove(i);
+        let len = range.len();
+        self.keys.replace_range(range.clone(), "");
+        for (r, _) in &mut self.entries {
+            if r.start >= range.end {
+                r.start -= len;
+                r.end -= len;
+            }
+        }
+        Some(value)
+    }
+}
+
+impl<V: Clone> Clone for CompactLinearMap<V> {
+    fn clone(&self) -> Self {
+        CompactLinearMap { keys: self.keys.clone(), entries: self.entries.clone() }
+    }
+}
+
+impl<V: Debug> Debug for CompactLinearMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V> Default for CompactLinearMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: AsRef<str>, V> Extend<(S, V)> for CompactLinearMap<V> {
+    fn extend<I: IntoIterator<Item = (S, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key.as_ref(), value); }
+    }
+}
+
+impl<S: AsRef<str>, V> iter::FromIterator<(S, V)> for CompactLinearMap<V> {
+    fn from_iter<I: IntoIterator<Item = (S, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+impl<V: PartialEq> PartialEq for CompactLinearMap<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<V: Eq> Eq for CompactLinearMap<V> {}
+
+impl<'a, V> IntoIterator for &'a CompactLinearMap<V> {
+    type Item = (&'a str, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    fn into_iter(self) -> Iter<'a, V> {
+        self.iter()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a mut CompactLinearMap<V> {
+    type Item = (&'a str, &'a mut V);
+    type IntoIter = IterMut<'a, V>;
+
+    fn into_iter(self) -> IterMut<'a, V> {
+        self.iter_mut()
+    }
+}
+
+/// An iterator yielding the keys of a `CompactLinearMap` and references to their values.
+///
+/// See [`CompactLinearMap::iter`](struct.CompactLinearMap.html#method.iter) for details.
+pub struct Iter<'a, V: 'a> {
+    keys: &'a str,
+    iter: slice::Iter<'a, (Range<usize>, V)>,
+}
+
+/// An iterator yielding the keys of a `CompactLinearMap` and mutable references to their values.
+///
+/// See [`CompactLinearMap::iter_mut`](struct.CompactLinearMap.html#method.iter_mut) for details.
+pub struct IterMut<'a, V: 'a> {
+    keys: &'a str,
+    iter: slice::IterMut<'a, (Range<usize>, V)>,
+}
+
+/// An iterator yielding the keys of a `CompactLinearMap`.
+///
+/// See [`CompactLinearMap::keys`](struct.CompactLinearMap.html#method.keys) for details.
+pub struct Keys<'a, V: 'a> {
+    iter: Iter<'a, V>,
+}
+
+/// An iterator yielding references to the values of a `CompactLinearMap`.
+///
+/// See [`CompactLinearMap::values`](struct.CompactLinearMap.html#method.values) for details.
+pub struct Values<'a, V: 'a> {
+    iter: Iter<'a, V>,
+}
+
+macro_rules! impl_compact_iter {($typ:ident, $item:ty, $map:expr) => {
+    impl<'a, V> Iterator for $typ<'a, V> {
+        type Item = $item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let keys = self.keys;
+            self.iter.next().map(|e| $map(keys, e))
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+
+    impl<'a, V> DoubleEndedIterator for $typ<'a, V> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            let keys = self.keys;
+            self.iter.next_back().map(|e| $map(keys, e))
+        }
+    }
+
+    impl<'a, V> ExactSizeIterator for $typ<'a, V> {
+        fn len(&self) -> usize {
+            self.iter.len()
+        }
+    }
+}}
+impl_compact_iter!{Iter, (&'a str, &'a V), |k: &'a str, e: &'a (Range<usize>, V)| (&k[e.0.clone()], &e.1)}
+impl_compact_iter!{IterMut, (&'a str, &'a mut V), |k: &'a str, e: &'a mut (Range<usize>, V)| (&k[e.0.clone()], &mut e.1)}
+
+macro_rules! impl_proj_iter {($typ:ident, $item:ty, $map:expr) => {
+    impl<'a, V> Iterator for $typ<'a, V> {
+        type Item = $item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.iter.next().map($map)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+
+    impl<'a, V> DoubleEndedIterator for $typ<'a, V> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.iter.next_back().map($map)
+        }
+    }
+
+    impl<'a, V> ExactSizeIterator for $typ<'a, V> {
+        fn len(&self) -> usize {
+            self.iter.len()
+        }
+    }
+}}
+impl_proj_iter!{Keys, &'a str, |e: (&'a str, &'a V)| e.0}
+impl_proj_iter!{Values, &'a V, |e: (&'a str, &'a V)| e.1}
+
+impl<'a, V> Clone for Iter<'a, V> {
+    fn clone(&self) -> Self {
+        Iter { keys: self.keys, iter: self.iter.clone() }
+    }
+}
@@ -0,0 +1,322 @@
+//! A map whose keys are compared by a user-supplied comparator instead of `Eq`.
+//!
+//! See the [`LinearMapBy`](struct.LinearMapBy.html) type for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::mem;
+use std::ops::{self, Deref};
+
+use super::{LinearMap, Drain, IntoIter, Iter, IterMut, ValuesMut};
+
+/// An equivalence relation on keys, used by [`LinearMapBy`](struct.LinearMapBy.html) in place
+/// of `Eq`.
+///
+/// Implementations must behave like an equivalence relation: reflexive, symmetric and
+/// transitive. Closures of type `Fn(&K, &K) -> bool` implement this trait.
+pub trait KeyCompare<K: ?Sized> {
+    /// Returns `true` if the two keys are to be considered the same key.
+    fn eq(&self, a: &K, b: &K) -> bool;
+}
+
+impl<K: ?Sized, F> KeyCompare<K> for F where F: Fn(&K, &K) -> bool {
+    fn eq(&self, a: &K, b: &K) -> bool {
+        self(a, b)
+    }
+}
+
+/// A comparator that compares keys with their `Eq` implementation.
+///
+/// `LinearMapBy<K, V, EqCompare>` behaves exactly like `LinearMap<K, V>`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EqCompare;
+
+impl<K: ?Sized + Eq> KeyCompare<K> for EqCompare {
+    fn eq(&self, a: &K, b: &K) -> bool {
+        a == b
+    }
+}
+
+/// A comparator for string keys that ignores ASCII case.
+///
+/// Any key type implementing `AsRef<str>` can be compared, so a map keyed by `String` can be
+/// queried with a `&str`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AsciiCaseInsensitive;
+
+impl<S: ?Sized + AsRef<str>> KeyCompare<S> for AsciiCaseInsensitive {
+    fn eq(&self, a: &S, b: &S) -> bool {
+        a.as_ref().eq_ignore_ascii_case(b.as_ref())
+    }
+}
+
+/// A comparator for string keys that ignores Unicode case.
+///
+/// Strings are compared by their lowercase mappings, character by character. This covers the
+/// common cases but is not full Unicode case folding: for instance `"ß"` and `"SS"` are
+/// different keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UnicodeCaseInsensitive;
+
+impl<S: ?Sized + AsRef<str>> KeyCompare<S> for UnicodeCaseInsensitive {
+    fn eq(&self, a: &S, b: &S) -> bool {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        a == b || a.chars().flat_map(char::to_lowercase).eq(b.chars().flat_map(char::to_lowercase))
+    }
+}
+
+/// A map whose string keys are compared ignoring ASCII case, e.g. for HTTP header names.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::compare::AsciiCaseInsensitiveMap;
+///
+/// let mut headers = AsciiCaseInsensitiveMap::new();
+/// headers.insert("Content-Type".to_string(), "text/plain");
+/// assert_eq!(headers.get("content-type"), Some(&"text/plain"));
+/// ```
+pub type AsciiCaseInsensitiveMap<K, V> = LinearMapBy<K, V, AsciiCaseInsensitive>;
+
+/// A map whose string keys are compared ignoring Unicode case.
+///
+/// See [`UnicodeCaseInsensitive`](struct.UnicodeCaseInsensitive.html) for the exact semantics.
+pub type UnicodeCaseInsensitiveMap<K, V> = LinearMapBy<K, V, UnicodeCaseInsensitive>;
+
+/// A map implemented by searching linearly in a vector, comparing keys with a comparator.
+///
+/// This is like [`LinearMap`](../struct.LinearMap.html), except that keys are compared with
+/// the comparator `C` rather than with `Eq`. This allows case-insensitive or otherwise
+/// normalized lookups without wrapping every key in a newtype. The first key inserted for a
+/// given equivalence class is the one stored in the map.
+///
+/// The map dereferences to the underlying `LinearMap`, so `len`, `iter` and the other methods
+/// that do not look keys up are available. Key lookups must go through the methods defined here:
+/// those reached through `Deref`, such as `LinearMap::get_key_value`, compare keys with
+/// `PartialEq` and ignore the comparator.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::compare::LinearMapBy;
+///
+/// // keys that differ only in sign are the same key.
+/// let mut map = LinearMapBy::with_comparator(|a: &i32, b: &i32| a.abs() == b.abs());
+/// map.insert(3, "three");
+/// assert_eq!(map.get(&-3), Some(&"three"));
+/// assert_eq!(map.insert(-3, "minus three"), Some("three"));
+/// assert_eq!(map.len(), 1);
+/// assert_eq!(map.keys().next(), Some(&3));
+/// ```
+pub struct LinearMapBy<K, V, C> {
+    map: LinearMap<K, V>,
+    cmp: C,
+}
+
+impl<K, V, C: Default> LinearMapBy<K, V, C> {
+    /// Creates an empty map using the default comparator. This method does not allocate.
+    pub fn new() -> Self {
+        Self::with_comparator(C::default())
+    }
+
+    /// Creates an empty map with the given initial capacity, using the default comparator.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_comparator(capacity, C::default())
+    }
+}
+
+impl<K, V, C> LinearMapBy<K, V, C> {
+    /// Creates an empty map using the given comparator. This method does not allocate.
+    pub fn with_comparator(cmp: C) -> Self {
+        LinearMapBy { map: LinearMap::new(), cmp }
+    }
+
+    /// Creates an empty map with the given initial capacity, using the given comparator.
+    pub fn with_capacity_and_comparator(capacity: usize, cmp: C) -> Self {
+        LinearMapBy { map: LinearMap::with_capacity(capacity), cmp }
+    }
+
+    /// Returns a reference to the map's comparator.
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Like [`LinearMap::reserve`](../struct.LinearMap.html#method.reserve).
+    pub fn reserve(&mut self, additional: usize) {
+        self.map.reserve(additional);
+    }
+
+    /// Like [`LinearMap::shrink_to_fit`](../struct.LinearMap.html#method.shrink_to_fit).
+    pub fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit();
+    }
+
+    /// Like [`LinearMap::clear`](../struct.LinearMap.html#method.clear).
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Like [`LinearMap::retain`](../struct.LinearMap.html#method.retain).
+    pub fn retain<F>(&mut self, keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        self.map.retain(keep_fn);
+    }
+
+    /// Like [`LinearMap::drain`](../struct.LinearMap.html#method.drain).
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        self.map.drain()
+    }
+
+    /// Like [`LinearMap::iter_mut`](../struct.LinearMap.html#method.iter_mut).
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Like [`LinearMap::values_mut`](../struct.LinearMap.html#method.values_mut).
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.map.values_mut()
+    }
+
+    fn position<Q: ?Sized>(&self, key: &Q) -> Option<usize>
+    where K: Borrow<Q>, C: KeyCompare<Q> {
+        let cmp = &self.cmp;
+        self.map.storage.iter().position(|(k, _)| cmp.eq(k.borrow(), key))
+    }
+
+    /// Like [`LinearMap::get`](../struct.LinearMap.html#method.get), comparing keys with the
+    /// comparator.
+    ///
+    /// The given key may be any borrowed form of the map's key type, as long as the comparator
+    /// can compare it.
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&V>
+    where K: Borrow<Q>, C: KeyCompare<Q> {
+        self.position(key).map(|i| &self.map.storage[i].1)
+    }
+
+    /// Like [`LinearMap::get_mut`](../struct.LinearMap.html#method.get_mut), comparing keys with
+    /// the comparator.
+    pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where K: Borrow<Q>, C: KeyCompare<Q> {
+        self.position(key).map(move |i| &mut self.map.storage[i].1)
+    }
+
+    /// Like [`LinearMap::contains_key`](../struct.LinearMap.html#method.contains_key), comparing
+    /// keys with the comparator.
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where K: Borrow<Q>, C: KeyCompare<Q> {
+        self.position(key).is_some()
+    }
+
+    /// Like [`LinearMap::insert`](../struct.LinearMap.html#method.insert), comparing keys with the
+    /// comparator. When the key is already present, the stored key is kept.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where C: KeyCompare<K> {
+        match self.position(&key) {
+            Some(i) => Some(mem::replace(&mut self.map.storage[i].1, value)),
+            None => {
+                self.map.invalidate_positions();
+                self.map.storage.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Like [`LinearMap::remove`](../struct.LinearMap.html#method.remove), comparing keys with the
+    /// comparator.
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where K: Borrow<Q>, C: KeyCompare<Q> {
+        let i = self.position(key)?;
+        self.map.invalidate_positions();
+        Some(self.map.storage.swap_remove(i).1)
+    }
+}
+
+impl<K, V, C> Deref for LinearMapBy<K, V, C> {
+    type Target = LinearMap<K, V>;
+
+    fn deref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V, C> AsRef<LinearMap<K, V>> for LinearMapBy<K, V, C> {
+    fn as_ref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K: Clone, V: Clone, C: Clone> Clone for LinearMapBy<K, V, C> {
+    fn clone(&self) -> Self {
+        LinearMapBy { map: self.map.clone(), cmp: self.cmp.clone() }
+    }
+}
+
+impl<K: Debug, V: Debug, C> Debug for LinearMapBy<K, V, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K, V, C: Default> Default for LinearMapBy<K, V, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, C: KeyCompare<K>> Extend<(K, V)> for LinearMapBy<K, V, C> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K, V, C: KeyCompare<K> + Default> iter::FromIterator<(K, V)> for LinearMapBy<K, V, C> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+impl<'a, K: Borrow<Q>, V, C: KeyCompare<Q>, Q: ?Sized> ops::Index<&'a Q> for LinearMapBy<K, V, C> {
+    type Output = V;
+
+    fn index(&self, key: &'a Q) -> &V {
+        self.get(key).expect("key not found")
+    }
+}
+
+impl<K, V: PartialEq, C: KeyCompare<K>> PartialEq for LinearMapBy<K, V, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K, V: Eq, C: KeyCompare<K>> Eq for LinearMapBy<K, V, C> {}
+
+impl<K, V, C> IntoIterator for LinearMapBy<K, V, C> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K, V, C> IntoIterator for &'a LinearMapBy<K, V, C> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, C> IntoIterator for &'a mut LinearMapBy<K, V, C> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,59 @@
+//! Optional `HashMap`-compatible constructors, for swapping `HashMap` for `LinearMap` through a
+//! type alias.
+//!
+//! `LinearMap` does not hash its keys, so these constructors accept and ignore any hasher. Code
+//! written against `HashMap` that passes a hasher explicitly keeps compiling when the map type is
+//! replaced, which makes it cheap to benchmark both.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::hash_map::RandomState;
+//! use linear_map::LinearMap;
+//!
+//! // Was `type Registry<K, V> = std::collections::HashMap<K, V>;`
+//! type Registry<K, V> = LinearMap<K, V>;
+//!
+//! let mut registry: Registry<&str, u32> =
+//!     Registry::with_capacity_and_hasher(4, RandomState::new());
+//! registry.insert("a", 1);
+//! assert_eq!(registry.get("a"), Some(&1));
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::BuildHasher;
+
+use super::LinearMap;
+
+/// A stand-in for a map's hash builder, returned by [`LinearMap::hasher`].
+///
+/// It builds std's default hasher, so it can be passed wherever a `BuildHasher` is expected.
+///
+/// [`LinearMap::hasher`]: ../struct.LinearMap.html#method.hasher
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DefaultHashBuilder;
+
+impl BuildHasher for DefaultHashBuilder {
+    type Hasher = DefaultHasher;
+
+    fn build_hasher(&self) -> DefaultHasher {
+        DefaultHasher::new()
+    }
+}
+
+impl<K, V> LinearMap<K, V> {
+    /// Creates an empty map, ignoring the given hash builder. This method does not allocate.
+    pub fn with_hasher<S>(_hash_builder: S) -> Self {
+        LinearMap::new()
+    }
+
+    /// Creates an empty map with the given initial capacity, ignoring the given hash builder.
+    pub fn with_capacity_and_hasher<S>(capacity: usize, _hash_builder: S) -> Self {
+        LinearMap::with_capacity(capacity)
+    }
+
+    /// Returns a placeholder for the map's hash builder; `LinearMap` does not hash its keys.
+    pub fn hasher(&self) -> &DefaultHashBuilder {
+        &DefaultHashBuilder
+    }
+}
@@ -0,0 +1,231 @@
+//! A multiset counting occurrences of keys.
+//!
+//! See the [`LinearCounter`](struct.LinearCounter.html) type for details.
+
+use std::borrow::Borrow;
+use std::cmp;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use super::{LinearMap, IntoIter, Iter, Keys};
+
+/// A counter of key occurrences, implemented by searching linearly in a vector.
+///
+/// Only keys with a positive count are stored: decrementing a key to zero removes it. Counters
+/// can be added and subtracted like multisets; subtraction saturates at zero.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::counter::LinearCounter;
+///
+/// let words: LinearCounter<&str> = "the cat saw the dog and the bird".split(' ').collect();
+/// assert_eq!(words.count("the"), 3);
+/// assert_eq!(words.count("fish"), 0);
+/// assert_eq!(words.most_common(1), [(&"the", 3)]);
+/// ```
+pub struct LinearCounter<K> {
+    map: LinearMap<K, u64>,
+}
+
+impl<K: PartialEq> LinearCounter<K> {
+    /// Creates an empty counter. This method does not allocate.
+    pub const fn new() -> Self {
+        LinearCounter { map: LinearMap::new() }
+    }
+
+    /// Creates an empty counter with capacity for the given number of distinct keys.
+    pub fn with_capacity(capacity: usize) -> Self {
+        LinearCounter { map: LinearMap::with_capacity(capacity) }
+    }
+
+    /// Returns the number of distinct keys with a positive count.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if no key has a positive count.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the sum of all counts.
+    pub fn total(&self) -> u64 {
+        self.map.values().sum()
+    }
+
+    /// Removes all keys. Keeps the allocated memory for reuse.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns an iterator yielding references to the keys and their counts in arbitrary order.
+    ///
+    /// The iterator's item type is `(&K, &u64)`.
+    pub fn iter(&self) -> Iter<'_, K, u64> {
+        self.map.iter()
+    }
+
+    /// Returns an iterator yielding references to the counted keys in arbitrary order.
+    ///
+    /// The iterator's item type is `&K`.
+    pub fn keys(&self) -> Keys<'_, K, u64> {
+        self.map.keys()
+    }
+
+    /// Returns the count of the key equal to the given key, which is zero if it is absent.
+    pub fn count<Q: ?Sized + PartialEq>(&self, key: &Q) -> u64 where K: Borrow<Q> {
+        self.map.get(key).cloned().unwrap_or(0)
+    }
+
+    /// Increments the count of the given key by one and returns the new count.
+    pub fn increment(&mut self, key: K) -> u64 {
+        self.increment_by(key, 1)
+    }
+
+    /// Increments the count of the given key by `n` and returns the new count.
+    pub fn increment_by(&mut self, key: K, n: u64) -> u64 {
+        if n == 0 {
+            return self.count(&key);
+        }
+        let count = self.map.entry(key).or_insert(0);
+        *count += n;
+        *count
+    }
+
+    /// Decrements the count of the key equal to the given key by one and returns the new count,
+    /// removing the key if it reaches zero.
+    pub fn decrement<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> u64 where K: Borrow<Q> {
+        self.decrement_by(key, 1)
+    }
+
+    /// Decrements the count of the key equal to the given key by `n`, saturating at zero, and
+    /// returns the new count. The key is removed if its count reaches zero.
+    pub fn decrement_by<Q: ?Sized + PartialEq>(&mut self, key: &Q, n: u64) -> u64
+    where K: Borrow<Q> {
+        let count = match self.map.get_mut(key) {
+            Some(count) => { *count = count.saturating_sub(n); *count }
+            None => return 0,
+        };
+        if count == 0 {
+            self.map.remove(key);
+        }
+        count
+    }
+
+    /// Removes the key equal to the given key and returns its count, which is zero if it was
+    /// absent.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> u64 where K: Borrow<Q> {
+        self.map.remove(key).unwrap_or(0)
+    }
+
+    /// Returns the `n` keys with the highest counts, most common first.
+    ///
+    /// Keys with equal counts are returned in arbitrary order. If `n` is at least the number of
+    /// keys, all keys are returned.
+    pub fn most_common(&self, n: usize) -> Vec<(&K, u64)> {
+        let mut counts: Vec<(&K, u64)> = self.map.iter().map(|(k, &c)| (k, c)).collect();
+        counts.sort_by_key(|&(_, c)| cmp::Reverse(c));
+        counts.truncate(n);
+        counts
+    }
+
+    /// Returns a reference to the underlying map from keys to counts.
+    pub fn as_map(&self) -> &LinearMap<K, u64> {
+        &self.map
+    }
+
+    /// Consumes the counter and returns the underlying map from keys to counts.
+    pub fn into_map(self) -> LinearMap<K, u64> {
+        self.map
+    }
+}
+
+impl<K: Clone> Clone for LinearCounter<K> {
+    fn clone(&self) -> Self {
+        LinearCounter { map: self.map.clone() }
+    }
+}
+
+impl<K: PartialEq + Debug> Debug for LinearCounter<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: PartialEq> Default for LinearCounter<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq> Extend<K> for LinearCounter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, keys: I) {
+        for key in keys { self.increment(key); }
+    }
+}
+
+impl<K: PartialEq> iter::FromIterator<K> for LinearCounter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(keys: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(keys);
+        counter
+    }
+}
+
+impl<K: PartialEq> PartialEq for LinearCounter<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Eq> Eq for LinearCounter<K> {}
+
+impl<K: PartialEq> AddAssign for LinearCounter<K> {
+    fn add_assign(&mut self, other: Self) {
+        for (key, n) in other.map { self.increment_by(key, n); }
+    }
+}
+
+impl<K: PartialEq> Add for LinearCounter<K> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<K: PartialEq> SubAssign for LinearCounter<K> {
+    fn sub_assign(&mut self, other: Self) {
+        for (key, n) in other.map { self.decrement_by(&key, n); }
+    }
+}
+
+impl<K: PartialEq> Sub for LinearCounter<K> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<K: PartialEq> IntoIterator for LinearCounter<K> {
+    type Item = (K, u64);
+    type IntoIter = IntoIter<K, u64>;
+
+    fn into_iter(self) -> IntoIter<K, u64> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K: PartialEq> IntoIterator for &'a LinearCounter<K> {
+    type Item = (&'a K, &'a u64);
+    type IntoIter = Iter<'a, K, u64>;
+
+    fn into_iter(self) -> Iter<'a, K, u64> {
+        self.iter()
+    }
+}
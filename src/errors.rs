@@ -0,0 +1,203 @@
+//! Error types returned by the fallible operations of this crate.
+//!
+//! Each error hands back the key (and value, where one was given) that the operation rejected,
+//! so that nothing passed in by value is lost on failure.
+
+use std::collections::TryReserveError;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+
+use super::OccupiedEntry;
+
+/// The error returned by [`LinearMap::rename_key`](../struct.LinearMap.html#method.rename_key).
+///
+/// Both variants hand back the new key that was not inserted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RenameError<K> {
+    /// The map contains no key equal to the old key.
+    NotFound(K),
+    /// The map already contains a key equal to the new key.
+    KeyExists(K),
+}
+
+impl<K> RenameError<K> {
+    /// Returns the new key that was not inserted.
+    pub fn into_key(self) -> K {
+        match self {
+            RenameError::NotFound(k) | RenameError::KeyExists(k) => k,
+        }
+    }
+}
+
+impl<K> Display for RenameError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RenameError::NotFound(_) => f.write_str("key to rename not found"),
+            RenameError::KeyExists(_) => f.write_str("new key already exists"),
+        }
+    }
+}
+
+impl<K: Debug> Error for RenameError<K> {}
+
+/// The error returned when a key-value pair is rejected because its key is already present.
+///
+/// Returned by [`LinearMap::try_from_iter`](../struct.LinearMap.html#method.try_from_iter).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateKeyError<K, V> {
+    /// The rejected key.
+    pub key: K,
+    /// The value that was to be inserted with the key.
+    pub value: V,
+}
+
+impl<K, V> DuplicateKeyError<K, V> {
+    /// Returns the rejected key-value pair.
+    pub fn into_inner(self) -> (K, V) {
+        (self.key, self.value)
+    }
+}
+
+impl<K, V> Display for DuplicateKeyError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("duplicate key")
+    }
+}
+
+impl<K: Debug, V: Debug> Error for DuplicateKeyError<K, V> {}
+
+/// The error returned when inserting under a key that is already present.
+///
+/// Returned by [`LinearMap::try_insert`](../struct.LinearMap.html#method.try_insert). Holds a view
+/// of the occupied entry, which can be used to inspect or update the existing value.
+pub struct OccupiedError<'a, K: 'a, V: 'a> {
+    /// The entry in the map that was already occupied.
+    pub entry: OccupiedEntry<'a, K, V>,
+    /// The value that was not inserted.
+    pub value: V,
+}
+
+impl<'a, K: Debug, V: Debug> Debug for OccupiedError<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OccupiedError")
+            .field("key", self.entry.key())
+            .field("old_value", self.entry.get())
+            .field("new_value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Display for OccupiedError<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to insert {:?}, key {:?} already exists with value {:?}",
+               self.value, self.entry.key(), self.entry.get())
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Error for OccupiedError<'a, K, V> {}
+
+/// The error returned when adding a value to the value of an equal key overflows.
+///
+/// Returned by [`LinearMap::merge_add_checked`](../struct.LinearMap.html#method.merge_add_checked).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverflowError<K, V> {
+    /// The key whose sum overflowed.
+    pub key: K,
+    /// The value that could not be added.
+    pub value: V,
+}
+
+impl<K, V> OverflowError<K, V> {
+    /// Returns the rejected key-value pair.
+    pub fn into_inner(self) -> (K, V) {
+        (self.key, self.value)
+    }
+}
+
+impl<K, V> Display for OverflowError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("arithmetic overflow when merging values")
+    }
+}
+
+impl<K: Debug, V: Debug> Error for OverflowError<K, V> {}
+
+/// The error returned when a key-value pair cannot be inserted because memory for it could not be
+/// allocated.
+///
+/// Returned by
+/// [`NoPanicLinearMap::insert`](../no_panic/struct.NoPanicLinearMap.html#method.insert).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReserveError<K, V> {
+    /// The key that was not inserted.
+    pub key: K,
+    /// The value that was not inserted.
+    pub value: V,
+    /// The reason the allocation failed.
+    pub error: TryReserveError,
+}
+
+impl<K, V> ReserveError<K, V> {
+    /// Returns the rejected key-value pair.
+    pub fn into_inner(self) -> (K, V) {
+        (self.key, self.value)
+    }
+}
+
+impl<K, V> Display for ReserveError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl<K: Debug, V: Debug> Error for ReserveError<K, V> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The error returned when inserting under a key that is not in a map's fixed key table.
+///
+/// Returned by [`SharedKeysMap::insert`](../shared/struct.SharedKeysMap.html#method.insert).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownKeyError<V> {
+    /// The value that was not inserted.
+    pub value: V,
+}
+
+impl<V> UnknownKeyError<V> {
+    /// Returns the value that was not inserted.
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
+
+impl<V> Display for UnknownKeyError<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("key not in the key table")
+    }
+}
+
+impl<V: Debug> Error for UnknownKeyError<V> {}
+
+/// The error returned when a struct cannot be rebuilt from a map of its fields.
+///
+/// Returned by [`ToLinearMap::from_linear_map`](../fields/trait.ToLinearMap.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldError {
+    /// The map has no entry for the named field.
+    Missing(&'static str),
+    /// The map's value for the named field has the wrong type or is out of range.
+    Invalid(&'static str),
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FieldError::Missing(name) => write!(f, "missing field `{}`", name),
+            FieldError::Invalid(name) => write!(f, "invalid value for field `{}`", name),
+        }
+    }
+}
+
+impl Error for FieldError {}
@@ -0,0 +1,265 @@
+//! A map whose entries can expire after a time-to-live.
+//!
+//! See the [`ExpiringLinearMap`](struct.ExpiringLinearMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::ops::Add;
+use std::time::Instant;
+
+use super::LinearMap;
+use super::Iter as MapIter;
+
+/// A source of the current time for an [`ExpiringLinearMap`](struct.ExpiringLinearMap.html).
+///
+/// Closures of type `Fn() -> I` implement this trait, which makes it easy to drive the map from
+/// a custom or simulated clock.
+pub trait Clock {
+    /// The type of the points in time returned by the clock.
+    type Instant: Copy + Ord;
+
+    /// Returns the current time.
+    fn now(&self) -> Self::Instant;
+}
+
+impl<I: Copy + Ord, F: Fn() -> I> Clock for F {
+    type Instant = I;
+
+    fn now(&self) -> I {
+        self()
+    }
+}
+
+/// The default clock, reading `std::time::Instant::now()`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    type Instant = Instant;
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A map implemented by searching linearly in a vector, whose entries may carry an expiry time.
+///
+/// Entries inserted with [`insert_with_ttl`](#method.insert_with_ttl) or
+/// [`insert_until`](#method.insert_until) expire once the map's clock reaches their deadline;
+/// entries inserted with [`insert`](#method.insert) never expire. Expired entries are invisible
+/// to all lookups and iterators. They are removed lazily when accessed mutably, or all at once by
+/// [`purge_expired`](#method.purge_expired).
+///
+/// Lookups search linearly, so they run in `O(n)` time, and read the clock once.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use linear_map::expiring::ExpiringLinearMap;
+///
+/// let mut cache = ExpiringLinearMap::new();
+/// cache.insert_with_ttl("session", 42, Duration::from_secs(60));
+/// cache.insert("config", 7);
+/// assert_eq!(cache.get("session"), Some(&42));
+/// assert_eq!(cache.purge_expired(), 0);
+/// ```
+pub struct ExpiringLinearMap<K, V, C: Clock = SystemClock> {
+    map: LinearMap<K, (V, Option<C::Instant>)>,
+    clock: C,
+}
+
+impl<K: PartialEq, V> ExpiringLinearMap<K, V> {
+    /// Creates an empty map using the system clock. This method does not allocate.
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl<K: PartialEq, V, C: Clock> ExpiringLinearMap<K, V, C> {
+    /// Creates an empty map using the given clock. This method does not allocate.
+    pub fn with_clock(clock: C) -> Self {
+        ExpiringLinearMap { map: LinearMap::new(), clock }
+    }
+
+    /// Returns a reference to the map's clock.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns the number of unexpired entries in the map. This reads the clock and scans every
+    /// entry.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns true if the map has no unexpired entries.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Removes all entries, expired or not, keeping the allocated memory for reuse.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Removes all expired entries from the map and returns how many were removed.
+    pub fn purge_expired(&mut self) -> usize {
+        let now = self.clock.now();
+        let before = self.map.len();
+        self.map.retain(|_, v| live(v.1, now));
+        before - self.map.len()
+    }
+
+    /// Like [`LinearMap::iter`](../struct.LinearMap.html#method.iter), skipping the entries that
+    /// have expired by the time this is called.
+    pub fn iter(&self) -> Iter<'_, K, V, C::Instant> {
+        Iter { iter: self.map.iter(), now: self.clock.now() }
+    }
+
+    /// Like [`LinearMap::get`](../struct.LinearMap.html#method.get), unless the key's entry has
+    /// expired.
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        let now = self.clock.now();
+        match self.map.get(key) {
+            Some(&(ref v, expiry)) if live(expiry, now) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Like [`LinearMap::get_mut`](../struct.LinearMap.html#method.get_mut), unless the key's entry
+    /// has expired, in which case the entry is removed.
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+        if self.expire(key) {
+            return None;
+        }
+        self.map.get_mut(key).map(|e| &mut e.0)
+    }
+
+    /// Like [`LinearMap::contains_key`](../struct.LinearMap.html#method.contains_key), ignoring
+    /// expired entries.
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.get(key).is_some()
+    }
+
+    /// Returns the expiry time of the given key, or `None` if the key is absent or expired.
+    ///
+    /// The inner option is `None` for an entry that never expires.
+    pub fn expiry<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<Option<C::Instant>>
+    where K: Borrow<Q> {
+        let now = self.clock.now();
+        match self.map.get(key) {
+            Some(&(_, expiry)) if live(expiry, now) => Some(expiry),
+            _ => None,
+        }
+    }
+
+    /// Inserts a key-value pair that never expires.
+    ///
+    /// Returns the previous value for the key, unless it had expired.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert_entry(key, value, None)
+    }
+
+    /// Inserts a key-value pair that expires once `ttl` has elapsed on the map's clock.
+    ///
+    /// Returns the previous value for the key, unless it had expired.
+    pub fn insert_with_ttl<D>(&mut self, key: K, value: V, ttl: D) -> Option<V>
+    where C::Instant: Add<D, Output = C::Instant> {
+        let deadline = self.clock.now() + ttl;
+        self.insert_entry(key, value, Some(deadline))
+    }
+
+    /// Inserts a key-value pair that expires once the map's clock reaches `deadline`.
+    ///
+    /// Returns the previous value for the key, unless it had expired.
+    pub fn insert_until(&mut self, key: K, value: V, deadline: C::Instant) -> Option<V> {
+        self.insert_entry(key, value, Some(deadline))
+    }
+
+    /// Like [`LinearMap::remove`](../struct.LinearMap.html#method.remove). An expired entry is
+    /// removed too, but its value is not returned.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let now = self.clock.now();
+        match self.map.remove(key) {
+            Some((v, expiry)) if live(expiry, now) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn insert_entry(&mut self, key: K, value: V, expiry: Option<C::Instant>) -> Option<V> {
+        let now = self.clock.now();
+        match self.map.insert(key, (value, expiry)) {
+            Some((v, old_expiry)) if live(old_expiry, now) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Removes the entry for `key` if it has expired, returning whether it did.
+    fn expire<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> bool where K: Borrow<Q> {
+        let now = self.clock.now();
+        match self.map.get(key) {
+            Some(&(_, expiry)) if !live(expiry, now) => {
+                self.map.remove(key);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn live<I: Ord>(expiry: Option<I>, now: I) -> bool {
+    match expiry {
+        Some(deadline) => now < deadline,
+        None => true,
+    }
+}
+
+impl<K: PartialEq, V> Default for ExpiringLinearMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq + Debug, V: Debug, C: Clock> Debug for ExpiringLinearMap<K, V, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator yielding references to an `ExpiringLinearMap`'s unexpired keys and their
+/// corresponding values.
+///
+/// See [`ExpiringLinearMap::iter`](struct.ExpiringLinearMap.html#method.iter) for details.
+pub struct Iter<'a, K: 'a, V: 'a, I: 'a> {
+    iter: MapIter<'a, K, (V, Option<I>)>,
+    now: I,
+}
+
+impl<'a, K, V, I: Copy + Ord> Iterator for Iter<'a, K, V, I> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let now = self.now;
+        self.iter.by_ref().find(|e| live((e.1).1, now)).map(|(k, v)| (k, &v.0))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, K, V, I: Copy> Clone for Iter<'a, K, V, I> {
+    fn clone(&self) -> Self {
+        Iter { iter: self.iter.clone(), now: self.now }
+    }
+}
+
+impl<'a, K: PartialEq, V, C: Clock> IntoIterator for &'a ExpiringLinearMap<K, V, C> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, C::Instant>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,206 @@
+//! Optional C interface to concretely typed maps.
+//!
+//! Two map types are exposed to C through opaque pointers: [`LinearMapU64`], mapping `u64` to
+//! `u64`, and [`LinearMapCStr`], mapping nul-terminated strings to nul-terminated strings. Each
+//! map is created by its `*_new` function, must be released by its `*_free` function, and may only
+//! be used from one thread at a time. A C header declaring these functions is shipped as
+//! `include/linear_map.h`.
+//!
+//! The functions are exported unmangled, so they are visible from C once this crate is linked into
+//! a `staticlib` or `cdylib`.
+//!
+//! [`LinearMapU64`]: type.LinearMapU64.html
+//! [`LinearMapCStr`]: type.LinearMapCStr.html
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use super::LinearMap;
+
+/// A map from `u64` to `u64`, handed to C as an opaque pointer.
+pub type LinearMapU64 = LinearMap<u64, u64>;
+
+/// A map from strings to strings, handed to C as an opaque pointer.
+pub type LinearMapCStr = LinearMap<CString, CString>;
+
+/// Creates an empty `u64` map.
+#[no_mangle]
+pub extern "C" fn linear_map_u64_new() -> *mut LinearMapU64 {
+    Box::into_raw(Box::default())
+}
+
+/// Releases a `u64` map. Does nothing if `map` is null.
+///
+/// # Safety
+///
+/// `map` must be null or a map returned by `linear_map_u64_new` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_u64_free(map: *mut LinearMapU64) {
+    if !map.is_null() {
+        drop(Box::from_raw(map));
+    }
+}
+
+/// Returns the number of entries in a `u64` map.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_u64_new`.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_u64_len(map: *const LinearMapU64) -> usize {
+    (*map).len()
+}
+
+/// Inserts a key-value pair into a `u64` map.
+///
+/// Returns `true` if the key was already present, in which case its previous value is written to
+/// `old_value` unless that is null.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_u64_new`, and `old_value` must be null or
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_u64_insert(map: *mut LinearMapU64, key: u64, value: u64,
+                                               old_value: *mut u64) -> bool {
+    write_found((*map).insert(key, value), old_value)
+}
+
+/// Looks up a key in a `u64` map.
+///
+/// Returns `true` if the key is present, in which case its value is written to `value` unless
+/// that is null.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_u64_new`, and `value` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_u64_get(map: *const LinearMapU64, key: u64, value: *mut u64)
+    -> bool {
+    write_found((*map).get(&key).copied(), value)
+}
+
+/// Removes a key from a `u64` map.
+///
+/// Returns `true` if the key was present, in which case its value is written to `value` unless
+/// that is null.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_u64_new`, and `value` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_u64_remove(map: *mut LinearMapU64, key: u64, value: *mut u64)
+    -> bool {
+    write_found((*map).remove(&key), value)
+}
+
+/// Removes all entries from a `u64` map, keeping its memory for reuse.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_u64_new`.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_u64_clear(map: *mut LinearMapU64) {
+    (*map).clear();
+}
+
+unsafe fn write_found(found: Option<u64>, out: *mut u64) -> bool {
+    match found {
+        Some(v) => {
+            if !out.is_null() {
+                *out = v;
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Creates an empty string map.
+#[no_mangle]
+pub extern "C" fn linear_map_cstr_new() -> *mut LinearMapCStr {
+    Box::into_raw(Box::default())
+}
+
+/// Releases a string map and all of its strings. Does nothing if `map` is null.
+///
+/// # Safety
+///
+/// `map` must be null or a map returned by `linear_map_cstr_new` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_cstr_free(map: *mut LinearMapCStr) {
+    if !map.is_null() {
+        drop(Box::from_raw(map));
+    }
+}
+
+/// Returns the number of entries in a string map.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_cstr_new`.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_cstr_len(map: *const LinearMapCStr) -> usize {
+    (*map).len()
+}
+
+/// Inserts copies of a key and a value into a string map.
+///
+/// Returns `true` if the key was already present, in which case its previous value is replaced.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_cstr_new`, and `key` and `value` must point
+/// to nul-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_cstr_insert(map: *mut LinearMapCStr, key: *const c_char,
+                                                value: *const c_char) -> bool {
+    let key = CStr::from_ptr(key).to_owned();
+    let value = CStr::from_ptr(value).to_owned();
+    (*map).insert(key, value).is_some()
+}
+
+/// Looks up a key in a string map.
+///
+/// Returns the value, or null if the key is absent. The value is owned by the map and stays
+/// valid until the map is next modified or freed.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_cstr_new`, and `key` must point to a
+/// nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_cstr_get(map: *const LinearMapCStr, key: *const c_char)
+    -> *const c_char {
+    match (*map).get(CStr::from_ptr(key)) {
+        Some(value) => value.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Removes a key and its value from a string map.
+///
+/// Returns `true` if the key was present.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_cstr_new`, and `key` must point to a
+/// nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_cstr_remove(map: *mut LinearMapCStr, key: *const c_char)
+    -> bool {
+    (*map).remove(CStr::from_ptr(key)).is_some()
+}
+
+/// Removes all entries from a string map, keeping its memory for reuse.
+///
+/// # Safety
+///
+/// `map` must be a live map returned by `linear_map_cstr_new`.
+#[no_mangle]
+pub unsafe extern "C" fn linear_map_cstr_clear(map: *mut LinearMapCStr) {
+    (*map).clear();
+}
@@ -0,0 +1,140 @@
+//! Dynamic access to the fields of plain structs through a `LinearMap`.
+//!
+//! A struct implementing [`ToLinearMap`](trait.ToLinearMap.html) can be turned into a map from
+//! field names to [`FieldValue`](enum.FieldValue.html)s and back, which is handy for debugging,
+//! diffing two instances, or reading fields by name. With the `derive` feature enabled, the trait
+//! can be derived for structs with named fields whose types implement
+//! [`Field`](trait.Field.html).
+//!
+//! # Example
+//!
+//! ```
+//! use linear_map::LinearMap;
+//! use linear_map::fields::{FieldValue, ToLinearMap};
+//! use linear_map::errors::FieldError;
+//!
+//! struct Point { x: i32, y: i32 }
+//!
+//! impl ToLinearMap for Point {
+//!     fn to_linear_map(&self) -> LinearMap<&'static str, FieldValue> {
+//!         let mut map = LinearMap::new();
+//!         map.insert("x", FieldValue::Int(self.x.into()));
+//!         map.insert("y", FieldValue::Int(self.y.into()));
+//!         map
+//!     }
+//!
+//!     fn from_linear_map(mut map: LinearMap<&str, FieldValue>) -> Result<Self, FieldError> {
+//!         let mut field = |name| match map.remove(name) {
+//!             Some(FieldValue::Int(v)) => Ok(v as i32),
+//!             Some(_) => Err(FieldError::Invalid(name)),
+//!             None => Err(FieldError::Missing(name)),
+//!         };
+//!         Ok(Point { x: field("x")?, y: field("y")? })
+//!     }
+//! }
+//!
+//! let mut map = Point { x: 1, y: 2 }.to_linear_map();
+//! map.insert("y", FieldValue::Int(5));
+//! let point = Point::from_linear_map(map).unwrap();
+//! assert_eq!((point.x, point.y), (1, 5));
+//! ```
+
+#[cfg(feature = "derive")]
+extern crate linear_map_derive;
+
+use std::convert::TryFrom;
+
+use errors::FieldError;
+use super::LinearMap;
+
+#[cfg(feature = "derive")]
+pub use self::linear_map_derive::ToLinearMap;
+
+/// The dynamically typed value of a struct field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+    /// A boolean.
+    Bool(bool),
+    /// A signed integer.
+    Int(i64),
+    /// An unsigned integer.
+    UInt(u64),
+    /// A floating-point number.
+    Float(f64),
+    /// A character.
+    Char(char),
+    /// A string.
+    Str(String),
+}
+
+/// A type that can be stored in a [`FieldValue`](enum.FieldValue.html).
+pub trait Field: Sized {
+    /// Converts the value into a `FieldValue`.
+    fn to_field(&self) -> FieldValue;
+
+    /// Converts a `FieldValue` back, or returns `None` if it holds a different type or a value
+    /// out of range for `Self`.
+    fn from_field(value: FieldValue) -> Option<Self>;
+}
+
+macro_rules! impl_field_int {($variant:ident: $wide:ty: $($t:ty)*) => {$(
+    impl Field for $t {
+        fn to_field(&self) -> FieldValue {
+            FieldValue::$variant(*self as $wide)
+        }
+
+        fn from_field(value: FieldValue) -> Option<Self> {
+            match value {
+                FieldValue::$variant(v) => <$t>::try_from(v).ok(),
+                _ => None,
+            }
+        }
+    }
+)*}}
+impl_field_int!{Int: i64: i8 i16 i32 i64 isize}
+impl_field_int!{UInt: u64: u8 u16 u32 u64 usize}
+
+macro_rules! impl_field {($variant:ident: $($t:ty)*) => {$(
+    impl Field for $t {
+        fn to_field(&self) -> FieldValue {
+            FieldValue::$variant(self.clone().into())
+        }
+
+        fn from_field(value: FieldValue) -> Option<Self> {
+            match value {
+                FieldValue::$variant(v) => Some(v),
+                _ => None,
+            }
+        }
+    }
+)*}}
+impl_field!{Bool: bool}
+impl_field!{Float: f64}
+impl_field!{Char: char}
+impl_field!{Str: String}
+
+impl Field for f32 {
+    fn to_field(&self) -> FieldValue {
+        FieldValue::Float((*self).into())
+    }
+
+    fn from_field(value: FieldValue) -> Option<Self> {
+        match value {
+            FieldValue::Float(v) => Some(v as f32),
+            _ => None,
+        }
+    }
+}
+
+/// A struct whose fields can be read into and rebuilt from a `LinearMap` keyed by field name.
+///
+/// With the `derive` feature enabled, this trait can be derived for structs with named fields.
+pub trait ToLinearMap: Sized {
+    /// Returns a map from each field's name to its value, in declaration order.
+    fn to_linear_map(&self) -> LinearMap<&'static str, FieldValue>;
+
+    /// Rebuilds a value from a map of field names to values.
+    ///
+    /// Entries whose key is not a field name are ignored.
+    fn from_linear_map(map: LinearMap<&str, FieldValue>) -> Result<Self, FieldError>;
+}
@@ -0,0 +1,342 @@
+//! A map from keys to booleans, storing the values as a bitset.
+//!
+//! See the [`LinearFlagMap`](struct.LinearFlagMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::slice;
+use std::vec;
+
+const BITS: usize = 64;
+
+/// A map from keys to `bool` values, implemented by searching linearly in a vector.
+///
+/// This behaves like a `LinearMap<K, bool>`, but keeps its values in a bitset next to the list
+/// of keys instead of storing one `bool` (plus padding) per entry. Besides saving memory, this
+/// answers [`all`](#method.all), [`any`](#method.any) and [`count_true`](#method.count_true) one
+/// 64-bit word at a time.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::flags::LinearFlagMap;
+///
+/// let mut features = LinearFlagMap::new();
+/// features.insert("dark-mode", true);
+/// features.insert("beta-search", false);
+///
+/// assert_eq!(features.get("dark-mode"), Some(true));
+/// assert!(features.any());
+/// assert!(!features.all());
+/// ```
+pub struct LinearFlagMap<K> {
+    keys: Vec<K>,
+    bits: Vec<u64>,
+}
+
+impl<K> LinearFlagMap<K> {
+    /// Creates an empty map. This method does not allocate.
+    pub const fn new() -> Self {
+        LinearFlagMap { keys: Vec::new(), bits: Vec::new() }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        LinearFlagMap {
+            keys: Vec::with_capacity(capacity),
+            bits: Vec::with_capacity(capacity.div_ceil(BITS)),
+        }
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.keys.capacity()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Clears the map, removing all elements. Keeps the allocated memory for
+    /// reuse.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.bits.clear();
+    }
+
+    /// Returns true if every value in the map is `true`, or if the map is empty.
+    pub fn all(&self) -> bool {
+        let full = self.len() / BITS;
+        self.bits[..full].iter().all(|&w| w == !0)
+            && self.bits.get(full).is_none_or(|&w| w == tail_mask(self.len()))
+    }
+
+    /// Returns true if any value in the map is `true`.
+    pub fn any(&self) -> bool {
+        self.bits.iter().any(|&w| w != 0)
+    }
+
+    /// Returns the number of values in the map that are `true`.
+    pub fn count_true(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Scan through the map and keep those entries where the closure returns `true`.
+    ///
+    /// The order the elements are visited is not specified.
+    pub fn retain<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(&K, bool) -> bool {
+        let mut i = 0;
+        while i < self.len() {
+            if keep_fn(&self.keys[i], self.bit(i)) {
+                i += 1;
+            } else {
+                self.swap_remove(i);
+            }
+        }
+    }
+
+    /// Returns an iterator yielding references to the map's keys and their corresponding values in
+    /// arbitrary order.
+    ///
+    /// The iterator's item type is `(&K, bool)`.
+    pub fn iter(&self) -> Iter<'_, K> {
+        Iter { keys: self.keys.iter().enumerate(), bits: &self.bits }
+    }
+
+    /// Returns an iterator yielding references to the map's keys in arbitrary order.
+    ///
+    /// The iterator's item type is `&K`.
+    pub fn keys(&self) -> slice::Iter<'_, K> {
+        self.keys.iter()
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        get_bit(&self.bits, index)
+    }
+
+    fn set_bit(&mut self, index: usize, value: bool) {
+        let word = &mut self.bits[index / BITS];
+        if value {
+            *word |= 1 << (index % BITS);
+        } else {
+            *word &= !(1 << (index % BITS));
+        }
+    }
+
+    fn push(&mut self, key: K, value: bool) {
+        if self.len().is_multiple_of(BITS) {
+            self.bits.push(0);
+        }
+        self.keys.push(key);
+        self.set_bit(self.len() - 1, value);
+    }
+
+    fn swap_remove(&mut self, index: usize) -> (K, bool) {
+        let value = self.bit(index);
+        let last = self.len() - 1;
+        let last_value = self.bit(last);
+        self.set_bit(index, last_value);
+        self.set_bit(last, false);
+        if last.is_multiple_of(BITS) {
+            self.bits.pop();
+        }
+        (self.keys.swap_remove(index), value)
+    }
+}
+
+impl<K: PartialEq> LinearFlagMap<K> {
+    fn position<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<usize> where K: Borrow<Q> {
+        self.keys.iter().position(|k| k.borrow() == key)
+    }
+
+    /// Returns the value corresponding to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<bool> where K: Borrow<Q> {
+        self.position(key).map(|i| self.bit(i))
+    }
+
+    /// Checks if the map contains a key that is equal to the given key.
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.position(key).is_some()
+    }
+
+    /// Sets the value of an existing key, returning its previous value.
+    ///
+    /// Returns `None`, leaving the map unchanged, if the map contains no such key.
+    pub fn set<Q: ?Sized + PartialEq>(&mut self, key: &Q, value: bool) -> Option<bool>
+    where K: Borrow<Q> {
+        let i = self.position(key)?;
+        let old = self.bit(i);
+        self.set_bit(i, value);
+        Some(old)
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// Returns `None` if the map did not contain the given key. Otherwise the key's value is
+    /// replaced with the given value, and the old value is returned.
+    pub fn insert(&mut self, key: K, value: bool) -> Option<bool> {
+        match self.position(&key) {
+            Some(i) => {
+                let old = self.bit(i);
+                self.set_bit(i, value);
+                Some(old)
+            }
+            None => {
+                self.push(key, value);
+                None
+            }
+        }
+    }
+
+    /// Removes the key in the map that is equal to the given key and returns its corresponding
+    /// value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<bool> where K: Borrow<Q> {
+        let i = self.position(key)?;
+        Some(self.swap_remove(i).1)
+    }
+}
+
+fn tail_mask(len: usize) -> u64 {
+    match len % BITS {
+        0 => !0,
+        n => (1 << n) - 1,
+    }
+}
+
+impl<K: Clone> Clone for LinearFlagMap<K> {
+    fn clone(&self) -> Self {
+        LinearFlagMap { keys: self.keys.clone(), bits: self.bits.clone() }
+    }
+}
+
+impl<K: Debug> Debug for LinearFlagMap<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K> Default for LinearFlagMap<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq> Extend<(K, bool)> for LinearFlagMap<K> {
+    fn extend<I: IntoIterator<Item = (K, bool)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K: PartialEq> iter::FromIterator<(K, bool)> for LinearFlagMap<K> {
+    fn from_iter<I: IntoIterator<Item = (K, bool)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+impl<K: PartialEq> PartialEq for LinearFlagMap<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Eq> Eq for LinearFlagMap<K> {}
+
+impl<K> IntoIterator for LinearFlagMap<K> {
+    type Item = (K, bool);
+    type IntoIter = IntoIter<K>;
+
+    fn into_iter(self) -> IntoIter<K> {
+        IntoIter { keys: self.keys.into_iter().enumerate(), bits: self.bits }
+    }
+}
+
+impl<'a, K> IntoIterator for &'a LinearFlagMap<K> {
+    type Item = (&'a K, bool);
+    type IntoIter = Iter<'a, K>;
+
+    fn into_iter(self) -> Iter<'a, K> {
+        self.iter()
+    }
+}
+
+fn get_bit(bits: &[u64], index: usize) -> bool {
+    bits[index / BITS] & 1 << (index % BITS) != 0
+}
+
+/// A borrowing iterator over the entries of a `LinearFlagMap`.
+///
+/// See [`LinearFlagMap::iter`](struct.LinearFlagMap.html#method.iter) for details.
+pub struct Iter<'a, K: 'a> {
+    keys: iter::Enumerate<slice::Iter<'a, K>>,
+    bits: &'a [u64],
+}
+
+/// An owning iterator over the entries of a `LinearFlagMap`.
+///
+/// See [`LinearFlagMap::into_iter`](struct.LinearFlagMap.html#method.into_iter) for details.
+pub struct IntoIter<K> {
+    keys: iter::Enumerate<vec::IntoIter<K>>,
+    bits: Vec<u64>,
+}
+
+impl<'a, K> Iterator for Iter<'a, K> {
+    type Item = (&'a K, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.keys.next().map(|(i, k)| (k, get_bit(self.bits, i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<'a, K> DoubleEndedIterator for Iter<'a, K> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.keys.next_back().map(|(i, k)| (k, get_bit(self.bits, i)))
+    }
+}
+
+impl<'a, K> ExactSizeIterator for Iter<'a, K> {}
+
+impl<'a, K> Clone for Iter<'a, K> {
+    fn clone(&self) -> Self {
+        Iter { keys: self.keys.clone(), bits: self.bits }
+    }
+}
+
+impl<K> Iterator for IntoIter<K> {
+    type Item = (K, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bits = &self.bits;
+        self.keys.next().map(|(i, k)| (k, get_bit(bits, i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<K> DoubleEndedIterator for IntoIter<K> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let bits = &self.bits;
+        self.keys.next_back().map(|(i, k)| (k, get_bit(bits, i)))
+    }
+}
+
+impl<K> ExactSizeIterator for IntoIter<K> {}
@@ -0,0 +1,69 @@
+//! Optional support for maps keyed by floating-point numbers, through `ordered-float`.
+//!
+//! `OrderedFloat` gives floats a total equality, under which `NaN` equals itself, so a
+//! `LinearMap<OrderedFloat<f64>, V>` can hold and find any float key. The methods here take and
+//! return plain floats, wrapping them as needed.
+//!
+//! # Example
+//!
+//! ```
+//! use linear_map::float::F64Map;
+//!
+//! let mut labels = F64Map::new();
+//! labels.insert_float(0.5, "medium");
+//! labels.insert_float(0.9, "high");
+//! labels.insert_float(f64::NAN, "unknown");
+//!
+//! assert_eq!(labels.get_float(0.9), Some(&"high"));
+//! assert_eq!(labels.get_float(f64::NAN), Some(&"unknown"));
+//! assert_eq!(labels.remove_float(0.5), Some("medium"));
+//! ```
+
+extern crate ordered_float;
+
+pub use self::ordered_float::OrderedFloat;
+use self::ordered_float::FloatCore;
+
+use super::LinearMap;
+
+/// A `LinearMap` keyed by `f64`s.
+pub type F64Map<V> = LinearMap<OrderedFloat<f64>, V>;
+
+/// A `LinearMap` keyed by `f32`s.
+pub type F32Map<V> = LinearMap<OrderedFloat<f32>, V>;
+
+impl<F: FloatCore, V> LinearMap<OrderedFloat<F>, V> {
+    /// Returns a reference to the value corresponding to the given float.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_float(&self, key: F) -> Option<&V> {
+        self.get(&OrderedFloat(key))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the given float.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_float_mut(&mut self, key: F) -> Option<&mut V> {
+        self.get_mut(&OrderedFloat(key))
+    }
+
+    /// Checks if the map contains the given float as a key.
+    pub fn contains_float(&self, key: F) -> bool {
+        self.contains_key(&OrderedFloat(key))
+    }
+
+    /// Inserts a value under the given float.
+    ///
+    /// Returns `None` if the map did not contain the key. Otherwise the key's value is replaced
+    /// with the given value, and the old value is returned.
+    pub fn insert_float(&mut self, key: F, value: V) -> Option<V> {
+        self.insert(OrderedFloat(key), value)
+    }
+
+    /// Removes the given float from the map and returns its corresponding value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove_float(&mut self, key: F) -> Option<V> {
+        self.remove(&OrderedFloat(key))
+    }
+}
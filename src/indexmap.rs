@@ -0,0 +1,63 @@
+//! Optional conversions between the linear collections and `indexmap`'s `IndexMap` and
+//! `IndexSet`.
+//!
+//! Both sides keep their entries in a vector, so conversions preserve the order of entries and
+//! never search for duplicates. [`LinearMap::eq_indexmap`] and [`LinearSet::eq_indexset`] compare
+//! the two, ignoring order as each type does on its own.
+//!
+//! [`LinearMap::eq_indexmap`]: ../struct.LinearMap.html#method.eq_indexmap
+//! [`LinearSet::eq_indexset`]: ../set/struct.LinearSet.html#method.eq_indexset
+
+extern crate indexmap;
+
+use std::hash::{BuildHasher, Hash};
+
+use self::indexmap::{IndexMap, IndexSet};
+
+use super::LinearMap;
+use super::set::LinearSet;
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> From<LinearMap<K, V>> for IndexMap<K, V, S> {
+    fn from(map: LinearMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V, S> From<IndexMap<K, V, S>> for LinearMap<K, V> {
+    fn from(index_map: IndexMap<K, V, S>) -> Self {
+        let mut map = LinearMap::with_capacity(index_map.len());
+        // The keys of an `IndexMap` are unique, so there is nothing to search for.
+        map.storage.extend(index_map);
+        map
+    }
+}
+
+impl<K: Hash + Eq, V: PartialEq> LinearMap<K, V> {
+    /// Checks whether the map holds the same key-value pairs as the given `IndexMap`, in any
+    /// order.
+    pub fn eq_indexmap<S: BuildHasher>(&self, other: &IndexMap<K, V, S>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher + Default> From<LinearSet<T>> for IndexSet<T, S> {
+    fn from(set: LinearSet<T>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T: Eq, S> From<IndexSet<T, S>> for LinearSet<T> {
+    fn from(index_set: IndexSet<T, S>) -> Self {
+        let mut set = LinearSet::with_capacity(index_set.len());
+        // The values of an `IndexSet` are unique, so there is nothing to search for.
+        set.map.storage.extend(index_set.into_iter().map(|value| (value, ())));
+        set
+    }
+}
+
+impl<T: Hash + Eq> LinearSet<T> {
+    /// Checks whether the set holds the same values as the given `IndexSet`, in any order.
+    pub fn eq_indexset<S: BuildHasher>(&self, other: &IndexSet<T, S>) -> bool {
+        self.len() == other.len() && self.iter().all(|value| other.contains(value))
+    }
+}
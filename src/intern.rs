@@ -0,0 +1,109 @@
+//! Optional support for maps keyed by interned strings, through `lasso`.
+//!
+//! Comparing interned symbols is a single integer comparison, so a `LinearMap<Spur, V>` scans
+//! much faster than a map keyed by strings. The methods here take and return `&str`, translating
+//! through an interner: lookups only need a [`Reader`], which never interns anything, so a string
+//! that was never interned is known to be absent without scanning the map at all.
+//!
+//! # Example
+//!
+//! ```
+//! use linear_map::intern::{InternedLinearMap, Rodeo};
+//!
+//! let mut interner = Rodeo::default();
+//! let mut headers = InternedLinearMap::new();
+//! headers.insert_str(&mut interner, "content-type", "text/html");
+//! headers.insert_str(&mut interner, "content-length", "42");
+//!
+//! assert_eq!(headers.get_str(&interner, "content-length"), Some(&"42"));
+//! assert_eq!(headers.get_str(&interner, "etag"), None);
+//! assert_eq!(headers.iter_str(&interner).next(), Some(("content-type", &"text/html")));
+//! ```
+//!
+//! [`Reader`]: https://docs.rs/lasso/0.7/lasso/trait.Reader.html
+
+extern crate lasso;
+
+pub use self::lasso::{Interner, Key, Reader, Resolver, Rodeo, Spur};
+
+use super::{Iter, LinearMap};
+
+/// A `LinearMap` keyed by interned strings.
+pub type InternedLinearMap<V, S = Spur> = LinearMap<S, V>;
+
+impl<S: Key, V> LinearMap<S, V> {
+    /// Returns a reference to the value corresponding to the given string.
+    ///
+    /// Returns `None` if the map contains no such key, or if the string was never interned.
+    pub fn get_str<R: Reader<S>>(&self, interner: &R, key: &str) -> Option<&V> {
+        self.get(&interner.get(key)?)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the given string.
+    ///
+    /// Returns `None` if the map contains no such key, or if the string was never interned.
+    pub fn get_str_mut<R: Reader<S>>(&mut self, interner: &R, key: &str) -> Option<&mut V> {
+        self.get_mut(&interner.get(key)?)
+    }
+
+    /// Checks if the map contains the given string as a key.
+    pub fn contains_str<R: Reader<S>>(&self, interner: &R, key: &str) -> bool {
+        interner.get(key).is_some_and(|sym| self.contains_key(&sym))
+    }
+
+    /// Interns the given string and inserts a value under it.
+    ///
+    /// Returns `None` if the map did not contain the key. Otherwise the key's value is replaced
+    /// with the given value, and the old value is returned.
+    pub fn insert_str<I: Interner<S>>(&mut self, interner: &mut I, key: &str, value: V)
+        -> Option<V> {
+        self.insert(interner.get_or_intern(key), value)
+    }
+
+    /// Removes the given string from the map and returns its corresponding value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove_str<R: Reader<S>>(&mut self, interner: &R, key: &str) -> Option<V> {
+        self.remove(&interner.get(key)?)
+    }
+
+    /// Returns an iterator yielding the map's keys, resolved to strings, and references to their
+    /// corresponding values in arbitrary order.
+    ///
+    /// The iterator's item type is `(&str, &V)`.
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if a key was not interned by `resolver`.
+    pub fn iter_str<'a, R: Resolver<S>>(&'a self, resolver: &'a R) -> IterStr<'a, S, V, R> {
+        IterStr { iter: self.iter(), resolver }
+    }
+}
+
+/// An iterator over the entries of an `InternedLinearMap`, with keys resolved to strings.
+///
+/// See [`LinearMap::iter_str`](../struct.LinearMap.html#method.iter_str) for details.
+pub struct IterStr<'a, S: 'a, V: 'a, R: 'a> {
+    iter: Iter<'a, S, V>,
+    resolver: &'a R,
+}
+
+impl<'a, S: Key, V, R: Resolver<S>> Iterator for IterStr<'a, S, V, R> {
+    type Item = (&'a str, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, v)| (self.resolver.resolve(k), v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, S: Key, V, R: Resolver<S>> DoubleEndedIterator for IterStr<'a, S, V, R> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(k, v)| (self.resolver.resolve(k), v))
+    }
+}
+
+impl<'a, S: Key, V, R: Resolver<S>> ExactSizeIterator for IterStr<'a, S, V, R> {}
@@ -0,0 +1,237 @@
+//! A map keyed by ranges, supporting point and overlap queries.
+//!
+//! See the [`LinearIntervalMap`](struct.LinearIntervalMap.html) type for details.
+
+use std::fmt::{self, Debug};
+use std::iter;
+use std::ops::Range;
+
+use super::{LinearMap, IntoIter, Iter, IterMut};
+
+/// A map keyed by half-open ranges, implemented by searching linearly in a vector.
+///
+/// Ranges are keys like any other: inserting a range equal to one already in the map replaces
+/// its value. Ranges may overlap; point and overlap queries scan every entry, so this type is
+/// meant for a handful of intervals, such as memory-mapped regions or tier thresholds. Empty
+/// ranges never contain a point nor overlap anything.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::interval::LinearIntervalMap;
+///
+/// let mut tiers = LinearIntervalMap::new();
+/// tiers.insert(0..100, "bronze");
+/// tiers.insert(100..1000, "silver");
+/// tiers.insert(1000..u32::MAX, "gold");
+///
+/// assert_eq!(tiers.get_point(&250), Some(&"silver"));
+/// assert_eq!(tiers.overlapping(&(50..150)).count(), 2);
+/// ```
+pub struct LinearIntervalMap<K, V> {
+    map: LinearMap<Range<K>, V>,
+}
+
+impl<K: Ord, V> LinearIntervalMap<K, V> {
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        LinearIntervalMap { map: LinearMap::new() }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        LinearIntervalMap { map: LinearMap::with_capacity(capacity) }
+    }
+
+    /// Returns the number of intervals in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map contains no intervals.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Clears the map, removing all intervals. Keeps the allocated memory for
+    /// reuse.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns an iterator yielding references to the map's intervals and their corresponding
+    /// values in arbitrary order.
+    ///
+    /// The iterator's item type is `(&Range<K>, &V)`.
+    pub fn iter(&self) -> Iter<'_, Range<K>, V> {
+        self.map.iter()
+    }
+
+    /// Returns an iterator yielding references to the map's intervals and mutable references to
+    /// their corresponding values in arbitrary order.
+    ///
+    /// The iterator's item type is `(&Range<K>, &mut V)`.
+    pub fn iter_mut(&mut self) -> IterMut<'_, Range<K>, V> {
+        self.map.iter_mut()
+    }
+
+    /// Returns a reference to the value of the interval equal to the given range.
+    pub fn get(&self, range: &Range<K>) -> Option<&V> {
+        self.map.get(range)
+    }
+
+    /// Returns a mutable reference to the value of the interval equal to the given range.
+    pub fn get_mut(&mut self, range: &Range<K>) -> Option<&mut V> {
+        self.map.get_mut(range)
+    }
+
+    /// Returns a reference to the value of an interval containing the given point.
+    ///
+    /// If several intervals contain the point, which one is returned is unspecified; use
+    /// [`containing`](#method.containing) to visit all of them.
+    pub fn get_point(&self, point: &K) -> Option<&V> {
+        self.map.iter().find(|&(r, _)| r.contains(point)).map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the value of an interval containing the given point.
+    pub fn get_point_mut(&mut self, point: &K) -> Option<&mut V> {
+        self.map.iter_mut().find(|&(r, _)| r.contains(point)).map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over the intervals containing the given point.
+    ///
+    /// The iterator's item type is `(&Range<K>, &V)`.
+    pub fn containing<'a>(&'a self, point: &'a K) -> Containing<'a, K, V> {
+        Containing { iter: self.map.iter(), point }
+    }
+
+    /// Returns an iterator over the intervals overlapping the given range.
+    ///
+    /// The iterator's item type is `(&Range<K>, &V)`.
+    pub fn overlapping<'a>(&'a self, range: &'a Range<K>) -> Overlapping<'a, K, V> {
+        Overlapping { iter: self.map.iter(), range }
+    }
+
+    /// Inserts an interval into the map.
+    ///
+    /// Returns the previous value if an equal range was already in the map.
+    pub fn insert(&mut self, range: Range<K>, value: V) -> Option<V> {
+        self.map.insert(range, value)
+    }
+
+    /// Removes the interval equal to the given range and returns its value.
+    pub fn remove(&mut self, range: &Range<K>) -> Option<V> {
+        self.map.remove(range)
+    }
+
+    /// Removes every interval containing the given point and returns how many were removed.
+    pub fn remove_point(&mut self, point: &K) -> usize {
+        let before = self.map.len();
+        self.map.retain(|r, _| !r.contains(point));
+        before - self.map.len()
+    }
+}
+
+fn overlaps<K: Ord>(a: &Range<K>, b: &Range<K>) -> bool {
+    a.start < b.end && b.start < a.end && a.start < a.end && b.start < b.end
+}
+
+impl<K: Ord + Clone, V: Clone> Clone for LinearIntervalMap<K, V> {
+    fn clone(&self) -> Self {
+        LinearIntervalMap { map: self.map.clone() }
+    }
+}
+
+impl<K: Ord + Debug, V: Debug> Debug for LinearIntervalMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: Ord, V> Default for LinearIntervalMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> Extend<(Range<K>, V)> for LinearIntervalMap<K, V> {
+    fn extend<I: IntoIterator<Item = (Range<K>, V)>>(&mut self, intervals: I) {
+        self.map.extend(intervals);
+    }
+}
+
+impl<K: Ord, V> iter::FromIterator<(Range<K>, V)> for LinearIntervalMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (Range<K>, V)>>(intervals: I) -> Self {
+        LinearIntervalMap { map: intervals.into_iter().collect() }
+    }
+}
+
+impl<K: Ord, V: PartialEq> PartialEq for LinearIntervalMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Ord, V: Eq> Eq for LinearIntervalMap<K, V> {}
+
+impl<K: Ord, V> IntoIterator for LinearIntervalMap<K, V> {
+    type Item = (Range<K>, V);
+    type IntoIter = IntoIter<Range<K>, V>;
+
+    fn into_iter(self) -> IntoIter<Range<K>, V> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a LinearIntervalMap<K, V> {
+    type Item = (&'a Range<K>, &'a V);
+    type IntoIter = Iter<'a, Range<K>, V>;
+
+    fn into_iter(self) -> Iter<'a, Range<K>, V> {
+        self.iter()
+    }
+}
+
+/// An iterator over the intervals of a `LinearIntervalMap` containing a point.
+///
+/// See [`LinearIntervalMap::containing`](struct.LinearIntervalMap.html#method.containing) for
+/// details.
+pub struct Containing<'a, K: 'a, V: 'a> {
+    iter: Iter<'a, Range<K>, V>,
+    point: &'a K,
+}
+
+/// An iterator over the intervals of a `LinearIntervalMap` overlapping a range.
+///
+/// See [`LinearIntervalMap::overlapping`](struct.LinearIntervalMap.html#method.overlapping) for
+/// details.
+pub struct Overlapping<'a, K: 'a, V: 'a> {
+    iter: Iter<'a, Range<K>, V>,
+    range: &'a Range<K>,
+}
+
+impl<'a, K: Ord, V> Iterator for Containing<'a, K, V> {
+    type Item = (&'a Range<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.point;
+        self.iter.by_ref().find(|&(r, _)| r.contains(point))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for Overlapping<'a, K, V> {
+    type Item = (&'a Range<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = self.range;
+        self.iter.by_ref().find(|&(r, _)| overlaps(r, range))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
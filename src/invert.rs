@@ -0,0 +1,112 @@
+//! Inverting a `LinearMap`, turning its values into keys.
+//!
+//! Since several keys may map to equal values, [`LinearMap::invert`] takes a collision policy
+//! deciding what happens to them:
+//!
+//! - [`Reject`](struct.Reject.html) fails on the first collision;
+//! - [`KeepFirst`](struct.KeepFirst.html) and [`KeepLast`](struct.KeepLast.html) keep one of the
+//!   colliding keys;
+//! - [`Group`](struct.Group.html) collects all of them into a `Vec`.
+//!
+//! Which key is "first" follows the map's iteration order.
+//!
+//! [`LinearMap::invert`]: ../struct.LinearMap.html#method.invert
+
+use errors::DuplicateKeyError;
+
+use super::LinearMap;
+use super::Entry::{Occupied, Vacant};
+
+/// A policy for resolving keys that map to equal values when inverting a map.
+pub trait InvertPolicy<K, V> {
+    /// The result of inverting a `LinearMap<K, V>` with this policy.
+    type Output;
+
+    /// Inverts the map.
+    fn invert(self, map: LinearMap<K, V>) -> Self::Output;
+}
+
+/// Fails with the colliding value and key on the first collision.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Reject;
+
+/// Keeps the first key for each value.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KeepFirst;
+
+/// Keeps the last key for each value.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KeepLast;
+
+/// Collects all keys for each value into a `Vec`, in iteration order.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Group;
+
+impl<K, V: Eq> InvertPolicy<K, V> for Reject {
+    type Output = Result<LinearMap<V, K>, DuplicateKeyError<V, K>>;
+
+    fn invert(self, map: LinearMap<K, V>) -> Self::Output {
+        LinearMap::try_from_iter(map.storage.into_iter().map(|(k, v)| (v, k)))
+    }
+}
+
+impl<K, V: Eq> InvertPolicy<K, V> for KeepFirst {
+    type Output = LinearMap<V, K>;
+
+    fn invert(self, map: LinearMap<K, V>) -> Self::Output {
+        let mut inverse = LinearMap::with_capacity(map.len());
+        for (k, v) in map.storage {
+            inverse.entry(v).or_insert(k);
+        }
+        inverse
+    }
+}
+
+impl<K, V: Eq> InvertPolicy<K, V> for KeepLast {
+    type Output = LinearMap<V, K>;
+
+    fn invert(self, map: LinearMap<K, V>) -> Self::Output {
+        map.storage.into_iter().map(|(k, v)| (v, k)).collect()
+    }
+}
+
+impl<K, V: Eq> InvertPolicy<K, V> for Group {
+    type Output = LinearMap<V, Vec<K>>;
+
+    fn invert(self, map: LinearMap<K, V>) -> Self::Output {
+        let mut inverse = LinearMap::<V, Vec<K>>::new();
+        for (k, v) in map.storage {
+            match inverse.entry(v) {
+                Occupied(mut e) => e.get_mut().push(k),
+                Vacant(e) => { e.insert(vec![k]); }
+            }
+        }
+        inverse
+    }
+}
+
+impl<K: PartialEq, V> LinearMap<K, V> {
+    /// Consumes the map and returns the inverse map, from values to keys.
+    ///
+    /// Keys that map to equal values are resolved by `policy`; see the [`invert`](invert/index.html)
+    /// module for the available policies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    /// use linear_map::invert::{Group, KeepFirst, Reject};
+    ///
+    /// let mut codes = LinearMap::new();
+    /// codes.insert("ok", 200);
+    /// codes.insert("created", 201);
+    /// codes.insert("success", 200);
+    ///
+    /// assert!(codes.clone().invert(Reject).is_err());
+    /// assert_eq!(codes.clone().invert(KeepFirst)[&200], "ok");
+    /// assert_eq!(codes.invert(Group)[&200], ["ok", "success"]);
+    /// ```
+    pub fn invert<P: InvertPolicy<K, V>>(self, policy: P) -> P::Output {
+        policy.invert(self)
+    }
+}
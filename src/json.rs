@@ -0,0 +1,46 @@
+//! Optional conversions between `LinearMap` and `serde_json` objects.
+//!
+//! The conversions move entries without cloning them and keep them in the order in which the
+//! source yields them. Whether a `serde_json::Map` itself remembers insertion order depends on
+//! `serde_json`'s `preserve_order` feature; without it, objects are sorted by key.
+
+extern crate serde_json;
+
+use std::convert::TryFrom;
+
+use self::serde_json::{Map, Value};
+
+use super::LinearMap;
+
+impl From<LinearMap<String, Value>> for Map<String, Value> {
+    fn from(map: LinearMap<String, Value>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl From<Map<String, Value>> for LinearMap<String, Value> {
+    fn from(object: Map<String, Value>) -> Self {
+        let mut map = LinearMap::with_capacity(object.len());
+        // The keys of a JSON object are unique, so there is nothing to search for.
+        map.storage.extend(object);
+        map
+    }
+}
+
+impl From<LinearMap<String, Value>> for Value {
+    fn from(map: LinearMap<String, Value>) -> Self {
+        Value::Object(map.into())
+    }
+}
+
+/// Converts a JSON object into a map, handing back any other kind of value as the error.
+impl TryFrom<Value> for LinearMap<String, Value> {
+    type Error = Value;
+
+    fn try_from(value: Value) -> Result<Self, Value> {
+        match value {
+            Value::Object(object) => Ok(object.into()),
+            other => Err(other),
+        }
+    }
+}
@@ -10,4 +10,72 @@ pub use map::*;
 // Optional Serde support
 #[cfg(feature = "serde_impl")]
 pub mod serde;
+pub mod adaptive;
+// Optional bincode 2 support
+#[cfg(feature = "bincode")]
+mod bincode;
+pub mod any;
+pub mod approx;
+pub mod bitset;
+// Optional bump arena allocation
+#[cfg(feature = "bumpalo")]
+pub mod bump;
+pub mod compact;
+// Optional HashMap-compatible constructors
+#[cfg(feature = "hashmap-compat")]
+pub mod compat;
+pub mod compare;
+pub mod counter;
+pub mod errors;
+pub mod expiring;
+// Optional C interface
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fields;
+pub mod flags;
+// Optional float keys through ordered-float
+#[cfg(feature = "ordered-float")]
+pub mod float;
+// Optional indexmap interop
+#[cfg(feature = "indexmap")]
+mod indexmap;
+// Optional interned string keys through lasso
+#[cfg(feature = "lasso")]
+pub mod intern;
+pub mod interval;
+pub mod invert;
+pub mod merge;
+pub mod multimap;
+// Optional serde_json interop
+#[cfg(feature = "serde_json")]
+mod json;
+// Optional serde_with adapters
+#[cfg(feature = "serde_with")]
+mod serde_with;
+// Optional random sampling support
+#[cfg(feature = "rand")]
+mod rand;
+pub mod mutable_keys;
+pub mod nested;
+// Optional panic-free API subset
+#[cfg(feature = "no-panic")]
+pub mod no_panic;
+pub mod policy;
+pub mod pool;
+pub mod prefilter;
 pub mod set;
+pub mod shared;
+pub mod sorted;
+// Optional per-key access statistics
+#[cfg(feature = "diagnostics")]
+pub mod stats;
+pub mod thin;
+pub mod tombstone;
+pub mod vec_map;
+// Optional size-threshold diagnostics
+#[cfg(feature = "diagnostics")]
+pub mod watch;
+// Optional JavaScript interop
+#[cfg(feature = "wasm")]
+mod wasm;
+pub mod weak;
@@ -5,26 +5,44 @@
 use std::borrow::Borrow;
 use std::fmt::{self, Debug};
 use std::iter;
+use std::marker::PhantomData;
 use std::mem;
-use std::ops;
+use std::ops::{self, ControlFlow};
+use std::ptr::{self, NonNull};
 use std::slice;
 use std::vec;
 
 use self::Entry::{Occupied, Vacant};
+use errors::{DuplicateKeyError, OccupiedError, RenameError};
+use set::LinearSet;
 
 /// A map implemented by searching linearly in a vector.
 ///
-/// `LinearMap`'s keys are compared using the [`Eq`][eq] trait. All search operations
-/// (`contains_key`, `get`, `get_mut`, `insert`, and `remove`) run in `O(n)` time, making this
-/// implementation suitable only for small numbers of keys. The ordering of the keys in the
-/// underlying vector is arbitrary.
+/// `LinearMap`'s keys are compared using the [`PartialEq`][partial_eq] trait. All search
+/// operations (`contains_key`, `get`, `get_mut`, `insert`, and `remove`) run in `O(n)` time,
+/// making this implementation suitable only for small numbers of keys. The ordering of the keys
+/// in the underlying vector is arbitrary.
+///
+/// Keys need not implement [`Eq`][eq], so types such as `f64` can be used directly. A key that
+/// is not equal to itself, such as `NaN`, can never be found again once inserted: lookups and
+/// removals miss it, and inserting it again adds another entry rather than replacing the first.
+///
+/// Removing a key moves the last entry of the underlying vector into the removed entry's place,
+/// so the relative order of entries is not preserved across removals. Iterators and entries
+/// borrow the map, so this reordering can never be observed through them; positions obtained
+/// from [`indexed_iter`](#method.indexed_iter), however, refer to different entries after a
+/// removal. Debug builds detect this and panic when such a stale [`EntryIndex`] is used.
+///
+/// [`EntryIndex`]: struct.EntryIndex.html
 ///
 /// It is a logic error for a key to be modified in such a way that the key's equality, as
-/// determined by the [`Eq`][eq] trait, changes while it is in the map. This is normally only
-/// possible through [`Cell`][cell], [`RefCell`][ref_cell], global state, I/O, or unsafe code.
+/// determined by the [`PartialEq`][partial_eq] trait, changes while it is in the map. This is
+/// normally only possible through [`Cell`][cell], [`RefCell`][ref_cell], global state, I/O, or
+/// unsafe code.
 ///
 /// [cell]: https://doc.rust-lang.org/nightly/std/cell/struct.Cell.html
 /// [eq]: https://doc.rust-lang.org/nightly/std/cmp/trait.Eq.html
+/// [partial_eq]: https://doc.rust-lang.org/nightly/std/cmp/trait.PartialEq.html
 /// [ref_cell]: https://doc.rust-lang.org/nightly/std/cell/struct.RefCell.html
 ///
 /// # Example
@@ -66,18 +84,61 @@ use self::Entry::{Occupied, Vacant};
 /// }
 /// ```
 pub struct LinearMap<K, V> {
-    storage: Vec<(K, V)>,
+    pub(crate) storage: Vec<(K, V)>,
+    // Advanced whenever entries are inserted, removed or reordered, so that debug builds can
+    // reject positions obtained before.
+    #[cfg(debug_assertions)]
+    generation: u64,
 }
 
-impl<K: Eq, V> LinearMap<K, V> {
+impl<K, V> LinearMap<K, V> {
     /// Creates an empty map. This method does not allocate.
-    pub fn new() -> Self {
-        LinearMap { storage: vec![] }
+    ///
+    /// This is a `const fn`, so it can initialize a `static` map behind a lock.
+    pub const fn new() -> Self {
+        LinearMap::from_storage(Vec::new())
     }
 
     /// Creates an empty map with the given initial capacity.
     pub fn with_capacity(capacity: usize) -> Self {
-        LinearMap { storage: Vec::with_capacity(capacity) }
+        LinearMap::from_storage(Vec::with_capacity(capacity))
+    }
+
+    /// Wraps a vector of entries whose keys are known to be distinct.
+    pub(crate) const fn from_storage(storage: Vec<(K, V)>) -> Self {
+        LinearMap {
+            storage,
+            #[cfg(debug_assertions)]
+            generation: 0,
+        }
+    }
+
+    /// Records that entries were inserted, removed or reordered, which makes every `EntryIndex`
+    /// handed out so far stale.
+    ///
+    /// Code that changes `storage` directly must call this for anything but changes to values.
+    pub(crate) fn invalidate_positions(&mut self) {
+        #[cfg(debug_assertions)]
+        {
+            self.generation = self.generation.wrapping_add(1);
+        }
+    }
+
+    fn positions(&self) -> Positions {
+        Positions {
+            range: 0..self.storage.len(),
+            #[cfg(debug_assertions)]
+            generation: self.generation,
+        }
+    }
+
+    /// Checks that `index` is not stale and returns the position it stands for.
+    fn resolve(&self, index: EntryIndex) -> usize {
+        #[cfg(debug_assertions)]
+        assert!(index.generation == self.generation,
+                "stale EntryIndex {}: entries were inserted or removed since it was obtained",
+                index.index);
+        index.index
     }
 
     /// Returns the number of elements the map can hold without reallocating.
@@ -132,15 +193,30 @@ impl<K: Eq, V> LinearMap<K, V> {
     /// Clears the map, removing all elements. Keeps the allocated memory for
     /// reuse.
     pub fn clear(&mut self) {
+        self.invalidate_positions();
         self.storage.clear();
     }
 
     /// Scan through the map and keep those key-value pairs where the
     /// closure returns `true`.
     ///
-    /// The order the elements are visited is not specified.
+    /// The order the elements are visited is not specified. The map is traversed once, so this
+    /// takes linear time, unlike removing the rejected keys one by one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+    /// map.retain(|&k, v| { *v += 1; k % 2 == 0 });
+    /// assert_eq!(map.len(), 4);
+    /// assert_eq!(map[&4], 41);
+    /// assert!(!map.contains_key(&3));
+    /// ```
     pub fn retain<F>(&mut self, mut keep_fn: F)
     where F: FnMut(&K, &mut V) -> bool {
+        self.invalidate_positions();
         let mut del = 0;
         {
             let v = &mut *self.storage;
@@ -158,22 +234,161 @@ impl<K: Eq, V> LinearMap<K, V> {
         }
     }
 
+    /// Scan through the map like [`retain`](#method.retain), letting the closure stop the scan
+    /// early.
+    ///
+    /// The closure returns `ControlFlow::Continue(keep)` to keep or remove the current entry and
+    /// move on, or `ControlFlow::Break(value)` to stop: the current entry and all entries not yet
+    /// visited are then kept, and `Break(value)` is returned. Returns `Continue(())` if every
+    /// entry was visited.
+    ///
+    /// The order the elements are visited is not specified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ops::ControlFlow;
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut limits = LinearMap::new();
+    /// limits.insert("cpu", 0);
+    /// limits.insert("memory", -1);
+    /// limits.insert("disk", 0);
+    ///
+    /// let result = limits.retain_with_control(|name, &mut limit| match limit {
+    ///     0 => ControlFlow::Continue(false),
+    ///     l if l < 0 => ControlFlow::Break(format!("negative limit for {}", name)),
+    ///     _ => ControlFlow::Continue(true),
+    /// });
+    /// assert_eq!(result, ControlFlow::Break("negative limit for memory".to_string()));
+    /// assert!(!limits.contains_key("cpu"));
+    /// assert!(limits.contains_key("disk"));
+    /// ```
+    pub fn retain_with_control<B, F>(&mut self, mut control_fn: F) -> ControlFlow<B>
+    where F: FnMut(&K, &mut V) -> ControlFlow<B, bool> {
+        self.invalidate_positions();
+        let mut del = 0;
+        let mut result = ControlFlow::Continue(());
+        {
+            let v = &mut *self.storage;
+            for i in 0..v.len() {
+                let keep = match result {
+                    ControlFlow::Continue(()) => match control_fn(&v[i].0, &mut v[i].1) {
+                        ControlFlow::Continue(keep) => keep,
+                        ControlFlow::Break(b) => {
+                            result = ControlFlow::Break(b);
+                            true
+                        }
+                    },
+                    ControlFlow::Break(_) if del == 0 => break,
+                    ControlFlow::Break(_) => true,
+                };
+                if !keep {
+                    del += 1;
+                } else if del > 0 {
+                    v.swap(i - del, i);
+                }
+            }
+        }
+        if del > 0 {
+            let len = self.storage.len();
+            self.storage.truncate(len - del);
+        }
+        result
+    }
+
+    /// Scan through the map, replacing each value with the result of the closure, and removing
+    /// the key-value pairs for which it returns `None`.
+    ///
+    /// The closure takes ownership of each value, so it can transform and filter in a single
+    /// pass. The order the elements are visited is not specified.
+    ///
+    /// If the closure panics, the pair it was called on is dropped and all other pairs stay in
+    /// the map.
+    pub fn retain_map<F>(&mut self, mut map_fn: F)
+    where F: FnMut(&K, V) -> Option<V> {
+        self.invalidate_positions();
+        // Compacts the pairs in place: those before `write` are kept, and `read` is the next one
+        // to visit. The vector's length stays zero during the scan; the guard moves the pairs not
+        // visited yet down after the kept ones and restores it, both when the scan completes and
+        // when `map_fn` panics.
+        struct Guard<'a, K: 'a, V: 'a> {
+            storage: &'a mut Vec<(K, V)>,
+            read: usize,
+            write: usize,
+            len: usize,
+        }
+
+        impl<'a, K, V> Drop for Guard<'a, K, V> {
+            fn drop(&mut self) {
+                let rest = self.len - self.read;
+                unsafe {
+                    let base = self.storage.as_mut_ptr();
+                    ptr::copy(base.add(self.read), base.add(self.write), rest);
+                    self.storage.set_len(self.write + rest);
+                }
+            }
+        }
+
+        let len = self.storage.len();
+        unsafe { self.storage.set_len(0) };
+        let mut guard = Guard { storage: &mut self.storage, read: 0, write: 0, len };
+        while guard.read < guard.len {
+            let base = guard.storage.as_mut_ptr();
+            let (k, v) = unsafe { ptr::read(base.add(guard.read)) };
+            guard.read += 1;
+            if let Some(v) = map_fn(&k, v) {
+                unsafe { ptr::write(base.add(guard.write), (k, v)) };
+                guard.write += 1;
+            }
+        }
+    }
+
     /// Removes all key-value pairs from the map and returns an iterator that yields them in
     /// arbitrary order.
     ///
-    /// All key-value pairs are removed even if the iterator is not exhausted. However, the
-    /// behavior of this method is unspecified if the iterator is leaked.
+    /// All key-value pairs are removed even if the iterator is not exhausted. If the iterator is
+    /// leaked (for example with `mem::forget`), the map is left empty and the pairs that were not
+    /// yielded are leaked; the map's allocation is kept either way.
     ///
     /// The iterator's item type is `(K, V)`.
-    pub fn drain(&mut self) -> Drain<K, V> {
-        Drain { iter: self.storage.drain(..) }
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        self.invalidate_positions();
+        Drain::new(&mut self.storage)
+    }
+
+    /// Returns an iterator that removes the key-value pairs for which the closure returns `true`
+    /// and yields them in arbitrary order.
+    ///
+    /// Pairs for which the closure returns `false` stay in the map. If the iterator is dropped
+    /// before it is exhausted, the pairs it has not visited yet stay in the map as well. Like
+    /// [`remove`](#method.remove), each removal moves the last entry into the removed entry's
+    /// place, so the order of the remaining entries may change.
+    ///
+    /// The iterator's item type is `(K, V)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map: LinearMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+    /// let mut odd: Vec<_> = map.extract_if(|&k, _| k % 2 == 1).collect();
+    /// odd.sort();
+    /// assert_eq!(odd, [(1, 10), (3, 30), (5, 50), (7, 70)]);
+    /// assert_eq!(map.len(), 4);
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F>
+    where F: FnMut(&K, &mut V) -> bool {
+        self.invalidate_positions();
+        ExtractIf { storage: &mut self.storage, index: 0, pred }
     }
 
     /// Returns an iterator yielding references to the map's keys and their corresponding values in
     /// arbitrary order.
     ///
     /// The iterator's item type is `(&K, &V)`.
-    pub fn iter(&self) -> Iter<K, V> {
+    pub fn iter(&self) -> Iter<'_, K, V> {
         Iter { iter: self.storage.iter() }
     }
 
@@ -181,38 +396,353 @@ impl<K: Eq, V> LinearMap<K, V> {
     /// corresponding values in arbitrary order.
     ///
     /// The iterator's item type is `(&K, &mut V)`.
-    pub fn iter_mut(&mut self) -> IterMut<K, V> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
         IterMut { iter: self.storage.iter_mut() }
     }
 
+    /// Returns an iterator yielding copies of the map's keys and their corresponding values in
+    /// arbitrary order.
+    ///
+    /// The iterator's item type is `(K, V)`.
+    pub fn iter_copied(&self) -> IterCopied<'_, K, V> where K: Copy, V: Copy {
+        IterCopied { iter: self.storage.iter() }
+    }
+
+    /// Returns an iterator yielding clones of the map's keys and their corresponding values in
+    /// arbitrary order.
+    ///
+    /// Keys or values alone can be copied or cloned with the `copied` and `cloned` adapters of
+    /// [`keys`](#method.keys) and [`values`](#method.values).
+    ///
+    /// The iterator's item type is `(K, V)`.
+    pub fn iter_cloned(&self) -> IterCloned<'_, K, V> where K: Clone, V: Clone {
+        IterCloned { iter: self.storage.iter() }
+    }
+
+    /// Returns a map of references to this map's keys and values, in the same order.
+    ///
+    /// Only the entries' references are copied, so this is a cheap way to pass a borrowed view to
+    /// code written against maps of owned values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// fn sorted_keys<K: Ord, V>(map: LinearMap<K, V>) -> Vec<K> {
+    ///     let mut keys: Vec<K> = map.into_iter().map(|(k, _)| k).collect();
+    ///     keys.sort();
+    ///     keys
+    /// }
+    ///
+    /// let mut stock = LinearMap::new();
+    /// stock.insert(String::from("pears"), 4);
+    /// stock.insert(String::from("apples"), 3);
+    ///
+    /// assert_eq!(sorted_keys(stock.as_borrowed()), ["apples", "pears"]);
+    /// ```
+    pub fn as_borrowed(&self) -> LinearMap<&K, &V> {
+        LinearMap::from_storage(self.storage.iter().map(|(k, v)| (k, v)).collect())
+    }
+
+    /// Returns an iterator over the map's entries in batches of `chunk_size`, in the order of the
+    /// underlying vector. The last batch holds fewer entries if the map's length is not a
+    /// multiple of `chunk_size`.
+    ///
+    /// The iterator's item type is [`Iter`](struct.Iter.html), yielding `(&K, &V)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn chunks(&self, chunk_size: usize) -> Chunks<'_, K, V> {
+        Chunks { iter: self.storage.chunks(chunk_size) }
+    }
+
+    /// Returns an iterator over the map's entries in batches of `chunk_size`, with mutable
+    /// references to the values, in the order of the underlying vector. The last batch holds
+    /// fewer entries if the map's length is not a multiple of `chunk_size`.
+    ///
+    /// The iterator's item type is [`IterMut`](struct.IterMut.html), yielding `(&K, &mut V)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn chunks_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, K, V> {
+        ChunksMut { iter: self.storage.chunks_mut(chunk_size) }
+    }
+
     /// Returns an iterator yielding references to the map's keys in arbitrary order.
     ///
     /// The iterator's item type is `&K`.
-    pub fn keys(&self) -> Keys<K, V> {
+    pub fn keys(&self) -> Keys<'_, K, V> {
         Keys { iter: self.iter() }
     }
 
     /// Returns an iterator yielding references to the map's values in arbitrary order.
     ///
     /// The iterator's item type is `&V`.
-    pub fn values(&self) -> Values<K, V> {
+    pub fn values(&self) -> Values<'_, K, V> {
         Values { iter: self.iter() }
     }
 
     /// Returns an iterator yielding mutable references to the map's values in arbitrary order.
     ///
     /// The iterator's item type is `&mut V`.
-    pub fn values_mut(&mut self) -> ValuesMut<K, V> {
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
         ValuesMut { iter: self.iter_mut() }
     }
 
+    /// Returns an iterator yielding the position of each entry in the underlying vector along
+    /// with references to its key and value.
+    ///
+    /// The positions can be passed to [`get_index`](#method.get_index) and related methods as long
+    /// as no entries are inserted or removed in between.
+    ///
+    /// The iterator's item type is `(EntryIndex, &K, &V)`.
+    pub fn indexed_iter(&self) -> IndexedIter<'_, K, V> {
+        IndexedIter { iter: self.positions().zip(self.storage.iter()) }
+    }
+
+    /// Returns an iterator yielding the position of each entry in the underlying vector along
+    /// with a reference to its key and a mutable reference to its value.
+    ///
+    /// The iterator's item type is `(EntryIndex, &K, &mut V)`.
+    pub fn indexed_iter_mut(&mut self) -> IndexedIterMut<'_, K, V> {
+        IndexedIterMut { iter: self.positions().zip(self.storage.iter_mut()) }
+    }
+
+    /// Returns the position `index` of the underlying vector, for use with
+    /// [`get_index`](#method.get_index) and related methods.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn index_at(&self, index: usize) -> Option<EntryIndex> {
+        self.positions().nth(index)
+    }
+
+    /// Returns references to the key and value at the given position in the underlying vector.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `index` is stale, that is if entries were inserted or removed
+    /// since it was obtained.
+    pub fn get_index(&self, index: EntryIndex) -> Option<(&K, &V)> {
+        self.storage.get(self.resolve(index)).map(|e| (&e.0, &e.1))
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value at the given position
+    /// in the underlying vector.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `index` is stale, like [`get_index`](#method.get_index).
+    pub fn get_index_mut(&mut self, index: EntryIndex) -> Option<(&K, &mut V)> {
+        let index = self.resolve(index);
+        self.storage.get_mut(index).map(|e| (&e.0, &mut e.1))
+    }
+
+    /// Returns references to the key and value at the given position in the underlying vector,
+    /// without checking that the position is in bounds.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than [`len`](#method.len), which it is if it was obtained from this
+    /// map and no entries were inserted or removed since. Calling this method with an
+    /// out-of-bounds index is undefined behavior, even if the result is not used.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `index` is stale, like [`get_index`](#method.get_index).
+    pub unsafe fn get_index_unchecked(&self, index: EntryIndex) -> (&K, &V) {
+        let e = self.storage.get_unchecked(self.resolve(index));
+        (&e.0, &e.1)
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value at the given position
+    /// in the underlying vector, without checking that the position is in bounds.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`get_index_unchecked`](#method.get_index_unchecked).
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `index` is stale, like [`get_index`](#method.get_index).
+    pub unsafe fn get_index_unchecked_mut(&mut self, index: EntryIndex) -> (&K, &mut V) {
+        let index = self.resolve(index);
+        let e = self.storage.get_unchecked_mut(index);
+        (&e.0, &mut e.1)
+    }
+
+    /// Removes the entry at the given position in the underlying vector and returns it.
+    ///
+    /// The last entry is moved into the removed entry's place, like [`remove`](#method.remove)
+    /// does. Returns `None` if `index` is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `index` is stale, like [`get_index`](#method.get_index).
+    pub fn swap_remove_index(&mut self, index: EntryIndex) -> Option<(K, V)> {
+        let index = self.resolve(index);
+        if index < self.storage.len() {
+            self.invalidate_positions();
+            Some(self.storage.swap_remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Consumes the map and returns an iterator yielding its keys in arbitrary order.
+    ///
+    /// The iterator's item type is `K`.
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys { iter: self.into_iter() }
+    }
+
+    /// Consumes the map and returns an iterator yielding its values in arbitrary order.
+    ///
+    /// The iterator's item type is `V`.
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues { iter: self.into_iter() }
+    }
+
+    /// Consumes the map and returns its keys and values as two vectors of equal length, in the
+    /// same order: the value at each position corresponds to the key at that position.
+    pub fn into_keys_values(self) -> (Vec<K>, Vec<V>) {
+        self.storage.into_iter().unzip()
+    }
+
+    /// Returns a set of clones of the map's keys, in the same order.
+    ///
+    /// The map's keys are already distinct, so the set is built in a single pass without the
+    /// duplicate checks that collecting the keys into a set would perform.
+    pub fn keys_set(&self) -> LinearSet<K> where K: Clone {
+        let storage = self.storage.iter().map(|(k, _)| (k.clone(), ())).collect();
+        LinearSet { map: LinearMap::from_storage(storage) }
+    }
+
+    /// Returns a vector of clones of the map's values, in the same order as
+    /// [`keys`](#method.keys).
+    pub fn values_vec(&self) -> Vec<V> where V: Clone {
+        self.storage.iter().map(|(_, v)| v.clone()).collect()
+    }
+
+    /// Decomposes the map into a raw pointer to its entries, its length and its capacity, like
+    /// the vector of entries it wraps.
+    ///
+    /// The caller becomes responsible for the memory: the entries must be dropped and the
+    /// allocation freed, for example by rebuilding the map with
+    /// [`from_raw_parts`](#method.from_raw_parts).
+    pub fn into_raw_parts(self) -> (*mut (K, V), usize, usize) {
+        let mut storage = mem::ManuallyDrop::new(self.storage);
+        (storage.as_mut_ptr(), storage.len(), storage.capacity())
+    }
+
+    /// Creates a map directly from a pointer to entries, a length and a capacity.
+    ///
+    /// # Safety
+    ///
+    /// The arguments must satisfy the requirements of [`Vec::from_raw_parts`][vec], as they do
+    /// when they come from [`into_raw_parts`](#method.into_raw_parts). In addition, no two of the
+    /// `length` entries may have equal keys; violating this is a logic error rather than
+    /// undefined behavior.
+    ///
+    /// [vec]: https://doc.rust-lang.org/std/vec/struct.Vec.html#method.from_raw_parts
+    pub unsafe fn from_raw_parts(ptr: *mut (K, V), length: usize, capacity: usize) -> Self {
+        LinearMap::from_storage(Vec::from_raw_parts(ptr, length, capacity))
+    }
+
+    /// Returns an adapter that displays the map's entries in order, writing `key_sep` between
+    /// each key and its value and `pair_sep` between entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// assert_eq!(map.display_with("=", ", ").to_string(), "a=1, b=2");
+    /// ```
+    pub fn display_with<'a>(&'a self, key_sep: &'a str, pair_sep: &'a str)
+        -> DisplayWith<'a, K, V> {
+        DisplayWith { map: self, key_sep, pair_sep }
+    }
+}
+
+impl<K: PartialEq, V> LinearMap<K, V> {
+    /// Creates a map from an iterator of key-value pairs, failing on the first pair whose key is
+    /// already in the map.
+    ///
+    /// Unlike `collect`, which keeps the last value for each key, this rejects duplicates and
+    /// returns the offending pair in the error.
+    pub fn try_from_iter<I>(key_values: I) -> Result<Self, DuplicateKeyError<K, V>>
+    where I: IntoIterator<Item = (K, V)> {
+        let mut map = LinearMap::new();
+        map.try_extend_unique(key_values)?;
+        Ok(map)
+    }
+
+    /// Creates a map from an iterator of key-value pairs, keeping the first value for each key.
+    ///
+    /// This is the counterpart of `collect`, which keeps the last value.
+    pub fn from_iter_keep_first<I>(key_values: I) -> Self where I: IntoIterator<Item = (K, V)> {
+        let mut map = LinearMap::new();
+        map.extend_keep_first(key_values);
+        map
+    }
+
+    /// Extends the map with the given key-value pairs, ignoring pairs whose key is already in the
+    /// map.
+    ///
+    /// Unlike `extend`, which replaces the values of existing keys, this keeps the value already
+    /// in the map, including one inserted earlier in the same call.
+    pub fn extend_keep_first<I>(&mut self, key_values: I) where I: IntoIterator<Item = (K, V)> {
+        let iter = key_values.into_iter();
+        self.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            if !self.contains_key(&key) {
+                self.invalidate_positions();
+                self.storage.push((key, value));
+            }
+        }
+    }
+
+    /// Extends the map with the given key-value pairs, failing on the first pair whose key is
+    /// already in the map, including one inserted earlier in the same call.
+    ///
+    /// On failure the map is left as it was before the call and the offending pair is returned
+    /// in the error; the pairs before it are dropped.
+    pub fn try_extend_unique<I>(&mut self, key_values: I) -> Result<(), DuplicateKeyError<K, V>>
+    where I: IntoIterator<Item = (K, V)> {
+        let iter = key_values.into_iter();
+        self.reserve(iter.size_hint().0);
+        let len = self.storage.len();
+        for (key, value) in iter {
+            if self.contains_key(&key) {
+                // New pairs are only ever pushed, so this restores the map exactly.
+                self.storage.truncate(len);
+                return Err(DuplicateKeyError { key, value });
+            }
+            self.storage.push((key, value));
+        }
+        if self.storage.len() > len {
+            self.invalidate_positions();
+        }
+        Ok(())
+    }
+
     /// Returns a reference to the value in the map whose key is equal to the given key.
     ///
     /// Returns `None` if the map contains no such key.
     ///
-    /// The given key may be any borrowed form of the map's key type, but `Eq` on the borrowed form
-    /// *must* match that of the key type.
-    pub fn get<Q: ?Sized + Eq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+    /// The given key may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
         for (k, v) in self {
             if key == k.borrow() {
                 return Some(v);
@@ -225,9 +755,9 @@ impl<K: Eq, V> LinearMap<K, V> {
     ///
     /// Returns `None` if the map contains no such key.
     ///
-    /// The given key may be any borrowed form of the map's key type, but `Eq` on the borrowed form
-    /// *must* match that of the key type.
-    pub fn get_mut<Q: ?Sized + Eq>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+    /// The given key may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
         for (k, v) in self {
             if key == k.borrow() {
                 return Some(v);
@@ -236,14 +766,146 @@ impl<K: Eq, V> LinearMap<K, V> {
         None
     }
 
+    /// Returns references to the key in the map that is equal to the given key and to its value.
+    ///
+    /// Returns `None` if the map contains no such key.
+    ///
+    /// The given key may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn get_key_value<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<(&K, &V)>
+    where K: Borrow<Q> {
+        self.iter().find(|&(k, _)| key == k.borrow())
+    }
+
+    /// Returns a reference to the key in the map that is equal to the given key, along with a
+    /// mutable reference to its value.
+    ///
+    /// Returns `None` if the map contains no such key.
+    ///
+    /// The given key may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn get_key_value_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<(&K, &mut V)>
+    where K: Borrow<Q> {
+        self.iter_mut().find(|&(k, _)| key == k.borrow())
+    }
+
     /// Checks if the map contains a key that is equal to the given key.
     ///
-    /// The given key may be any borrowed form of the map's key type, but `Eq` on the borrowed form
-    /// *must* match that of the key type.
-    pub fn contains_key<Q: ?Sized + Eq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+    /// The given key may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where K: Borrow<Q> {
         self.get(key).is_some()
     }
 
+    /// Returns a reference to the value in the map whose key is equal to the given key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map contains no such key. Unlike indexing, the panic message includes the
+    /// missing key and the keys the map does contain.
+    pub fn get_expect<Q: ?Sized + PartialEq + Debug>(&self, key: &Q) -> &V
+    where K: Borrow<Q> + Debug {
+        match self.position(key) {
+            Some(i) => &self.storage[i].1,
+            None => self.missing_key(key),
+        }
+    }
+
+    /// Returns a mutable reference to the value in the map whose key is equal to the given key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map contains no such key, like [`get_expect`](#method.get_expect).
+    pub fn get_expect_mut<Q: ?Sized + PartialEq + Debug>(&mut self, key: &Q) -> &mut V
+    where K: Borrow<Q> + Debug {
+        match self.position(key) {
+            Some(i) => &mut self.storage[i].1,
+            None => self.missing_key(key),
+        }
+    }
+
+    #[cold]
+    fn missing_key<Q: ?Sized + Debug>(&self, key: &Q) -> ! where K: Debug {
+        let keys: Vec<&K> = self.keys().collect();
+        panic!("key {:?} not found in map with keys {:?}", key, keys)
+    }
+
+    /// Checks that no two entries of the map have equal keys.
+    ///
+    /// This always holds for maps built through the map's own methods, but may not after
+    /// [`from_raw_parts`](#method.from_raw_parts) or after keys were modified in place. On failure,
+    /// returns every key that appears more than once, each reported a single time, in the order
+    /// of its first appearance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// use linear_map::mutable_keys::MutableKeys;
+    ///
+    /// let mut fields = LinearMap::new();
+    /// for (i, name) in ["id", "Name", "ID", "tag", "name"].iter().enumerate() {
+    ///     fields.insert(name.to_string(), i);
+    /// }
+    /// assert_eq!(fields.validate_unique(), Ok(()));
+    ///
+    /// for (k, _) in fields.iter_full_mut() {
+    ///     k.make_ascii_lowercase();
+    /// }
+    /// assert_eq!(fields.validate_unique(), Err(vec![&"id".to_string(), &"name".to_string()]));
+    /// ```
+    pub fn validate_unique(&self) -> Result<(), Vec<&K>> {
+        let mut duplicates: Vec<&K> = Vec::new();
+        for (i, (k, _)) in self.storage.iter().enumerate() {
+            if self.storage[..i].iter().any(|(seen, _)| seen == k)
+                && !duplicates.contains(&k) {
+                duplicates.push(k);
+            }
+        }
+        if duplicates.is_empty() { Ok(()) } else { Err(duplicates) }
+    }
+
+    /// Checks if the map has exactly the same keys as `other`, in any order. Values are ignored.
+    pub fn keys_eq<W>(&self, other: &LinearMap<K, W>) -> bool {
+        self.len() == other.len() && self.keys_subset_of(other)
+    }
+
+    /// Checks if every key of the map is also a key of `other`. Values are ignored.
+    pub fn keys_subset_of<W>(&self, other: &LinearMap<K, W>) -> bool {
+        self.len() <= other.len() && self.keys().all(|k| other.contains_key(k))
+    }
+
+    /// Checks if every key-value pair of the map also appears in `other`: each key is a key of
+    /// `other`, with an equal value. Use [`keys_subset_of`](#method.keys_subset_of) to compare
+    /// keys only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let granted: LinearMap<_, _> = vec![("read", true), ("write", true)].into_iter().collect();
+    /// let requested: LinearMap<_, _> = vec![("read", true)].into_iter().collect();
+    /// assert!(requested.is_submap_of(&granted));
+    /// assert!(requested.is_proper_submap_of(&granted));
+    /// assert!(!granted.is_submap_of(&requested));
+    /// ```
+    pub fn is_submap_of(&self, other: &LinearMap<K, V>) -> bool where V: PartialEq {
+        self.len() <= other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+
+    /// Checks if the map is a submap of `other` and `other` has at least one more entry.
+    pub fn is_proper_submap_of(&self, other: &LinearMap<K, V>) -> bool where V: PartialEq {
+        self.len() < other.len() && self.is_submap_of(other)
+    }
+
+    /// Checks if every key of the map is also a key of `other`, and `other` has at least one more
+    /// key. Values are ignored.
+    pub fn keys_proper_subset_of<W>(&self, other: &LinearMap<K, W>) -> bool {
+        self.len() < other.len() && self.keys_subset_of(other)
+    }
+
     /// Inserts a key-value pair into the map.
     ///
     /// Returns `None` if the map did not contain a key that is equal to the given key.
@@ -261,66 +923,326 @@ impl<K: Eq, V> LinearMap<K, V> {
         }
     }
 
+    /// Inserts a key-value pair into the map if it contains no key equal to the given key.
+    ///
+    /// Returns a mutable reference to the inserted value. If the map already contains such a key,
+    /// nothing is updated, and an error holding the occupied entry and the rejected value is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// assert_eq!(*map.try_insert("a", 1).unwrap(), 1);
+    ///
+    /// let err = map.try_insert("a", 2).unwrap_err();
+    /// assert_eq!(err.entry.get(), &1);
+    /// assert_eq!(err.value, 2);
+    /// ```
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V>> {
+        match self.entry(key) {
+            Occupied(entry) => Err(OccupiedError { entry, value }),
+            Vacant(entry) => Ok(entry.insert(value)),
+        }
+    }
+
+    /// Inserts a key-value pair into the map like [`insert`](#method.insert), also returning a
+    /// token for the entry.
+    ///
+    /// The token can be passed to [`get_with_token`](#method.get_with_token) and
+    /// [`get_mut_with_token`](#method.get_mut_with_token) to look the key up again cheaply.
+    pub fn insert_full(&mut self, key: K, value: V) -> (EntryToken, Option<V>) {
+        match self.entry(key) {
+            Occupied(mut e) => (EntryToken { index: e.index }, Some(e.insert(value))),
+            Vacant(e) => {
+                let index = e.map.storage.len();
+                e.insert(value);
+                (EntryToken { index }, None)
+            }
+        }
+    }
+
+    /// Returns a token for the entry whose key is equal to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    ///
+    /// The given key may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn get_token<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<EntryToken>
+    where K: Borrow<Q> {
+        self.position(key).map(|index| EntryToken { index })
+    }
+
+    /// Returns a reference to the value whose key is equal to the given key, using `token` to
+    /// skip the search.
+    ///
+    /// The token is checked by comparing the key at its position with the given key, which costs
+    /// a single comparison. Tokens stay valid while only values change; if entries have been
+    /// inserted or removed since the token was obtained and the check fails, the map is searched
+    /// as by [`get`](#method.get) and `token` is updated to the key's new position.
+    pub fn get_with_token<Q: ?Sized + PartialEq>(&self, token: &mut EntryToken, key: &Q)
+        -> Option<&V> where K: Borrow<Q> {
+        let index = self.revalidate(token, key)?;
+        Some(&self.storage[index].1)
+    }
+
+    /// Returns a mutable reference to the value whose key is equal to the given key, using
+    /// `token` to skip the search.
+    ///
+    /// See [`get_with_token`](#method.get_with_token) for details.
+    pub fn get_mut_with_token<Q: ?Sized + PartialEq>(&mut self, token: &mut EntryToken, key: &Q)
+        -> Option<&mut V> where K: Borrow<Q> {
+        let index = self.revalidate(token, key)?;
+        Some(&mut self.storage[index].1)
+    }
+
+    pub(crate) fn position<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<usize>
+    where K: Borrow<Q> {
+        self.storage.iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    fn revalidate<Q: ?Sized + PartialEq>(&self, token: &mut EntryToken, key: &Q) -> Option<usize>
+    where K: Borrow<Q> {
+        match self.storage.get(token.index) {
+            Some((k, _)) if k.borrow() == key => Some(token.index),
+            _ => {
+                let index = self.position(key)?;
+                token.index = index;
+                Some(index)
+            }
+        }
+    }
+
     /// Removes the key in the map that is equal to the given key and returns its corresponding
     /// value.
     ///
     /// Returns `None` if the map contained no such key.
     ///
-    /// The given key may be any borrowed form of the map's key type, but `Eq` on the borrowed form
-    /// *must* match that of the key type.
-    pub fn remove<Q: ?Sized + Eq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+    /// The given key may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
         for i in 0..self.storage.len() {
             if self.storage[i].0.borrow() == key {
+                self.invalidate_positions();
                 return Some(self.storage.swap_remove(i).1);
             }
         }
         None
     }
 
+    /// Removes the key in the map that is equal to the given key and returns it along with its
+    /// corresponding value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    ///
+    /// Like [`remove`](#method.remove), this moves the last entry into the removed entry's place.
+    ///
+    /// The given key may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn remove_entry<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<(K, V)>
+    where K: Borrow<Q> {
+        let i = self.position(key)?;
+        self.invalidate_positions();
+        Some(self.storage.swap_remove(i))
+    }
+
+    /// Removes the key in the map that is equal to the given key and returns its corresponding
+    /// value, shifting all following entries down.
+    ///
+    /// Returns `None` if the map contained no such key.
+    ///
+    /// Unlike [`remove`](#method.remove), which moves the last entry into the removed entry's
+    /// place, this preserves the order of the remaining entries, at the cost of moving all of
+    /// them that follow the removed one.
+    pub fn shift_remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let i = self.position(key)?;
+        self.invalidate_positions();
+        Some(self.storage.remove(i).1)
+    }
+
+    /// Exchanges the values of the two keys in the map that are equal to the given keys.
+    ///
+    /// Returns `false`, leaving the map unchanged, if either key is missing from the map.
+    ///
+    /// The given keys may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn swap<Q: ?Sized + PartialEq>(&mut self, k1: &Q, k2: &Q) -> bool where K: Borrow<Q> {
+        let mut i1 = None;
+        let mut i2 = None;
+        for (i, (k, _)) in self.storage.iter().enumerate() {
+            let k = k.borrow();
+            if i1.is_none() && k == k1 { i1 = Some(i); }
+            if i2.is_none() && k == k2 { i2 = Some(i); }
+            if i1.is_some() && i2.is_some() { break; }
+        }
+        match (i1, i2) {
+            (Some(i1), Some(i2)) => {
+                if i1 != i2 {
+                    let (lo, hi) = if i1 < i2 { (i1, i2) } else { (i2, i1) };
+                    let (left, right) = self.storage.split_at_mut(hi);
+                    mem::swap(&mut left[lo].1, &mut right[0].1);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Replaces the key in the map that is equal to `old` with `new`, keeping its value and its
+    /// position in the underlying vector.
+    ///
+    /// Fails, leaving the map unchanged and handing `new` back, if the map contains no key equal
+    /// to `old` or already contains a key equal to `new`. Renaming a key to an equal key
+    /// succeeds and replaces the stored key.
+    ///
+    /// The given key may be any borrowed form of the map's key type, but `PartialEq` on the
+    /// borrowed form *must* match that of the key type.
+    pub fn rename_key<Q: ?Sized + PartialEq>(&mut self, old: &Q, new: K)
+        -> Result<(), RenameError<K>>
+    where K: Borrow<Q> {
+        let mut index = None;
+        for (i, (k, _)) in self.storage.iter().enumerate() {
+            if k.borrow() == old {
+                index = Some(i);
+            } else if *k == new {
+                return Err(RenameError::KeyExists(new));
+            }
+        }
+        match index {
+            Some(i) => { self.storage[i].0 = new; Ok(()) }
+            None => Err(RenameError::NotFound(new)),
+        }
+    }
+
+    /// Returns a new map containing clones of the entries whose keys are among the given keys.
+    ///
+    /// Keys that are not in the map are ignored. The map is traversed once; each of its keys is
+    /// compared against the given keys.
+    pub fn sub_map<'q, Q, I>(&self, keys: I) -> LinearMap<K, V>
+    where K: Borrow<Q> + Clone, V: Clone, Q: ?Sized + PartialEq + 'q,
+          I: IntoIterator<Item = &'q Q> {
+        let keys: Vec<&Q> = keys.into_iter().collect();
+        let storage = self.storage.iter()
+            .filter(|&(k, _)| keys.contains(&k.borrow()))
+            .cloned()
+            .collect();
+        LinearMap::from_storage(storage)
+    }
+
+    /// Consumes the map and returns a map containing only the entries whose keys are among the
+    /// given keys.
+    ///
+    /// This is like [`sub_map`](#method.sub_map), but moves the entries instead of cloning them.
+    pub fn extract_keys<'q, Q, I>(self, keys: I) -> LinearMap<K, V>
+    where K: Borrow<Q>, Q: ?Sized + PartialEq + 'q, I: IntoIterator<Item = &'q Q> {
+        let keys: Vec<&Q> = keys.into_iter().collect();
+        let storage = self.storage.into_iter()
+            .filter(|(k, _)| keys.contains(&k.borrow()))
+            .collect();
+        LinearMap::from_storage(storage)
+    }
+
     /// Returns the given key's corresponding entry in the map for in-place manipulation.
-    pub fn entry(&mut self, key: K) -> Entry<K, V> {
-        match self.storage.iter().position(|&(ref k, _)| key == *k) {
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.storage.iter().position(|(k, _)| key == *k) {
             None => Vacant(VacantEntry {
                 map: self,
-                key: key
+                key
             }),
             Some(index) => Occupied(OccupiedEntry {
                 map: self,
-                index: index
+                index
             })
         }
     }
+
+    /// Returns the entry in the map for the given borrowed key, for in-place manipulation.
+    ///
+    /// Unlike [`entry`](#method.entry), this does not need an owned key: the key is only
+    /// converted with `to_owned` if a vacant entry is inserted into.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut counts: LinearMap<String, u32> = LinearMap::new();
+    /// for word in "a b a".split(' ') {
+    ///     *counts.entry_ref(word).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(counts["a"], 2);
+    /// assert_eq!(counts["b"], 1);
+    /// ```
+    pub fn entry_ref<'b, Q>(&mut self, key: &'b Q) -> EntryRef<'_, 'b, K, Q, V>
+    where K: Borrow<Q>, Q: ?Sized + PartialEq + ToOwned<Owned = K> {
+        match self.position(key) {
+            None => EntryRef::Vacant(VacantEntryRef { map: self, key }),
+            Some(index) => EntryRef::Occupied(OccupiedEntry { map: self, index }),
+        }
+    }
 }
 
 impl<K: Clone, V: Clone> Clone for LinearMap<K, V> {
     fn clone(&self) -> Self {
-        LinearMap { storage: self.storage.clone() }
+        // Positions obtained from the original also hold for the clone.
+        LinearMap {
+            storage: self.storage.clone(),
+            #[cfg(debug_assertions)]
+            generation: self.generation,
+        }
     }
 
     fn clone_from(&mut self, other: &Self) {
+        self.invalidate_positions();
         self.storage.clone_from(&other.storage);
     }
 }
 
-impl<K: Eq + Debug, V: Debug> Debug for LinearMap<K, V> {
+impl<K: Debug, V: Debug> Debug for LinearMap<K, V> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_map().entries(self).finish()
     }
 }
 
-impl<K: Eq, V> Default for LinearMap<K, V> {
+impl<K, V> Default for LinearMap<K, V> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<K: Eq, V> Extend<(K, V)> for LinearMap<K, V> {
+impl<K: PartialEq, V> Extend<(K, V)> for LinearMap<K, V> {
     fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
         for (key, value) in key_values { self.insert(key, value); }
     }
 }
 
-impl<K: Eq, V> iter::FromIterator<(K, V)> for LinearMap<K, V> {
+/// Extends the map with copies of borrowed key-value pairs, such as those yielded by another
+/// map's [`iter`](struct.LinearMap.html#method.iter).
+impl<'a, K: PartialEq + Copy, V: Copy> Extend<(&'a K, &'a V)> for LinearMap<K, V> {
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, key_values: I) {
+        self.extend(key_values.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+/// Collects key-value pairs into a map, with later values replacing earlier ones for equal keys.
+///
+/// Through the standard library's implementation for `Result`, fallible pairs can be collected
+/// into a `Result<LinearMap<K, V>, E>`, which stops at the first error:
+///
+/// ```
+/// use linear_map::LinearMap;
+///
+/// let parsed: Result<LinearMap<&str, i32>, _> = "a=1,b=x,c=3".split(',')
+///     .map(|pair| {
+///         let (k, v) = pair.split_once('=').unwrap();
+///         v.parse().map(|v| (k, v))
+///     })
+///     .collect();
+/// assert!(parsed.is_err());
+/// ```
+impl<K: PartialEq, V> iter::FromIterator<(K, V)> for LinearMap<K, V> {
     fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
         let mut map = Self::new();
         map.extend(key_values);
@@ -328,7 +1250,34 @@ impl<K: Eq, V> iter::FromIterator<(K, V)> for LinearMap<K, V> {
     }
 }
 
-impl<'a, K: Eq + Borrow<Q>, V, Q: ?Sized + Eq> ops::Index<&'a Q> for LinearMap<K, V> {
+/// Merges a sequence of maps into one. When several maps contain equal keys, the value from the
+/// last such map wins.
+///
+/// The first map is reused as the result, so its allocation is kept.
+impl<K: PartialEq, V> iter::FromIterator<LinearMap<K, V>> for LinearMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = LinearMap<K, V>>>(maps: I) -> Self {
+        let mut maps = maps.into_iter();
+        let mut merged = match maps.next() {
+            Some(map) => map,
+            None => return Self::new(),
+        };
+        for map in maps {
+            merged.reserve(map.len());
+            merged.extend(map);
+        }
+        merged
+    }
+}
+
+/// Merges a sequence of maps into one, like the corresponding `FromIterator` implementation.
+impl<K: PartialEq, V> iter::Sum for LinearMap<K, V> {
+    fn sum<I: Iterator<Item = LinearMap<K, V>>>(maps: I) -> Self {
+        maps.collect()
+    }
+}
+
+impl<'a, K, V, Q> ops::Index<&'a Q> for LinearMap<K, V>
+where K: PartialEq + Borrow<Q>, Q: ?Sized + PartialEq {
     type Output = V;
 
     fn index(&self, key: &'a Q) -> &V {
@@ -336,7 +1285,7 @@ impl<'a, K: Eq + Borrow<Q>, V, Q: ?Sized + Eq> ops::Index<&'a Q> for LinearMap<K
     }
 }
 
-impl<K: Eq, V: PartialEq> PartialEq for LinearMap<K, V> {
+impl<K: PartialEq, V: PartialEq> PartialEq for LinearMap<K, V> {
     fn eq(&self, other: &Self) -> bool {
         if self.len() != other.len() {
             return false;
@@ -354,9 +1303,94 @@ impl<K: Eq, V: PartialEq> PartialEq for LinearMap<K, V> {
 
 impl<K: Eq, V: Eq> Eq for LinearMap<K, V> {}
 
-impl<K: Eq, V> Into<Vec<(K, V)>> for LinearMap<K, V> {
-    fn into(self) -> Vec<(K, V)> {
-        self.storage
+/// Creates a map from an array of key-value pairs, with later values replacing earlier ones for
+/// equal keys.
+///
+/// ```
+/// use linear_map::LinearMap;
+///
+/// let map = LinearMap::from([("a", 1), ("b", 2), ("a", 3)]);
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map["a"], 3);
+/// ```
+impl<K: PartialEq, V, const N: usize> From<[(K, V); N]> for LinearMap<K, V> {
+    fn from(key_values: [(K, V); N]) -> Self {
+        let mut map = Self::with_capacity(N);
+        map.extend(key_values);
+        map
+    }
+}
+
+/// Creates a map from a vector of key-value pairs, reusing its allocation.
+///
+/// Equal keys are handled like repeated [`insert`](struct.LinearMap.html#method.insert) calls:
+/// the first occurrence of a key keeps its key and position, and the value of the last
+/// occurrence replaces the earlier ones.
+impl<K: PartialEq, V> From<Vec<(K, V)>> for LinearMap<K, V> {
+    fn from(mut storage: Vec<(K, V)>) -> Self {
+        let mut len = 0;
+        for i in 0..storage.len() {
+            let (kept, rest) = storage.split_at_mut(i);
+            match kept[..len].iter().position(|e| e.0 == rest[0].0) {
+                Some(j) => mem::swap(&mut kept[j].1, &mut rest[0].1),
+                None => {
+                    storage.swap(len, i);
+                    len += 1;
+                }
+            }
+        }
+        storage.truncate(len);
+        LinearMap::from_storage(storage)
+    }
+}
+
+impl<K, V> From<LinearMap<K, V>> for Vec<(K, V)> {
+    fn from(map: LinearMap<K, V>) -> Vec<(K, V)> {
+        map.storage
+    }
+}
+
+/// An opaque handle to an entry of a `LinearMap`, used to repeat lookups of the same key cheaply.
+///
+/// A token records the position of an entry. It is not tied to a borrow of the map, so it may
+/// become stale when entries are inserted or removed; the `*_with_token` methods detect this by
+/// checking the key and fall back to a search.
+///
+/// See [`LinearMap::get_with_token`](struct.LinearMap.html#method.get_with_token) for details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntryToken {
+    index: usize,
+}
+
+/// A position in a `LinearMap`'s underlying vector.
+///
+/// Positions are yielded by [`LinearMap::indexed_iter`] and [`LinearMap::indexed_iter_mut`], or
+/// made from a number with [`LinearMap::index_at`], and are passed to
+/// [`LinearMap::get_index`] and the methods around it.
+///
+/// Inserting or removing entries moves other entries to different positions, so a position is
+/// only meaningful until entries are next inserted or removed; methods that may remove entries,
+/// such as `retain` or `drain`, count as a removal even if they keep every entry. Debug builds
+/// stamp each position with a counter of such changes and panic when a stale position is used,
+/// instead of silently addressing another entry. Unlike an [`EntryToken`], a position is never
+/// revalidated.
+///
+/// [`LinearMap::indexed_iter`]: struct.LinearMap.html#method.indexed_iter
+/// [`LinearMap::indexed_iter_mut`]: struct.LinearMap.html#method.indexed_iter_mut
+/// [`LinearMap::index_at`]: struct.LinearMap.html#method.index_at
+/// [`LinearMap::get_index`]: struct.LinearMap.html#method.get_index
+/// [`EntryToken`]: struct.EntryToken.html
+#[derive(Clone, Copy, Debug)]
+pub struct EntryIndex {
+    index: usize,
+    #[cfg(debug_assertions)]
+    generation: u64,
+}
+
+impl EntryIndex {
+    /// Returns the position as a number.
+    pub fn get(self) -> usize {
+        self.index
     }
 }
 
@@ -364,6 +1398,18 @@ impl<K: Eq, V> Into<Vec<(K, V)>> for LinearMap<K, V> {
 ///
 /// The created `LinearMap` has a capacity set to the number of entries provided.
 ///
+/// When every key is a literal, repeating a key is a compile-time error:
+///
+/// ```compile_fail,E0080
+/// #[macro_use] extern crate linear_map;
+/// # fn main() {
+/// let map = linear_map!{
+///     "a" => 1,
+///     "a" => 2,
+/// };
+/// # }
+/// ```
+///
 /// # Example
 ///
 /// ```
@@ -381,6 +1427,28 @@ impl<K: Eq, V> Into<Vec<(K, V)>> for LinearMap<K, V> {
 /// ```
 #[macro_export]
 macro_rules! linear_map {
+    () => { $crate::LinearMap::new() };
+    ($($key:literal => $value:expr,)+) => { linear_map!($($key => $value),+) };
+    ($($key:literal => $value:expr),+) => {
+        {
+            const _: () = assert!(
+                !$crate::__has_duplicate(&[$(stringify!($key)),+]),
+                "duplicate key in linear_map!",
+            );
+            // Keys that are equal without being spelled the same, like `1` and `0x1`, make one
+            // of these patterns unreachable. The `None` case keeps the last arm reachable.
+            let _ = |key| match Some(key) {
+                $(Some($key) => {})+
+                _ => {}
+            };
+            let _cap = <[&str]>::len(&[$(stringify!($key)),*]);
+            let mut _map = $crate::LinearMap::with_capacity(_cap);
+            $(
+                _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
     ($($key:expr => $value:expr,)+) => { linear_map!($($key => $value),+) };
     ($($key:expr => $value:expr),*) => {
         {
@@ -394,20 +1462,52 @@ macro_rules! linear_map {
     };
 }
 
+/// Checks whether any two of the given literals are spelled the same, for `linear_map!`.
+#[doc(hidden)]
+pub const fn __has_duplicate(keys: &[&str]) -> bool {
+    let mut i = 0;
+    while i < keys.len() {
+        let mut j = i + 1;
+        while j < keys.len() {
+            if str_eq(keys[i], keys[j]) {
+                return true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
 /// A view into a single occupied location in a `LinearMap`.
 ///
 /// See [`LinearMap::entry`](struct.LinearMap.html#method.entry) for details.
 pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
-    map: &'a mut LinearMap<K, V>,
-    index: usize,
+    pub(crate) map: &'a mut LinearMap<K, V>,
+    pub(crate) index: usize,
 }
 
 /// A view into a single vacant location in a `LinearMap`.
 ///
 /// See [`LinearMap::entry`](struct.LinearMap.html#method.entry) for details.
 pub struct VacantEntry<'a, K: 'a, V: 'a> {
-    map: &'a mut LinearMap<K, V>,
-    key: K,
+    pub(crate) map: &'a mut LinearMap<K, V>,
+    pub(crate) key: K,
 }
 
 /// A view into a single entry in a `LinearMap`.
@@ -422,6 +1522,17 @@ pub enum Entry<'a, K: 'a, V: 'a> {
 }
 
 impl<'a, K, V> Entry<'a, K, V> {
+    /// Returns a reference to the entry's key.
+    ///
+    /// This is the key in the map if the entry is occupied, and the key passed to
+    /// [`LinearMap::entry`](struct.LinearMap.html#method.entry) if it is vacant.
+    pub fn key(&self) -> &K {
+        match *self {
+            Occupied(ref entry) => entry.key(),
+            Vacant(ref entry) => entry.key(),
+        }
+    }
+
     /// Ensures that the entry is occupied by inserting the given value if it is vacant.
     ///
     /// Returns a mutable reference to the entry's value.
@@ -442,9 +1553,163 @@ impl<'a, K, V> Entry<'a, K, V> {
             Vacant(entry) => entry.insert(default())
         }
     }
+
+    /// Ensures that the entry is occupied by inserting the default value if it is vacant.
+    ///
+    /// Returns a mutable reference to the entry's value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut groups: LinearMap<&str, Vec<u32>> = LinearMap::new();
+    /// groups.entry("even").or_default().push(2);
+    /// groups.entry("even").or_default().push(4);
+    /// assert_eq!(groups["even"], [2, 4]);
+    /// ```
+    pub fn or_default(self) -> &'a mut V where V: Default {
+        self.or_insert_with(V::default)
+    }
+
+    /// Sets the entry's value, inserting the entry if it is vacant, and returns a view of the
+    /// occupied entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut map = LinearMap::new();
+    /// let entry = map.entry("a").insert_entry(1);
+    /// assert_eq!(entry.key(), &"a");
+    /// assert_eq!(entry.remove(), 1);
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V> {
+        match self {
+            Occupied(mut entry) => {
+                entry.insert(value);
+                entry
+            }
+            Vacant(entry) => entry.insert_entry(value),
+        }
+    }
+
+    /// Calls the given function on the entry's value if it is occupied, and returns the entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut counts = LinearMap::new();
+    /// for word in ["a", "b", "a"] {
+    ///     counts.entry(word).and_modify(|n| *n += 1).or_insert(1);
+    /// }
+    /// assert_eq!(counts["a"], 2);
+    /// assert_eq!(counts["b"], 1);
+    /// ```
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Occupied(mut entry) => {
+                f(entry.get_mut());
+                Occupied(entry)
+            }
+            Vacant(entry) => Vacant(entry),
+        }
+    }
+}
+
+/// A view into a single vacant location in a `LinearMap`, holding a borrowed key.
+///
+/// See [`LinearMap::entry_ref`](struct.LinearMap.html#method.entry_ref) for details.
+pub struct VacantEntryRef<'a, 'b, K: 'a, Q: ?Sized + 'b, V: 'a> {
+    map: &'a mut LinearMap<K, V>,
+    key: &'b Q,
+}
+
+/// A view into a single entry in a `LinearMap`, looked up by a borrowed key.
+///
+/// See [`LinearMap::entry_ref`](struct.LinearMap.html#method.entry_ref) for details.
+pub enum EntryRef<'a, 'b, K: 'a, Q: ?Sized + 'b, V: 'a> {
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V>),
+
+    /// A vacant entry.
+    Vacant(VacantEntryRef<'a, 'b, K, Q, V>),
+}
+
+impl<'a, 'b, K, Q, V> EntryRef<'a, 'b, K, Q, V>
+where K: Borrow<Q>, Q: ?Sized + ToOwned<Owned = K> {
+    /// Returns a reference to the entry's key.
+    ///
+    /// This is the key in the map if the entry is occupied, and the key passed to
+    /// [`LinearMap::entry_ref`](struct.LinearMap.html#method.entry_ref) if it is vacant.
+    pub fn key(&self) -> &Q {
+        match *self {
+            EntryRef::Occupied(ref entry) => entry.key().borrow(),
+            EntryRef::Vacant(ref entry) => entry.key(),
+        }
+    }
+
+    /// Ensures that the entry is occupied by inserting the given value if it is vacant.
+    ///
+    /// Returns a mutable reference to the entry's value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Ensures that the entry is occupied by inserting the result of the given function if it
+    /// is vacant.
+    ///
+    /// Returns a mutable reference to the entry's value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures that the entry is occupied by inserting the default value if it is vacant.
+    ///
+    /// Returns a mutable reference to the entry's value.
+    pub fn or_default(self) -> &'a mut V where V: Default {
+        self.or_insert_with(V::default)
+    }
+
+    /// Calls the given function on the entry's value if it is occupied, and returns the entry.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            EntryRef::Occupied(mut entry) => {
+                f(entry.get_mut());
+                EntryRef::Occupied(entry)
+            }
+            EntryRef::Vacant(entry) => EntryRef::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, 'b, K, Q, V> VacantEntryRef<'a, 'b, K, Q, V> where Q: ?Sized + ToOwned<Owned = K> {
+    /// Returns a reference to the borrowed key that would be inserted.
+    pub fn key(&self) -> &'b Q {
+        self.key
+    }
+
+    /// Inserts the entry into the map with an owned copy of the key and the given value.
+    ///
+    /// Returns a mutable reference to the entry's value with the same lifetime as the map.
+    pub fn insert(self, value: V) -> &'a mut V {
+        VacantEntry { map: self.map, key: self.key.to_owned() }.insert(value)
+    }
 }
 
 impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// Returns a reference to the entry's key in the map.
+    pub fn key(&self) -> &K {
+        &self.map.storage[self.index].0
+    }
+
     /// Returns a reference to the entry's value.
     pub fn get(&self) -> &V {
         &self.map.storage[self.index].1
@@ -460,25 +1725,126 @@ impl<'a, K, V> OccupiedEntry<'a, K, V> {
         &mut self.map.storage[self.index].1
     }
 
+    /// Returns a reference to the entry's key in the map and a mutable reference to its value.
+    pub fn get_key_value_mut(&mut self) -> (&K, &mut V) {
+        let e = &mut self.map.storage[self.index];
+        (&e.0, &mut e.1)
+    }
+
+    /// Returns a reference to the entry's key in the map and a mutable reference to its value,
+    /// with the same lifetime as the map.
+    pub fn into_key_value_mut(self) -> (&'a K, &'a mut V) {
+        let e = &mut self.map.storage[self.index];
+        (&e.0, &mut e.1)
+    }
+
     /// Replaces the entry's value with the given one and returns the previous value.
     pub fn insert(&mut self, value: V) -> V {
         mem::replace(self.get_mut(), value)
     }
 
+    /// Replaces the entry's key in the map with the given key, and returns the previous key.
+    ///
+    /// This matters for keys that can be `==` without being identical, such as strings compared
+    /// case-insensitively. The given key should be equal to the previous one, or the map may end
+    /// up with duplicate keys.
+    pub fn replace_key(&mut self, key: K) -> K {
+        mem::replace(&mut self.map.storage[self.index].0, key)
+    }
+
+    /// Replaces the entry's key and value in the map with the given ones, and returns the
+    /// previous key and value.
+    ///
+    /// Like for [`replace_key`](#method.replace_key), the given key should be equal to the
+    /// previous one.
+    pub fn replace_entry(self, key: K, value: V) -> (K, V) {
+        mem::replace(&mut self.map.storage[self.index], (key, value))
+    }
+
     /// Removes the entry from the map and returns its value.
+    ///
+    /// This is the same as [`swap_remove`](#method.swap_remove).
     pub fn remove(self) -> V {
+        self.swap_remove()
+    }
+
+    /// Removes the entry from the map and returns its key and value.
+    ///
+    /// Like [`remove`](#method.remove), this moves the last entry into the removed entry's place.
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.invalidate_positions();
+        self.map.storage.swap_remove(self.index)
+    }
+
+    /// Removes the entry from the map by moving the last entry into its place, and returns its
+    /// value.
+    ///
+    /// This takes `O(1)` time but changes the order of the remaining entries.
+    pub fn swap_remove(self) -> V {
+        self.map.invalidate_positions();
         self.map.storage.swap_remove(self.index).1
     }
+
+    /// Removes the entry from the map by shifting all following entries down, and returns its
+    /// value.
+    ///
+    /// This preserves the order of the remaining entries but takes `O(n)` time.
+    pub fn shift_remove(self) -> V {
+        self.map.invalidate_positions();
+        self.map.storage.remove(self.index).1
+    }
 }
 
 impl<'a, K, V> VacantEntry<'a, K, V> {
+    /// Returns a reference to the key that would be inserted.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the key that would be inserted, without inserting it.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
     /// Inserts the entry into the map with the given value.
     ///
     /// Returns a mutable reference to the entry's value with the same lifetime as the map.
     pub fn insert(self, value: V) -> &'a mut V {
+        self.map.invalidate_positions();
         self.map.storage.push((self.key, value));
         &mut self.map.storage.last_mut().unwrap().1
     }
+
+    /// Inserts the entry into the map with the given value.
+    ///
+    /// Returns a view of the now occupied entry.
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V> {
+        let index = self.map.storage.len();
+        self.map.invalidate_positions();
+        self.map.storage.push((self.key, value));
+        OccupiedEntry { map: self.map, index }
+    }
+}
+
+/// An adapter displaying the entries of a `LinearMap` with custom separators.
+///
+/// See [`LinearMap::display_with`](struct.LinearMap.html#method.display_with) for details.
+pub struct DisplayWith<'a, K: 'a, V: 'a> {
+    map: &'a LinearMap<K, V>,
+    key_sep: &'a str,
+    pair_sep: &'a str,
+}
+
+impl<'a, K: fmt::Display, V: fmt::Display> fmt::Display for DisplayWith<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (k, v)) in self.map.iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.pair_sep)?;
+            }
+            write!(f, "{}{}{}", k, self.key_sep, v)?;
+        }
+        Ok(())
+    }
 }
 
 /// A consuming iterator over a `LinearMap`.
@@ -487,7 +1853,7 @@ impl<'a, K, V> VacantEntry<'a, K, V> {
 ///
 /// Acquire through [`IntoIterator`](struct.LinearMap.html#method.into_iter).
 pub struct IntoIter<K, V> {
-    iter: vec::IntoIter<(K, V)>,
+    pub(crate) iter: vec::IntoIter<(K, V)>,
 }
 
 impl<K, V> Iterator for IntoIter<K, V> {
@@ -514,11 +1880,125 @@ impl<K, V> ExactSizeIterator for IntoIter<K, V> {
     }
 }
 
+/// A consuming iterator over a `LinearMap`'s keys.
+///
+/// See [`LinearMap::into_keys`](struct.LinearMap.html#method.into_keys) for details.
+pub struct IntoKeys<K, V> {
+    iter: IntoIter<K, V>,
+}
+
+/// A consuming iterator over a `LinearMap`'s values.
+///
+/// See [`LinearMap::into_values`](struct.LinearMap.html#method.into_values) for details.
+pub struct IntoValues<K, V> {
+    iter: IntoIter<K, V>,
+}
+
 /// A draining iterator over a `LinearMap`.
 ///
 /// See [`LinearMap::drain`](struct.LinearMap.html#method.drain) for details.
 pub struct Drain<'a, K: 'a, V: 'a> {
-    iter: vec::Drain<'a, (K, V)>,
+    // A raw pointer rather than the `&'a mut Vec` itself keeps the iterator covariant in `K`
+    // and `V`; the marker ties it to the borrow of the map and to the pairs it owns.
+    buf: NonNull<(K, V)>,
+    front: usize,
+    back: usize,
+    marker: PhantomData<(&'a mut (), (K, V))>,
+}
+
+// SAFETY: a `Drain` owns the pairs it has not yielded yet, like `vec::Drain`.
+unsafe impl<'a, K: Send, V: Send> Send for Drain<'a, K, V> {}
+unsafe impl<'a, K: Sync, V: Sync> Sync for Drain<'a, K, V> {}
+
+impl<'a, K, V> Drain<'a, K, V> {
+    /// Empties `storage` up front, so that leaking the iterator leaves it empty, and yields the
+    /// pairs between `front` and `back` straight from its buffer.
+    pub(crate) fn new(storage: &'a mut Vec<(K, V)>) -> Self {
+        let back = storage.len();
+        // SAFETY: the pairs stay initialized in the buffer; from now on they are owned by the
+        // iterator, which yields or drops each of them exactly once. The buffer is not touched
+        // by anything else while `storage` is borrowed.
+        unsafe { storage.set_len(0); }
+        // SAFETY: a vector's pointer is never null, even when it has not allocated.
+        let buf = unsafe { NonNull::new_unchecked(storage.as_mut_ptr()) };
+        Drain { buf, front: 0, back, marker: PhantomData }
+    }
+}
+
+impl<'a, K, V> Iterator for Drain<'a, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        if self.front == self.back {
+            return None;
+        }
+        // SAFETY: `front < back`, and the pair at `front` has not been yielded yet.
+        let e = unsafe { ptr::read(self.buf.as_ptr().add(self.front)) };
+        self.front += 1;
+        Some(e)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Drain<'a, K, V> {
+    fn next_back(&mut self) -> Option<(K, V)> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        // SAFETY: the pair at `back` has not been yielded yet.
+        Some(unsafe { ptr::read(self.buf.as_ptr().add(self.back)) })
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Drain<'a, K, V> {
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+}
+
+impl<'a, K, V> Drop for Drain<'a, K, V> {
+    fn drop(&mut self) {
+        let len = self.back - self.front;
+        // SAFETY: the pairs between `front` and `back` are initialized and not yet yielded.
+        unsafe {
+            let rest = self.buf.as_ptr().add(self.front);
+            self.front = self.back;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(rest, len));
+        }
+    }
+}
+
+/// An iterator removing the entries of a `LinearMap` that match a predicate.
+///
+/// See [`LinearMap::extract_if`](struct.LinearMap.html#method.extract_if) for details.
+pub struct ExtractIf<'a, K: 'a, V: 'a, F> {
+    storage: &'a mut Vec<(K, V)>,
+    index: usize,
+    pred: F,
+}
+
+impl<'a, K, V, F> Iterator for ExtractIf<'a, K, V, F> where F: FnMut(&K, &mut V) -> bool {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        while let Some(e) = self.storage.get_mut(self.index) {
+            if (self.pred)(&e.0, &mut e.1) {
+                // The last entry moves into this slot and is visited next.
+                return Some(self.storage.swap_remove(self.index));
+            }
+            self.index += 1;
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.storage.len() - self.index))
+    }
 }
 
 /// An iterator yielding references to a `LinearMap`'s keys and their corresponding values.
@@ -536,6 +2016,34 @@ pub struct IterMut<'a, K: 'a, V: 'a> {
     iter: slice::IterMut<'a, (K, V)>,
 }
 
+/// An iterator yielding copies of a `LinearMap`'s keys and their corresponding values.
+///
+/// See [`LinearMap::iter_copied`](struct.LinearMap.html#method.iter_copied) for details.
+pub struct IterCopied<'a, K: 'a, V: 'a> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+/// An iterator yielding clones of a `LinearMap`'s keys and their corresponding values.
+///
+/// See [`LinearMap::iter_cloned`](struct.LinearMap.html#method.iter_cloned) for details.
+pub struct IterCloned<'a, K: 'a, V: 'a> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+/// An iterator over batches of a `LinearMap`'s entries.
+///
+/// See [`LinearMap::chunks`](struct.LinearMap.html#method.chunks) for details.
+pub struct Chunks<'a, K: 'a, V: 'a> {
+    iter: slice::Chunks<'a, (K, V)>,
+}
+
+/// An iterator over batches of a `LinearMap`'s entries, with mutable references to the values.
+///
+/// See [`LinearMap::chunks_mut`](struct.LinearMap.html#method.chunks_mut) for details.
+pub struct ChunksMut<'a, K: 'a, V: 'a> {
+    iter: slice::ChunksMut<'a, (K, V)>,
+}
+
 /// An iterator yielding references to a `LinearMap`'s keys in arbitrary order.
 ///
 /// See [`LinearMap::keys`](struct.LinearMap.html#method.keys) for details.
@@ -557,8 +2065,100 @@ pub struct ValuesMut<'a, K: 'a, V: 'a> {
     iter: IterMut<'a, K, V>,
 }
 
-macro_rules! impl_iter {($typ:ty, $item:ty, $map:expr) => {
-    impl<'a, K, V> Iterator for $typ {
+impl<'a, K, V> Iter<'a, K, V> {
+    /// Wraps an iterator over pairs held by a map type other than `LinearMap`.
+    pub(crate) fn new(iter: slice::Iter<'a, (K, V)>) -> Self {
+        Iter { iter }
+    }
+}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    /// Wraps an iterator over pairs held by a map type other than `LinearMap`.
+    pub(crate) fn new(iter: slice::IterMut<'a, (K, V)>) -> Self {
+        IterMut { iter }
+    }
+}
+
+impl<'a, K, V> Keys<'a, K, V> {
+    pub(crate) fn new(iter: Iter<'a, K, V>) -> Self {
+        Keys { iter }
+    }
+}
+
+impl<'a, K, V> Values<'a, K, V> {
+    pub(crate) fn new(iter: Iter<'a, K, V>) -> Self {
+        Values { iter }
+    }
+}
+
+impl<'a, K, V> ValuesMut<'a, K, V> {
+    pub(crate) fn new(iter: IterMut<'a, K, V>) -> Self {
+        ValuesMut { iter }
+    }
+}
+
+/// An iterator yielding the positions of a `LinearMap`'s entries along with references to their
+/// keys and values.
+///
+/// See [`LinearMap::indexed_iter`](struct.LinearMap.html#method.indexed_iter) for details.
+pub struct IndexedIter<'a, K: 'a, V: 'a> {
+    iter: iter::Zip<Positions, slice::Iter<'a, (K, V)>>,
+}
+
+/// An iterator yielding the positions of a `LinearMap`'s entries along with references to their
+/// keys and mutable references to their values.
+///
+/// See [`LinearMap::indexed_iter_mut`](struct.LinearMap.html#method.indexed_iter_mut) for details.
+pub struct IndexedIterMut<'a, K: 'a, V: 'a> {
+    iter: iter::Zip<Positions, slice::IterMut<'a, (K, V)>>,
+}
+
+/// The positions of a map's entries, stamped with the map's generation.
+#[derive(Clone)]
+struct Positions {
+    range: ops::Range<usize>,
+    #[cfg(debug_assertions)]
+    generation: u64,
+}
+
+impl Positions {
+    fn stamp(&self, index: usize) -> EntryIndex {
+        EntryIndex {
+            index,
+            #[cfg(debug_assertions)]
+            generation: self.generation,
+        }
+    }
+}
+
+impl Iterator for Positions {
+    type Item = EntryIndex;
+
+    fn next(&mut self) -> Option<EntryIndex> {
+        self.range.next().map(|i| self.stamp(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<EntryIndex> {
+        self.range.nth(n).map(|i| self.stamp(i))
+    }
+}
+
+impl DoubleEndedIterator for Positions {
+    fn next_back(&mut self) -> Option<EntryIndex> {
+        self.range.next_back().map(|i| self.stamp(i))
+    }
+}
+
+impl ExactSizeIterator for Positions {}
+
+macro_rules! impl_iter {
+($typ:ty, $item:ty, $map:expr) => { impl_iter!{$typ, $item, $map, where} };
+($typ:ty, $item:ty, $map:expr, where $($bound:tt)*) => {
+    impl<'a, K, V> Iterator for $typ where $($bound)* {
         type Item = $item;
 
         fn next(&mut self) -> Option<Self::Item> {
@@ -570,24 +2170,31 @@ macro_rules! impl_iter {($typ:ty, $item:ty, $map:expr) => {
         }
     }
 
-    impl<'a, K, V> DoubleEndedIterator for $typ {
+    impl<'a, K, V> DoubleEndedIterator for $typ where $($bound)* {
         fn next_back(&mut self) -> Option<Self::Item> {
             self.iter.next_back().map($map)
         }
     }
 
-    impl<'a, K, V> ExactSizeIterator for $typ {
+    impl<'a, K, V> ExactSizeIterator for $typ where $($bound)* {
         fn len(&self) -> usize {
             self.iter.len()
         }
     }
 }}
-impl_iter!{Drain<'a,K,V>,  (K,V),  |e| e }
 impl_iter!{Iter<'a,K,V>,  (&'a K, &'a V),  |e| (&e.0, &e.1) }
 impl_iter!{IterMut<'a,K,V>,  (&'a K, &'a mut V),  |e| (&e.0, &mut e.1) }
+impl_iter!{IterCopied<'a,K,V>,  (K, V),  |e| *e,  where K: Copy, V: Copy }
+impl_iter!{IterCloned<'a,K,V>,  (K, V),  |e| e.clone(),  where K: Clone, V: Clone }
+impl_iter!{Chunks<'a,K,V>,  Iter<'a, K, V>,  |c| Iter { iter: c.iter() } }
+impl_iter!{ChunksMut<'a,K,V>,  IterMut<'a, K, V>,  |c| IterMut { iter: c.iter_mut() } }
 impl_iter!{Keys<'a,K,V>,  &'a K,  |e| e.0 }
 impl_iter!{Values<'a,K,V>,  &'a V,  |e| e.1 }
 impl_iter!{ValuesMut<'a,K,V>,  &'a mut V,  |e| e.1 }
+impl_iter!{IndexedIter<'a,K,V>,  (EntryIndex, &'a K, &'a V),  |(i, e)| (i, &e.0, &e.1) }
+impl_iter!{IndexedIterMut<'a,K,V>,  (EntryIndex, &'a K, &'a mut V),  |(i, e)| (i, &e.0, &mut e.1) }
+impl_iter!{IntoKeys<K,V>,  K,  |e| e.0 }
+impl_iter!{IntoValues<K,V>,  V,  |e| e.1 }
 
 impl<'a, K, V> Clone for Iter<'a, K, V> {
     fn clone(&self) -> Self {
@@ -595,6 +2202,30 @@ impl<'a, K, V> Clone for Iter<'a, K, V> {
     }
 }
 
+impl<'a, K, V> Clone for Chunks<'a, K, V> {
+    fn clone(&self) -> Self {
+        Chunks { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, K, V> Clone for IterCopied<'a, K, V> {
+    fn clone(&self) -> Self {
+        IterCopied { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, K, V> Clone for IterCloned<'a, K, V> {
+    fn clone(&self) -> Self {
+        IterCloned { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, K, V> Clone for IndexedIter<'a, K, V> {
+    fn clone(&self) -> Self {
+        IndexedIter { iter: self.iter.clone() }
+    }
+}
+
 impl<'a, K, V> Clone for Keys<'a, K, V> {
     fn clone(&self) -> Self {
         Keys { iter: self.iter.clone() }
@@ -607,7 +2238,7 @@ impl<'a, K, V> Clone for Values<'a, K, V> {
     }
 }
 
-impl<K: Eq, V> IntoIterator for LinearMap<K, V> {
+impl<K, V> IntoIterator for LinearMap<K, V> {
     type Item = (K, V);
     type IntoIter = IntoIter<K, V>;
 
@@ -616,7 +2247,7 @@ impl<K: Eq, V> IntoIterator for LinearMap<K, V> {
     }
 }
 
-impl<'a, K: Eq, V> IntoIterator for &'a LinearMap<K, V> {
+impl<'a, K, V> IntoIterator for &'a LinearMap<K, V> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -625,7 +2256,7 @@ impl<'a, K: Eq, V> IntoIterator for &'a LinearMap<K, V> {
     }
 }
 
-impl<'a, K: Eq, V> IntoIterator for &'a mut LinearMap<K, V> {
+impl<'a, K, V> IntoIterator for &'a mut LinearMap<K, V> {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V>;
 
@@ -645,4 +2276,10 @@ fn assert_covariance() {
     fn d<'i, 'a, K, V>(x: Keys<'i, &'static K, &'static V>) -> Keys<'i, &'a K, &'a V> { x }
 
     fn e<'i, 'a, K, V>(x: Values<'i, &'static K, &'static V>) -> Values<'i, &'a K, &'a V> { x }
+
+    fn f<'a, K, V>(x: IntoKeys<&'static K, &'static V>) -> IntoKeys<&'a K, &'a V> { x }
+
+    fn g<'a, K, V>(x: IntoValues<&'static K, &'static V>) -> IntoValues<&'a K, &'a V> { x }
+
+    fn h<'i, 'a, K, V>(x: Drain<'i, &'static K, &'static V>) -> Drain<'i, &'a K, &'a V> { x }
 }
@@ -0,0 +1,114 @@
+//! Merging maps by combining the values of equal keys.
+//!
+//! The general [`merge_with_op`] takes a closure; [`merge_add`] sums values, and the
+//! [`Integer`](trait.Integer.html) trait enables saturating and checked sums for integer values.
+//!
+//! [`merge_with_op`]: ../struct.LinearMap.html#method.merge_with_op
+//! [`merge_add`]: ../struct.LinearMap.html#method.merge_add
+
+use std::ops::AddAssign;
+
+use errors::OverflowError;
+
+use super::LinearMap;
+
+/// An integer type whose values can be summed with saturating or checked arithmetic.
+pub trait Integer: Copy {
+    /// Adds two values, saturating at the numeric bounds.
+    fn saturating_add(self, other: Self) -> Self;
+
+    /// Adds two values, returning `None` on overflow.
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {($($t:ty)*) => {$(
+    impl Integer for $t {
+        fn saturating_add(self, other: $t) -> $t {
+            <$t>::saturating_add(self, other)
+        }
+
+        fn checked_add(self, other: $t) -> Option<$t> {
+            <$t>::checked_add(self, other)
+        }
+    }
+)*}}
+impl_integer!{u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize}
+
+impl<K: PartialEq, V> LinearMap<K, V> {
+    /// Moves all entries of `other` into the map, combining the values of keys present in both
+    /// with `op`.
+    ///
+    /// `op` is called with the key, a mutable reference to the map's value and `other`'s value.
+    /// Keys only present in `other` are inserted as they are.
+    pub fn merge_with_op<F>(&mut self, other: LinearMap<K, V>, mut op: F)
+    where F: FnMut(&K, &mut V, V) {
+        for (key, value) in other.storage {
+            match self.position(&key) {
+                Some(i) => {
+                    let (ref k, ref mut v) = self.storage[i];
+                    op(k, v, value);
+                }
+                None => {
+                    self.invalidate_positions();
+                    self.storage.push((key, value));
+                }
+            }
+        }
+    }
+
+    /// Moves all entries of `other` into the map, adding the values of keys present in both.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut total: LinearMap<&str, u64> = vec![("hits", 10), ("misses", 2)].into_iter().collect();
+    /// let shard: LinearMap<&str, u64> = vec![("hits", 5), ("errors", 1)].into_iter().collect();
+    /// total.merge_add(shard);
+    /// assert_eq!(total["hits"], 15);
+    /// assert_eq!(total["errors"], 1);
+    /// ```
+    pub fn merge_add(&mut self, other: LinearMap<K, V>) where V: AddAssign {
+        self.merge_with_op(other, |_, v, w| *v += w);
+    }
+
+    /// Moves all entries of `other` into the map, adding the values of keys present in both and
+    /// saturating at the numeric bounds.
+    pub fn merge_add_saturating(&mut self, other: LinearMap<K, V>) where V: Integer {
+        self.merge_with_op(other, |_, v, w| *v = v.saturating_add(w));
+    }
+
+    /// Moves all entries of `other` into the map, adding the values of keys present in both.
+    ///
+    /// If any sum overflows, the map is left unchanged and the offending entry of `other` is
+    /// returned in the error.
+    pub fn merge_add_checked(&mut self, other: LinearMap<K, V>) -> Result<(), OverflowError<K, V>>
+    where V: Integer {
+        // Compute every sum before touching the map, so that an overflow leaves it unchanged.
+        let mut sums = Vec::with_capacity(other.len());
+        for (i, (k, w)) in other.storage.iter().enumerate() {
+            let sum = match self.position(k) {
+                Some(j) => match self.storage[j].1.checked_add(*w) {
+                    Some(sum) => Some((j, sum)),
+                    None => {
+                        let (key, value) = { other }.storage.swap_remove(i);
+                        return Err(OverflowError { key, value });
+                    }
+                },
+                None => None,
+            };
+            sums.push(sum);
+        }
+        for (entry, sum) in other.storage.into_iter().zip(sums) {
+            match sum {
+                Some((j, sum)) => self.storage[j].1 = sum,
+                None => {
+                    self.invalidate_positions();
+                    self.storage.push(entry);
+                }
+            }
+        }
+        Ok(())
+    }
+}
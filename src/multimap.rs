@@ -0,0 +1,282 @@
+//! A map holding several values per key.
+//!
+//! See the [`LinearMultiMap`](struct.LinearMultiMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::iter;
+
+use super::{Entry, IntoIter, Iter, LinearMap};
+
+/// A map from keys to groups of values, implemented by searching linearly in a vector.
+///
+/// Each key holds a non-empty group of values in insertion order: operations that remove the
+/// last value of a group remove its key as well. Keys are searched as in `LinearMap`, so the map
+/// is meant for a small number of distinct keys, such as HTTP headers or query parameters.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::multimap::LinearMultiMap;
+///
+/// let mut headers = LinearMultiMap::new();
+/// headers.insert("accept", "text/html");
+/// headers.insert("accept", "application/json");
+/// headers.insert("host", "example.com");
+///
+/// assert_eq!(headers.get_all("accept"), ["text/html", "application/json"]);
+/// assert!(headers.remove_one("accept", &"text/html"));
+/// assert_eq!(headers.get_all("accept"), ["application/json"]);
+/// ```
+pub struct LinearMultiMap<K, V> {
+    map: LinearMap<K, Vec<V>>,
+}
+
+impl<K: PartialEq, V> LinearMultiMap<K, V> {
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        LinearMultiMap { map: LinearMap::new() }
+    }
+
+    /// Creates an empty map with room for the given number of keys.
+    pub fn with_capacity(capacity: usize) -> Self {
+        LinearMultiMap { map: LinearMap::with_capacity(capacity) }
+    }
+
+    /// Returns the number of keys in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns the number of values in the map, across all keys.
+    pub fn values_len(&self) -> usize {
+        self.map.values().map(Vec::len).sum()
+    }
+
+    /// Returns true if the map contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Clears the map, removing all keys and values.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the values of the given key in insertion order, or an empty slice if the map
+    /// contains no such key.
+    pub fn get_all<Q: ?Sized + PartialEq>(&self, key: &Q) -> &[V] where K: Borrow<Q> {
+        self.map.get(key).map_or(&[], |group| group)
+    }
+
+    /// Returns mutable references to the values of the given key, or an empty slice if the map
+    /// contains no such key.
+    pub fn get_all_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> &mut [V]
+    where K: Borrow<Q> {
+        match self.map.get_mut(key) {
+            Some(group) => group,
+            None => &mut [],
+        }
+    }
+
+    /// Checks if the map contains the given key.
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.map.contains_key(key)
+    }
+
+    /// Appends a value to the group of the given key, creating the group if needed.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.map.entry(key).or_default().push(value);
+    }
+
+    /// Returns the group of the given key for appending values.
+    pub fn group(&mut self, key: K) -> GroupEntry<'_, K, V> {
+        GroupEntry { entry: self.map.entry(key) }
+    }
+
+    /// Removes the given key and returns all of its values.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<Vec<V>>
+    where K: Borrow<Q> {
+        self.map.remove(key)
+    }
+
+    /// Removes the first value equal to `value` from the group of the given key, removing the key
+    /// if this empties its group.
+    ///
+    /// Returns true if a value was removed.
+    pub fn remove_one<Q: ?Sized + PartialEq>(&mut self, key: &Q, value: &V) -> bool
+    where K: Borrow<Q>, V: PartialEq {
+        let emptied = match self.map.get_mut(key) {
+            Some(group) => match group.iter().position(|v| v == value) {
+                Some(i) => {
+                    group.remove(i);
+                    group.is_empty()
+                }
+                None => return false,
+            },
+            None => return false,
+        };
+        if emptied {
+            self.map.remove(key);
+        }
+        true
+    }
+
+    /// Keeps only the values for which the closure returns `true`, removing keys whose group
+    /// becomes empty.
+    ///
+    /// Values of a key are visited in insertion order; the order of keys is not specified.
+    pub fn retain_values<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        self.map.retain(|k, group| {
+            group.retain_mut(|v| keep_fn(k, v));
+            !group.is_empty()
+        });
+    }
+
+    /// Returns an iterator yielding each key along with its values.
+    ///
+    /// The iterator's item type is `(&K, &[V])`.
+    pub fn iter_groups(&self) -> Groups<'_, K, V> {
+        Groups { iter: self.map.iter() }
+    }
+
+    /// Returns a reference to the underlying map of groups.
+    pub fn as_map(&self) -> &LinearMap<K, Vec<V>> {
+        &self.map
+    }
+
+    /// Consumes the multimap and returns the underlying map of groups.
+    pub fn into_inner(self) -> LinearMap<K, Vec<V>> {
+        self.map
+    }
+}
+
+/// The group of a key in a `LinearMultiMap`, which may not exist yet.
+///
+/// See [`LinearMultiMap::group`](struct.LinearMultiMap.html#method.group) for details.
+pub struct GroupEntry<'a, K: 'a, V: 'a> {
+    entry: Entry<'a, K, Vec<V>>,
+}
+
+impl<'a, K, V> GroupEntry<'a, K, V> {
+    /// Returns the group's current values, which are empty if the key is not in the map.
+    pub fn values(&self) -> &[V] {
+        match self.entry {
+            Entry::Occupied(ref e) => e.get(),
+            Entry::Vacant(_) => &[],
+        }
+    }
+
+    /// Appends a value to the group and returns all of its values.
+    pub fn push(self, value: V) -> &'a mut [V] {
+        let group = self.entry.or_default();
+        group.push(value);
+        group
+    }
+
+    /// Appends values to the group and returns all of its values.
+    ///
+    /// If the key is not in the map and `values` is empty, the map is left unchanged.
+    pub fn extend<I: IntoIterator<Item = V>>(self, values: I) -> &'a mut [V] {
+        match self.entry {
+            Entry::Occupied(e) => {
+                let group = e.into_mut();
+                group.extend(values);
+                group
+            }
+            Entry::Vacant(e) => {
+                let group: Vec<V> = values.into_iter().collect();
+                if group.is_empty() { &mut [] } else { e.insert(group) }
+            }
+        }
+    }
+}
+
+/// An iterator over the keys of a `LinearMultiMap` and their groups of values.
+///
+/// See [`LinearMultiMap::iter_groups`](struct.LinearMultiMap.html#method.iter_groups) for details.
+pub struct Groups<'a, K: 'a, V: 'a> {
+    iter: Iter<'a, K, Vec<V>>,
+}
+
+impl<'a, K, V> Iterator for Groups<'a, K, V> {
+    type Item = (&'a K, &'a [V]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, group)| (k, &group[..]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Groups<'a, K, V> {}
+
+impl<'a, K, V> Clone for Groups<'a, K, V> {
+    fn clone(&self) -> Self {
+        Groups { iter: self.iter.clone() }
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for LinearMultiMap<K, V> {
+    fn clone(&self) -> Self {
+        LinearMultiMap { map: self.map.clone() }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for LinearMultiMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: PartialEq, V> Default for LinearMultiMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq, V> Extend<(K, V)> for LinearMultiMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K: PartialEq, V> iter::FromIterator<(K, V)> for LinearMultiMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+/// Compares the groups of each key, including the order of their values.
+impl<K: PartialEq, V: PartialEq> PartialEq for LinearMultiMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for LinearMultiMap<K, V> {}
+
+impl<K, V> IntoIterator for LinearMultiMap<K, V> {
+    type Item = (K, Vec<V>);
+    type IntoIter = IntoIter<K, Vec<V>>;
+
+    fn into_iter(self) -> IntoIter<K, Vec<V>> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K: PartialEq, V> IntoIterator for &'a LinearMultiMap<K, V> {
+    type Item = (&'a K, &'a [V]);
+    type IntoIter = Groups<'a, K, V>;
+
+    fn into_iter(self) -> Groups<'a, K, V> {
+        self.iter_groups()
+    }
+}
@@ -0,0 +1,111 @@
+//! Opt-in mutable access to the keys of a `LinearMap`.
+//!
+//! Import the [`MutableKeys`](trait.MutableKeys.html) trait to use it.
+
+use std::slice;
+
+use super::LinearMap;
+
+/// Methods giving mutable access to the keys of a map.
+///
+/// These are kept in a trait, rather than on `LinearMap` itself, so that code mutating keys has
+/// to opt in explicitly. Mutating a key in a way that changes its equality with the other keys
+/// is a logic error unless it is followed by a check such as the one
+/// [`modify_keys`](#tymethod.modify_keys) performs.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::LinearMap;
+/// use linear_map::mutable_keys::MutableKeys;
+///
+/// let mut map = LinearMap::new();
+/// map.insert("a".to_string(), 1);
+/// map.insert("B".to_string(), 2);
+/// map.insert("b".to_string(), 3);
+///
+/// let removed = map.modify_keys(|k, _| k.make_ascii_lowercase());
+/// assert_eq!(removed, [("b".to_string(), 3)]);
+/// assert_eq!(map["b"], 2);
+/// ```
+pub trait MutableKeys {
+    /// The map's key type.
+    type Key;
+    /// The map's value type.
+    type Value;
+
+    /// Returns an iterator yielding mutable references to the map's keys and their corresponding
+    /// values in arbitrary order.
+    ///
+    /// The caller must not change the keys' equality with each other.
+    ///
+    /// The iterator's item type is `(&mut K, &mut V)`.
+    fn iter_full_mut(&mut self) -> IterFullMut<'_, Self::Key, Self::Value>;
+
+    /// Calls `f` on every key and value, then restores the uniqueness of the keys.
+    ///
+    /// Whenever a key has become equal to an earlier key, the later entry is removed from the map.
+    /// The removed entries are returned in arbitrary order; if the keys are still unique, the
+    /// returned vector is empty and does not allocate. This check takes `O(n²)` time.
+    fn modify_keys<F>(&mut self, f: F) -> Vec<(Self::Key, Self::Value)>
+    where F: FnMut(&mut Self::Key, &mut Self::Value);
+}
+
+impl<K: PartialEq, V> MutableKeys for LinearMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn iter_full_mut(&mut self) -> IterFullMut<'_, K, V> {
+        IterFullMut { iter: self.storage.iter_mut() }
+    }
+
+    fn modify_keys<F>(&mut self, mut f: F) -> Vec<(K, V)>
+    where F: FnMut(&mut K, &mut V) {
+        for (k, v) in self.iter_full_mut() {
+            f(k, v);
+        }
+        self.invalidate_positions();
+        let mut kept = 0;
+        for i in 0..self.storage.len() {
+            let (prefix, rest) = self.storage.split_at(i);
+            if !prefix[..kept].iter().any(|(k, _)| *k == rest[0].0) {
+                self.storage.swap(kept, i);
+                kept += 1;
+            }
+        }
+        self.storage.split_off(kept)
+    }
+}
+
+/// An iterator yielding mutable references to a `LinearMap`'s keys and their corresponding
+/// values.
+///
+/// See [`MutableKeys::iter_full_mut`](trait.MutableKeys.html#tymethod.iter_full_mut) for
+/// details.
+pub struct IterFullMut<'a, K: 'a, V: 'a> {
+    iter: slice::IterMut<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for IterFullMut<'a, K, V> {
+    type Item = (&'a mut K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|e| (&mut e.0, &mut e.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for IterFullMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|e| (&mut e.0, &mut e.1))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterFullMut<'a, K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
@@ -0,0 +1,71 @@
+//! Helpers for two-level maps, `LinearMap<K1, LinearMap<K2, V>>`.
+//!
+//! Grouping by two keys otherwise needs chains such as `map.entry(k1).or_default().entry(k2)`;
+//! the methods added here create the inner map on demand and look through both levels at once.
+
+use std::borrow::Borrow;
+
+use super::{Entry, LinearMap};
+
+impl<K1: PartialEq, K2: PartialEq, V> LinearMap<K1, LinearMap<K2, V>> {
+    /// Gets the entry for `k2` in the inner map of `k1`, inserting an empty inner map first if
+    /// `k1` is absent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::LinearMap;
+    ///
+    /// let mut by_team_and_role = LinearMap::new();
+    /// for (team, role) in [("red", "dev"), ("red", "ops"), ("red", "dev"), ("blue", "dev")] {
+    ///     *by_team_and_role.entry2(team, role).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(by_team_and_role.get_nested("red", "dev"), Some(&2));
+    /// assert_eq!(by_team_and_role["blue"].len(), 1);
+    /// ```
+    pub fn entry2(&mut self, k1: K1, k2: K2) -> Entry<'_, K2, V> {
+        self.entry(k1).or_default().entry(k2)
+    }
+
+    /// Returns a reference to the value under `k2` in the inner map of `k1`.
+    ///
+    /// Returns `None` if either key is absent.
+    pub fn get_nested<Q1, Q2>(&self, k1: &Q1, k2: &Q2) -> Option<&V>
+    where K1: Borrow<Q1>, K2: Borrow<Q2>, Q1: ?Sized + PartialEq, Q2: ?Sized + PartialEq {
+        self.get(k1)?.get(k2)
+    }
+
+    /// Returns a mutable reference to the value under `k2` in the inner map of `k1`.
+    ///
+    /// Returns `None` if either key is absent.
+    pub fn get_nested_mut<Q1, Q2>(&mut self, k1: &Q1, k2: &Q2) -> Option<&mut V>
+    where K1: Borrow<Q1>, K2: Borrow<Q2>, Q1: ?Sized + PartialEq, Q2: ?Sized + PartialEq {
+        self.get_mut(k1)?.get_mut(k2)
+    }
+
+    /// Inserts a value under `k2` in the inner map of `k1`, creating the inner map if needed.
+    ///
+    /// Returns the value previously stored under both keys, if any.
+    pub fn insert_nested(&mut self, k1: K1, k2: K2, value: V) -> Option<V> {
+        self.entry(k1).or_default().insert(k2, value)
+    }
+
+    /// Removes the value under `k2` in the inner map of `k1` and returns it.
+    ///
+    /// The inner map is removed as well if this leaves it empty. Returns `None` if either key is
+    /// absent.
+    pub fn remove_nested<Q1, Q2>(&mut self, k1: &Q1, k2: &Q2) -> Option<V>
+    where K1: Borrow<Q1>, K2: Borrow<Q2>, Q1: ?Sized + PartialEq, Q2: ?Sized + PartialEq {
+        let inner = self.get_mut(k1)?;
+        let value = inner.remove(k2)?;
+        if inner.is_empty() {
+            self.remove(k1);
+        }
+        Some(value)
+    }
+
+    /// Returns the total number of values across all inner maps.
+    pub fn nested_len(&self) -> usize {
+        self.values().map(LinearMap::len).sum()
+    }
+}
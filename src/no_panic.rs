@@ -0,0 +1,205 @@
+//! An optional map type exposing only operations that cannot panic.
+//!
+//! See the [`NoPanicLinearMap`](struct.NoPanicLinearMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::collections::TryReserveError;
+use std::fmt::{self, Debug};
+use std::mem;
+
+use super::{LinearMap, IntoIter, Iter, IterMut};
+use errors::ReserveError;
+
+/// A map implemented by searching linearly in a vector, whose API contains no operation that can
+/// panic.
+///
+/// This wraps a [`LinearMap`](../struct.LinearMap.html) and exposes the subset of its operations
+/// that are suitable for code audited for panics: there is no `Index` implementation, capacity is
+/// only ever reserved fallibly, and insertion reports allocation failure as an error instead of
+/// aborting. The operations themselves are written with checked accesses (`get`, `pop`,
+/// `try_reserve`) rather than indexing, so none of them contains a reachable panic. Closures
+/// passed in by the caller may of course still panic.
+///
+/// Read-only operations that cannot panic either, such as `len` and `iter`, are reached through
+/// the wrapper's `AsRef<LinearMap>` implementation. It does not dereference to the map, so that
+/// indexing is not available by accident.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::no_panic::NoPanicLinearMap;
+///
+/// let mut map = NoPanicLinearMap::new();
+/// assert_eq!(map.insert("limit", 10), Ok(None));
+/// assert_eq!(map.get("limit"), Some(&10));
+/// assert_eq!(map.get("missing"), None);
+/// assert_eq!(map.as_ref().len(), 1);
+/// assert!(map.try_reserve(usize::MAX).is_err());
+/// ```
+pub struct NoPanicLinearMap<K, V> {
+    map: LinearMap<K, V>,
+}
+
+impl<K: PartialEq, V> NoPanicLinearMap<K, V> {
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        NoPanicLinearMap { map: LinearMap::new() }
+    }
+
+    /// Creates an empty map with at least the given capacity.
+    ///
+    /// Returns an error if the memory could not be allocated.
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut map = Self::new();
+        map.try_reserve(capacity)?;
+        Ok(map)
+    }
+
+    /// Like [`LinearMap::reserve`](../struct.LinearMap.html#method.reserve), but returns an error
+    /// if the capacity overflows or the memory could not be allocated.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.map.storage.try_reserve(additional)
+    }
+
+    /// Like [`LinearMap::reserve_exact`](../struct.LinearMap.html#method.reserve_exact), but
+    /// returns an error if the capacity overflows or the memory could not be allocated.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.map.storage.try_reserve_exact(additional)
+    }
+
+    /// Like [`LinearMap::clear`](../struct.LinearMap.html#method.clear).
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Like [`LinearMap::retain`](../struct.LinearMap.html#method.retain).
+    pub fn retain<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        self.map.invalidate_positions();
+        self.map.storage.retain_mut(|(k, v)| keep_fn(k, v));
+    }
+
+    /// Like [`LinearMap::iter_mut`](../struct.LinearMap.html#method.iter_mut).
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Returns the key-value pair at the given position in the underlying vector, or `None` if
+    /// `index` is out of bounds.
+    ///
+    /// Unlike [`LinearMap::get_index`](../struct.LinearMap.html#method.get_index), this takes a
+    /// plain number and never checks it for staleness.
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.map.storage.get(index).map(|(k, v)| (k, v))
+    }
+
+    /// Like [`LinearMap::get`](../struct.LinearMap.html#method.get). This is the non-panicking
+    /// counterpart of indexing a `LinearMap`.
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        self.map.storage.iter().find(|(k, _)| k.borrow() == key).map(|(_, v)| v)
+    }
+
+    /// Like [`LinearMap::get_mut`](../struct.LinearMap.html#method.get_mut).
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V>
+    where K: Borrow<Q> {
+        self.map.storage.iter_mut().find(|(k, _)| k.borrow() == key).map(|(_, v)| v)
+    }
+
+    /// Like [`LinearMap::insert`](../struct.LinearMap.html#method.insert), with the result wrapped
+    /// in `Ok`. If the map is full and memory for the new entry could not be allocated, returns an
+    /// error holding the key and value.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, ReserveError<K, V>> {
+        if let Some(v) = self.get_mut(&key) {
+            return Ok(Some(mem::replace(v, value)));
+        }
+        if let Err(error) = self.try_reserve(1) {
+            return Err(ReserveError { key, value, error });
+        }
+        // Capacity was reserved above, so this does not reallocate.
+        self.map.invalidate_positions();
+        self.map.storage.push((key, value));
+        Ok(None)
+    }
+
+    /// Like [`LinearMap::remove`](../struct.LinearMap.html#method.remove).
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let index = self.map.storage.iter().position(|(k, _)| k.borrow() == key)?;
+        self.map.invalidate_positions();
+        // Equivalent to `swap_remove(index)`, without its bounds check.
+        let last = self.map.storage.pop()?;
+        match self.map.storage.get_mut(index) {
+            Some(entry) => Some(mem::replace(entry, last).1),
+            None => Some(last.1),
+        }
+    }
+
+    /// Consumes the wrapper and returns the underlying `LinearMap`.
+    pub fn into_inner(self) -> LinearMap<K, V> {
+        self.map
+    }
+}
+
+impl<K, V> AsRef<LinearMap<K, V>> for NoPanicLinearMap<K, V> {
+    fn as_ref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> From<LinearMap<K, V>> for NoPanicLinearMap<K, V> {
+    fn from(map: LinearMap<K, V>) -> Self {
+        NoPanicLinearMap { map }
+    }
+}
+
+impl<K, V> From<NoPanicLinearMap<K, V>> for LinearMap<K, V> {
+    fn from(map: NoPanicLinearMap<K, V>) -> Self {
+        map.map
+    }
+}
+
+impl<K: PartialEq + Debug, V: Debug> Debug for NoPanicLinearMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: PartialEq, V> Default for NoPanicLinearMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for NoPanicLinearMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for NoPanicLinearMap<K, V> {}
+
+impl<K: PartialEq, V> IntoIterator for NoPanicLinearMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K: PartialEq, V> IntoIterator for &'a NoPanicLinearMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.map.iter()
+    }
+}
+
+impl<'a, K: PartialEq, V> IntoIterator for &'a mut NoPanicLinearMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,250 @@
+//! A map with a configurable allocation policy.
+//!
+//! See the [`PolicyLinearMap`](struct.PolicyLinearMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::mem;
+use std::ops::Deref;
+
+use super::{LinearMap, IntoIter, Iter, IterMut, ValuesMut};
+
+/// How a [`PolicyLinearMap`](struct.PolicyLinearMap.html) grows its allocation when it is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Growth {
+    /// Grow by exactly one entry at a time, never holding unused capacity after an insertion.
+    Exact,
+    /// Grow geometrically, like `Vec`. This is the behaviour of `LinearMap`.
+    #[default]
+    Amortized,
+    /// Grow by the given number of entries at a time. A chunk size of zero is treated as one.
+    Chunked(usize),
+}
+
+/// A map implemented by searching linearly in a vector, with a per-map allocation policy.
+///
+/// The [`Growth`](enum.Growth.html) policy decides how much capacity is added when an insertion
+/// finds the map full. With auto-shrink enabled, removals that leave the map less than a quarter
+/// full release memory, keeping capacity for twice the remaining entries. This suits long-lived
+/// maps that are usually small but occasionally spike, which would otherwise hold on to their
+/// peak allocation.
+///
+/// The wrapper dereferences to the underlying `LinearMap`, so `len`, `get`, `iter` and the other
+/// read-only methods are available; only the methods that may change the allocation or need
+/// mutable access are defined here.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::policy::{Growth, PolicyLinearMap};
+///
+/// let mut map = PolicyLinearMap::with_policy(Growth::Chunked(4), true);
+/// for i in 0..100 {
+///     map.insert(i, i * i);
+/// }
+/// assert_eq!(map.capacity(), 100);
+///
+/// map.retain(|&k, _| k < 10);
+/// assert!(map.capacity() <= 20);
+/// ```
+pub struct PolicyLinearMap<K, V> {
+    map: LinearMap<K, V>,
+    growth: Growth,
+    auto_shrink: bool,
+}
+
+impl<K: PartialEq, V> PolicyLinearMap<K, V> {
+    /// Creates an empty map with amortized growth and no auto-shrink. This method does not
+    /// allocate.
+    pub fn new() -> Self {
+        Self::with_policy(Growth::Amortized, false)
+    }
+
+    /// Creates an empty map with the given policy. This method does not allocate.
+    pub fn with_policy(growth: Growth, auto_shrink: bool) -> Self {
+        PolicyLinearMap { map: LinearMap::new(), growth, auto_shrink }
+    }
+
+    /// Returns the map's growth policy.
+    pub fn growth(&self) -> Growth {
+        self.growth
+    }
+
+    /// Sets the map's growth policy. It applies from the next insertion that finds the map full.
+    pub fn set_growth(&mut self, growth: Growth) {
+        self.growth = growth;
+    }
+
+    /// Returns whether the map releases memory when it becomes mostly empty.
+    pub fn auto_shrink(&self) -> bool {
+        self.auto_shrink
+    }
+
+    /// Enables or disables releasing memory when the map becomes mostly empty.
+    ///
+    /// Enabling it shrinks the map right away if it is less than a quarter full.
+    pub fn set_auto_shrink(&mut self, auto_shrink: bool) {
+        self.auto_shrink = auto_shrink;
+        self.maybe_shrink();
+    }
+
+    /// Shrinks the capacity of the map as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit();
+    }
+
+    /// Like [`LinearMap::clear`](../struct.LinearMap.html#method.clear), but keeps the allocated
+    /// memory only if auto-shrink is disabled.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.maybe_shrink();
+    }
+
+    /// Like [`LinearMap::retain`](../struct.LinearMap.html#method.retain), shrinking the map
+    /// afterwards if auto-shrink is enabled.
+    pub fn retain<F>(&mut self, keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        self.map.retain(keep_fn);
+        self.maybe_shrink();
+    }
+
+    /// Like [`LinearMap::iter_mut`](../struct.LinearMap.html#method.iter_mut).
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Like [`LinearMap::values_mut`](../struct.LinearMap.html#method.values_mut).
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.map.values_mut()
+    }
+
+    /// Like [`LinearMap::get_mut`](../struct.LinearMap.html#method.get_mut).
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V>
+    where K: Borrow<Q> {
+        self.map.get_mut(key)
+    }
+
+    /// Like [`LinearMap::insert`](../struct.LinearMap.html#method.insert), growing the map
+    /// according to its policy if it is full.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if self.map.len() == self.map.capacity() {
+            // Only grow for a new key, so that overwriting a value keeps the map's capacity.
+            if let Some(v) = self.map.get_mut(&key) {
+                return Some(mem::replace(v, value));
+            }
+            match self.growth {
+                Growth::Exact => self.map.reserve_exact(1),
+                Growth::Amortized => self.map.reserve(1),
+                Growth::Chunked(n) => self.map.reserve_exact(n.max(1)),
+            }
+        }
+        self.map.insert(key, value)
+    }
+
+    /// Like [`LinearMap::remove`](../struct.LinearMap.html#method.remove), shrinking the map
+    /// afterwards if auto-shrink is enabled.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let value = self.map.remove(key);
+        self.maybe_shrink();
+        value
+    }
+
+    /// Consumes the wrapper and returns the underlying map.
+    pub fn into_inner(self) -> LinearMap<K, V> {
+        self.map
+    }
+
+    fn maybe_shrink(&mut self) {
+        let len = self.map.len();
+        if self.auto_shrink && len < self.map.capacity() / 4 {
+            self.map.storage.shrink_to(len * 2);
+        }
+    }
+}
+
+impl<K: PartialEq, V> From<LinearMap<K, V>> for PolicyLinearMap<K, V> {
+    fn from(map: LinearMap<K, V>) -> Self {
+        PolicyLinearMap { map, growth: Growth::Amortized, auto_shrink: false }
+    }
+}
+
+impl<K, V> Deref for PolicyLinearMap<K, V> {
+    type Target = LinearMap<K, V>;
+
+    fn deref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> AsRef<LinearMap<K, V>> for PolicyLinearMap<K, V> {
+    fn as_ref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for PolicyLinearMap<K, V> {
+    fn clone(&self) -> Self {
+        PolicyLinearMap { map: self.map.clone(), growth: self.growth, auto_shrink: self.auto_shrink }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for PolicyLinearMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: PartialEq, V> Default for PolicyLinearMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq, V> Extend<(K, V)> for PolicyLinearMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K: PartialEq, V> iter::FromIterator<(K, V)> for PolicyLinearMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
+        PolicyLinearMap::from(key_values.into_iter().collect::<LinearMap<K, V>>())
+    }
+}
+
+/// Compares the maps' entries; their policies are ignored.
+impl<K: PartialEq, V: PartialEq> PartialEq for PolicyLinearMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for PolicyLinearMap<K, V> {}
+
+impl<K: PartialEq, V> IntoIterator for PolicyLinearMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K: PartialEq, V> IntoIterator for &'a PolicyLinearMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K: PartialEq, V> IntoIterator for &'a mut PolicyLinearMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,130 @@
+//! A pool recycling the allocations of temporary maps.
+//!
+//! See the [`LinearMapPool`](struct.LinearMapPool.html) type for details.
+
+use std::fmt::{self, Debug};
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+use super::LinearMap;
+
+/// A pool of empty maps that keep their capacity between uses.
+///
+/// [`get`](#method.get) hands out a map from the pool, or a new one if the pool is empty, wrapped
+/// in a guard that dereferences to `LinearMap`. Dropping the guard clears the map and returns it
+/// to the pool, so maps built per request or per frame reuse their allocations instead of
+/// churning the allocator. The pool can be shared between threads.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::pool::LinearMapPool;
+///
+/// let pool = LinearMapPool::new();
+/// for frame in 0..3 {
+///     let mut scratch = pool.get();
+///     assert!(scratch.is_empty());
+///     scratch.insert("frame", frame);
+/// }
+/// assert_eq!(pool.idle(), 1);
+/// ```
+pub struct LinearMapPool<K, V> {
+    free: Mutex<Vec<LinearMap<K, V>>>,
+    max_idle: usize,
+}
+
+impl<K, V> LinearMapPool<K, V> {
+    /// Creates an empty pool that keeps any number of idle maps. This method does not allocate.
+    pub const fn new() -> Self {
+        Self::with_max_idle(usize::MAX)
+    }
+
+    /// Creates an empty pool that keeps at most `max_idle` maps; maps returned to a full pool
+    /// are dropped. This method does not allocate.
+    pub const fn with_max_idle(max_idle: usize) -> Self {
+        LinearMapPool { free: Mutex::new(Vec::new()), max_idle }
+    }
+
+    /// Takes an empty map from the pool, or creates one if the pool has none.
+    pub fn get(&self) -> PooledMap<'_, K, V> {
+        let map = self.lock().pop().unwrap_or_default();
+        PooledMap { pool: self, map }
+    }
+
+    /// Returns the number of maps waiting in the pool.
+    pub fn idle(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Drops all maps waiting in the pool, releasing their memory.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> ::std::sync::MutexGuard<'_, Vec<LinearMap<K, V>>> {
+        // The pool's state is a plain list of maps, which a panic cannot leave inconsistent.
+        self.free.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn put(&self, mut map: LinearMap<K, V>) {
+        map.clear();
+        let mut free = self.lock();
+        if free.len() < self.max_idle && map.capacity() > 0 {
+            free.push(map);
+        }
+    }
+}
+
+impl<K, V> Default for LinearMapPool<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Debug for LinearMapPool<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LinearMapPool").field("idle", &self.idle()).finish()
+    }
+}
+
+/// A map borrowed from a `LinearMapPool`, returned to it when dropped.
+///
+/// See [`LinearMapPool::get`](struct.LinearMapPool.html#method.get) for details.
+pub struct PooledMap<'a, K: 'a, V: 'a> {
+    pool: &'a LinearMapPool<K, V>,
+    map: LinearMap<K, V>,
+}
+
+impl<'a, K, V> PooledMap<'a, K, V> {
+    /// Takes the map out of the pool's management, so that it is not returned when dropped.
+    pub fn detach(mut self) -> LinearMap<K, V> {
+        mem::take(&mut self.map)
+    }
+}
+
+impl<'a, K, V> Deref for PooledMap<'a, K, V> {
+    type Target = LinearMap<K, V>;
+
+    fn deref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<'a, K, V> DerefMut for PooledMap<'a, K, V> {
+    fn deref_mut(&mut self) -> &mut LinearMap<K, V> {
+        &mut self.map
+    }
+}
+
+impl<'a, K, V> Drop for PooledMap<'a, K, V> {
+    fn drop(&mut self) {
+        self.pool.put(mem::take(&mut self.map));
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Debug for PooledMap<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
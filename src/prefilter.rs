@@ -0,0 +1,101 @@
+//! Faster lookups for maps keyed by strings or byte strings.
+//!
+//! Keys implementing [`ByteKey`](trait.ByteKey.html) can be looked up with the `*_prefiltered`
+//! methods of `LinearMap`, which reject most non-matching keys by comparing their lengths and
+//! their first and last bytes before comparing them in full. This pays off when keys are long and
+//! share common prefixes, such as paths or qualified names.
+//!
+//! # Example
+//!
+//! ```
+//! use linear_map::LinearMap;
+//!
+//! let mut routes = LinearMap::new();
+//! routes.insert("/api/v1/users".to_string(), 1);
+//! routes.insert("/api/v1/groups".to_string(), 2);
+//! assert_eq!(routes.get_prefiltered("/api/v1/groups"), Some(&2));
+//! assert_eq!(routes.get_prefiltered("/api/v1/userz"), None);
+//! ```
+
+use super::LinearMap;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A key type whose equality is equality of its byte representation.
+///
+/// This trait is sealed: it is implemented for `String`, `Vec<u8>`, `Box<str>`, `Box<[u8]>`,
+/// `&str` and `&[u8]`, and cannot be implemented outside this crate, since the prefiltered
+/// lookups rely on `Eq` agreeing with the bytes.
+pub trait ByteKey: Eq + sealed::Sealed {
+    /// Returns the bytes the key compares by.
+    fn key_bytes(&self) -> &[u8];
+}
+
+macro_rules! impl_byte_key {($($t:ty => $bytes:ident,)*) => {$(
+    impl sealed::Sealed for $t {}
+
+    impl ByteKey for $t {
+        fn key_bytes(&self) -> &[u8] {
+            self.$bytes()
+        }
+    }
+)*}}
+impl_byte_key!{
+    String => as_bytes,
+    Vec<u8> => as_slice,
+    Box<str> => as_bytes,
+    Box<[u8]> => as_ref,
+    &str => as_bytes,
+}
+
+impl sealed::Sealed for &[u8] {}
+
+impl ByteKey for &[u8] {
+    fn key_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+fn prefiltered_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.first() == b.first() && a.last() == b.last() && a == b
+}
+
+impl<K: ByteKey, V> LinearMap<K, V> {
+    fn position_prefiltered<Q: ?Sized + AsRef<[u8]>>(&self, key: &Q) -> Option<usize> {
+        let key = key.as_ref();
+        self.storage.iter().position(|(k, _)| prefiltered_eq(k.key_bytes(), key))
+    }
+
+    /// Returns a reference to the value in the map whose key has the same bytes as the given key.
+    ///
+    /// Returns `None` if the map contains no such key. This is equivalent to `get`, but compares
+    /// cheap summaries of the keys first.
+    pub fn get_prefiltered<Q: ?Sized + AsRef<[u8]>>(&self, key: &Q) -> Option<&V> {
+        self.position_prefiltered(key).map(|i| &self.storage[i].1)
+    }
+
+    /// Returns a mutable reference to the value in the map whose key has the same bytes as the
+    /// given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_mut_prefiltered<Q: ?Sized + AsRef<[u8]>>(&mut self, key: &Q) -> Option<&mut V> {
+        self.position_prefiltered(key).map(move |i| &mut self.storage[i].1)
+    }
+
+    /// Checks if the map contains a key that has the same bytes as the given key.
+    pub fn contains_key_prefiltered<Q: ?Sized + AsRef<[u8]>>(&self, key: &Q) -> bool {
+        self.position_prefiltered(key).is_some()
+    }
+
+    /// Removes the key in the map that has the same bytes as the given key and returns its
+    /// corresponding value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove_prefiltered<Q: ?Sized + AsRef<[u8]>>(&mut self, key: &Q) -> Option<V> {
+        let i = self.position_prefiltered(key)?;
+        self.invalidate_positions();
+        Some(self.storage.swap_remove(i).1)
+    }
+}
@@ -0,0 +1,75 @@
+//! Optional random sampling and shuffling of a `LinearMap`.
+
+extern crate rand;
+
+use self::rand::Rng;
+use self::rand::seq::SliceRandom;
+
+use super::LinearMap;
+
+impl<K, V> LinearMap<K, V> {
+    /// Permutes the order of the map's entries uniformly at random, in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate linear_map;
+    /// # extern crate rand;
+    /// use linear_map::LinearMap;
+    ///
+    /// # fn main() {
+    /// let mut jobs: LinearMap<_, _> = (0..8).map(|i| (i, i * 10)).collect();
+    /// jobs.shuffle(&mut rand::rng());
+    /// assert_eq!(jobs.len(), 8);
+    /// assert_eq!(jobs.get(&3), Some(&30));
+    /// # }
+    /// ```
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.invalidate_positions();
+        self.storage.shuffle(rng);
+    }
+}
+
+impl<K: PartialEq, V> LinearMap<K, V> {
+    /// Returns a key-value pair sampled at random, with probability proportional to the weight
+    /// that `weight` assigns to it.
+    ///
+    /// Entries with a weight that is zero, negative or NaN are never chosen. Returns `None` if
+    /// the map has no entry with a positive weight. The map is traversed once and `weight` is
+    /// called once per entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate linear_map;
+    /// # extern crate rand;
+    /// use linear_map::LinearMap;
+    ///
+    /// # fn main() {
+    /// let mut loot = LinearMap::new();
+    /// loot.insert("common", 90);
+    /// loot.insert("rare", 10);
+    /// loot.insert("cursed", 0);
+    ///
+    /// let (item, _) = loot.choose_weighted(&mut rand::rng(), |_, &w| w as f64).unwrap();
+    /// assert!(*item != "cursed");
+    /// # }
+    /// ```
+    pub fn choose_weighted<R, F>(&self, rng: &mut R, mut weight: F) -> Option<(&K, &V)>
+    where R: Rng + ?Sized, F: FnMut(&K, &V) -> f64 {
+        let mut total = 0.0;
+        let mut chosen = None;
+        for (k, v) in self {
+            let w = weight(k, v);
+            if w > 0.0 {
+                total += w;
+                // Replace the current choice with probability `w / total`; by induction every
+                // entry seen so far ends up chosen with probability proportional to its weight.
+                if rng.random::<f64>() * total < w {
+                    chosen = Some((k, v));
+                }
+            }
+        }
+        chosen
+    }
+}
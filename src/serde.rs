@@ -4,42 +4,96 @@
 //! - [Serialize][1].
 //! - [Deserialize][2].
 //!
+//! Keys and values may borrow from the input: a `LinearMap<&'de str, &'de str>` deserialized
+//! from an in-memory buffer copies no strings. Note that `Cow<'de, str>` always deserializes to
+//! the owned variant through serde's own implementation; use `&'de str` where borrowing is
+//! required and the input has no escape sequences.
+//!
 //! [1]: https://github.com/serde-rs/serde/blob/97856462467db2e90cf368e407c7ebcc726a01a9/serde/src/ser/impls.rs#L601-L611
 //! [2]: https://github.com/serde-rs/serde/blob/97856462467db2e90cf368e407c7ebcc726a01a9/serde/src/de/impls.rs#L694-L746
 
 extern crate serde;
 
 use super::LinearMap;
+use super::multimap::LinearMultiMap;
 use super::set::LinearSet;
+use super::sorted::SortedLinearMap;
+use super::thin::ThinLinearMap;
 
 use self::serde::{Serialize, Serializer, Deserialize, Deserializer};
-use self::serde::de::{Visitor, MapAccess, SeqAccess, Error};
+use self::serde::de::{Visitor, MapAccess, SeqAccess, Error, IntoDeserializer};
+use self::serde::de::value::MapDeserializer;
 use self::serde::ser::{SerializeMap, SerializeSeq};
 
 use std::marker::PhantomData;
 use std::fmt;
 
 impl<K, V> Serialize for LinearMap<K, V>
-    where K: Serialize + Eq,
+    where K: Serialize + PartialEq,
           V: Serialize,
 {
     #[inline]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer,
     {
-        let mut state = try!(serializer.serialize_map(Some(self.len())));
+        let mut state = serializer.serialize_map(Some(self.len()))?;
         for (k, v) in self {
-            try!(state.serialize_entry(k, v));
+            state.serialize_entry(k, v)?;
         }
         state.end()
     }
 }
 
+/// Serializes a map's entries in ascending key order rather than in the map's own order.
+///
+/// The output then depends only on the map's contents, not on the order of insertions and
+/// removals, which keeps it byte-stable for signing or reproducible builds. Deserializing it
+/// yields an equal map.
+///
+/// Use it on a field as `#[serde(serialize_with = "linear_map::serde::serialize_sorted")]`, or
+/// wrap a map in [`SortedKeys`](struct.SortedKeys.html).
+pub fn serialize_sorted<K, V, S>(map: &LinearMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where K: Serialize + Ord,
+          V: Serialize,
+          S: Serializer,
+{
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    let mut state = serializer.serialize_map(Some(entries.len()))?;
+    for (k, v) in entries {
+        state.serialize_entry(k, v)?;
+    }
+    state.end()
+}
+
+/// A borrowed map that serializes its entries in ascending key order.
+///
+/// See [`serialize_sorted`](fn.serialize_sorted.html) for details.
+#[derive(Debug)]
+pub struct SortedKeys<'a, K: 'a, V: 'a>(pub &'a LinearMap<K, V>);
+
+impl<'a, K, V> Serialize for SortedKeys<'a, K, V>
+    where K: Serialize + Ord,
+          V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serialize_sorted(self.0, serializer)
+    }
+}
+
 #[allow(missing_docs)]
 pub struct LinearMapVisitor<K, V> {
     marker: PhantomData<LinearMap<K, V>>,
 }
 
+impl<K, V> Default for LinearMapVisitor<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<K, V> LinearMapVisitor<K, V> {
     #[allow(missing_docs)]
     pub fn new() -> Self {
@@ -50,7 +104,7 @@ impl<K, V> LinearMapVisitor<K, V> {
 }
 
 impl<'de, K, V> Visitor<'de> for LinearMapVisitor<K, V>
-    where K: Deserialize<'de> + Eq,
+    where K: Deserialize<'de> + PartialEq,
           V: Deserialize<'de>,
 {
     type Value = LinearMap<K, V>;
@@ -72,7 +126,7 @@ impl<'de, K, V> Visitor<'de> for LinearMapVisitor<K, V>
     {
         let mut values = LinearMap::with_capacity(visitor.size_hint().unwrap_or(0));
 
-        while let Some((key, value)) = try!(visitor.next_entry()) {
+        while let Some((key, value)) = visitor.next_entry()? {
             values.insert(key, value);
         }
 
@@ -81,7 +135,7 @@ impl<'de, K, V> Visitor<'de> for LinearMapVisitor<K, V>
 }
 
 impl<'de, K, V> Deserialize<'de> for LinearMap<K, V>
-    where K: Deserialize<'de> + Eq,
+    where K: Deserialize<'de> + PartialEq,
           V: Deserialize<'de>,
 {
     fn deserialize<D>(deserializer: D) -> Result<LinearMap<K, V>, D::Error>
@@ -89,6 +143,66 @@ impl<'de, K, V> Deserialize<'de> for LinearMap<K, V>
     {
         deserializer.deserialize_map(LinearMapVisitor::new())
     }
+
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+        where D: Deserializer<'de>
+    {
+        deserializer.deserialize_map(LinearMapInPlaceVisitor(place))
+    }
+}
+
+/// Refills an existing map, keeping its allocation.
+struct LinearMapInPlaceVisitor<'a, K: 'a, V: 'a>(&'a mut LinearMap<K, V>);
+
+impl<'a, 'de, K, V> Visitor<'de> for LinearMapInPlaceVisitor<'a, K, V>
+    where K: Deserialize<'de> + PartialEq,
+          V: Deserialize<'de>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a LinearMap")
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where E: Error,
+    {
+        self.0.clear();
+        Ok(())
+    }
+
+    #[inline]
+    fn visit_map<Visitor>(self, mut visitor: Visitor) -> Result<Self::Value, Visitor::Error>
+        where Visitor: MapAccess<'de>
+    {
+        self.0.clear();
+        self.0.reserve(visitor.size_hint().unwrap_or(0));
+
+        while let Some((key, value)) = visitor.next_entry()? {
+            self.0.insert(key, value);
+        }
+
+        Ok(())
+    }
+}
+
+/// Lets a map act as a self-describing map deserializer, for example to hydrate a typed struct
+/// from a handful of settings without an intermediate encoding.
+///
+/// Keys and values are handed to the visitor through their own `IntoDeserializer`
+/// implementations: a `LinearMap<String, String>` only fills string fields, while a map whose
+/// values are a dynamic type such as `serde_json::Value` can fill fields of any type.
+impl<'de, K, V, E> IntoDeserializer<'de, E> for LinearMap<K, V>
+    where K: IntoDeserializer<'de, E>,
+          V: IntoDeserializer<'de, E>,
+          E: Error,
+{
+    type Deserializer = MapDeserializer<'de, super::IntoIter<K, V>, E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        MapDeserializer::new(self.into_iter())
+    }
 }
 
 impl<K> Serialize for LinearSet<K>
@@ -98,9 +212,9 @@ impl<K> Serialize for LinearSet<K>
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer,
     {
-        let mut state = try!(serializer.serialize_seq(Some(self.len())));
+        let mut state = serializer.serialize_seq(Some(self.len()))?;
         for k in self {
-            try!(state.serialize_element(k));
+            state.serialize_element(k)?;
         }
         state.end()
     }
@@ -112,6 +226,12 @@ pub struct LinearSetVisitor<K> {
     marker: PhantomData<LinearSet<K>>,
 }
 
+impl<K> Default for LinearSetVisitor<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<K> LinearSetVisitor<K>{
     #[allow(missing_docs)]
     pub fn new() -> Self {
@@ -143,7 +263,7 @@ impl<'de, K> Visitor<'de> for LinearSetVisitor<K>
     {
         let mut values = LinearSet::with_capacity(visitor.size_hint().unwrap_or(0));
 
-        while let Some(key) = try!(visitor.next_element()) {
+        while let Some(key) = visitor.next_element()? {
             values.insert(key);
         }
 
@@ -160,3 +280,132 @@ impl<'de, K> Deserialize<'de> for LinearSet<K>
         deserializer.deserialize_seq(LinearSetVisitor::new())
     }
 }
+
+impl<K, V> Serialize for SortedLinearMap<K, V>
+    where K: Serialize + Ord,
+          V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+/// Entries may come in any order; they are sorted as they are inserted.
+impl<'de, K, V> Deserialize<'de> for SortedLinearMap<K, V>
+    where K: Deserialize<'de> + Ord,
+          V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<SortedLinearMap<K, V>, D::Error>
+        where D: Deserializer<'de>
+    {
+        LinearMap::deserialize(deserializer).map(|map| map.into_iter().collect())
+    }
+}
+
+/// Serializes each key with the sequence of its values.
+impl<K, V> Serialize for LinearMultiMap<K, V>
+    where K: Serialize + PartialEq,
+          V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        self.as_map().serialize(serializer)
+    }
+}
+
+/// A key appearing several times has its values appended in order, and a key with an empty
+/// sequence of values is skipped.
+impl<'de, K, V> Deserialize<'de> for LinearMultiMap<K, V>
+    where K: Deserialize<'de> + PartialEq,
+          V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<LinearMultiMap<K, V>, D::Error>
+        where D: Deserializer<'de>
+    {
+        deserializer.deserialize_map(LinearMultiMapVisitor(PhantomData))
+    }
+}
+
+struct LinearMultiMapVisitor<K, V>(PhantomData<LinearMultiMap<K, V>>);
+
+impl<'de, K, V> Visitor<'de> for LinearMultiMapVisitor<K, V>
+    where K: Deserialize<'de> + PartialEq,
+          V: Deserialize<'de>,
+{
+    type Value = LinearMultiMap<K, V>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a LinearMultiMap")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+        where A: MapAccess<'de>
+    {
+        let mut map = LinearMultiMap::with_capacity(access.size_hint().unwrap_or(0));
+        while let Some((key, values)) = access.next_entry::<K, Vec<V>>()? {
+            map.group(key).extend(values);
+        }
+        Ok(map)
+    }
+}
+
+impl<K, V> Serialize for ThinLinearMap<K, V>
+    where K: Serialize + PartialEq,
+          V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+/// Fails with an invalid length error, rather than panicking, if the input holds more than
+/// `u32::MAX` distinct keys.
+///
+/// The crate has no array-backed fixed-capacity map, so `ThinLinearMap`, whose capacity is
+/// bounded by its `u32` header, is the bounded map whose capacity is checked here.
+impl<'de, K, V> Deserialize<'de> for ThinLinearMap<K, V>
+    where K: Deserialize<'de> + PartialEq,
+          V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<ThinLinearMap<K, V>, D::Error>
+        where D: Deserializer<'de>
+    {
+        deserializer.deserialize_map(ThinLinearMapVisitor(PhantomData))
+    }
+}
+
+struct ThinLinearMapVisitor<K, V>(PhantomData<ThinLinearMap<K, V>>);
+
+impl<'de, K, V> Visitor<'de> for ThinLinearMapVisitor<K, V>
+    where K: Deserialize<'de> + PartialEq,
+          V: Deserialize<'de>,
+{
+    type Value = ThinLinearMap<K, V>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a map with at most {} entries", u32::MAX)
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+        where A: MapAccess<'de>
+    {
+        const MAX: usize = u32::MAX as usize;
+        let hint = access.size_hint().unwrap_or(0);
+        if hint > MAX {
+            return Err(A::Error::invalid_length(hint, &self));
+        }
+        let mut map = ThinLinearMap::with_capacity(hint);
+        while let Some((key, value)) = access.next_entry()? {
+            if map.len() == MAX && !map.contains_key(&key) {
+                return Err(A::Error::invalid_length(MAX + 1, &self));
+            }
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+}
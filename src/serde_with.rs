@@ -0,0 +1,108 @@
+//! Optional `serde_with` adapters for `LinearMap`.
+//!
+//! Two adapters are provided, so that `LinearMap` composes with `#[serde_as]` like the standard
+//! maps do:
+//!
+//! - `LinearMap<KAs, VAs>` serializes a `LinearMap<K, V>` as a map, converting keys with `KAs` and
+//!   values with `VAs`, e.g. `LinearMap<DisplayFromStr, _>` for integer keys in JSON.
+//! - `Vec<(KAs, VAs)>` serializes a `LinearMap<K, V>` as a sequence of key-value pairs, for
+//!   formats or keys that do not support maps.
+//!
+//! Deserializing through either adapter keeps the last value of a repeated key.
+
+extern crate serde;
+extern crate serde_with;
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use self::serde::de::{Deserializer, MapAccess, Visitor};
+use self::serde::ser::{SerializeMap, SerializeSeq, Serializer};
+use self::serde::Deserialize;
+use self::serde_with::de::{DeserializeAs, DeserializeAsWrap};
+use self::serde_with::ser::{SerializeAs, SerializeAsWrap};
+
+use super::LinearMap;
+
+impl<K, KAs, V, VAs> SerializeAs<LinearMap<K, V>> for LinearMap<KAs, VAs>
+    where KAs: SerializeAs<K>,
+          VAs: SerializeAs<V>,
+{
+    fn serialize_as<S>(source: &LinearMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(source.len()))?;
+        for (k, v) in source {
+            state.serialize_entry(&SerializeAsWrap::<K, KAs>::new(k),
+                                  &SerializeAsWrap::<V, VAs>::new(v))?;
+        }
+        state.end()
+    }
+}
+
+impl<'de, K, KAs, V, VAs> DeserializeAs<'de, LinearMap<K, V>> for LinearMap<KAs, VAs>
+    where K: PartialEq,
+          KAs: DeserializeAs<'de, K>,
+          VAs: DeserializeAs<'de, V>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<LinearMap<K, V>, D::Error>
+        where D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MapAsVisitor::<K, KAs, V, VAs>(PhantomData))
+    }
+}
+
+struct MapAsVisitor<K, KAs, V, VAs>(PhantomData<(K, KAs, V, VAs)>);
+
+impl<'de, K, KAs, V, VAs> Visitor<'de> for MapAsVisitor<K, KAs, V, VAs>
+    where K: PartialEq,
+          KAs: DeserializeAs<'de, K>,
+          VAs: DeserializeAs<'de, V>,
+{
+    type Value = LinearMap<K, V>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a LinearMap")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+        where A: MapAccess<'de>,
+    {
+        let mut map = LinearMap::with_capacity(access.size_hint().unwrap_or(0));
+        while let Some((k, v)) =
+            access.next_entry::<DeserializeAsWrap<K, KAs>, DeserializeAsWrap<V, VAs>>()? {
+            map.insert(k.into_inner(), v.into_inner());
+        }
+        Ok(map)
+    }
+}
+
+impl<K, KAs, V, VAs> SerializeAs<LinearMap<K, V>> for Vec<(KAs, VAs)>
+    where KAs: SerializeAs<K>,
+          VAs: SerializeAs<V>,
+{
+    fn serialize_as<S>(source: &LinearMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(source.len()))?;
+        for (k, v) in source {
+            state.serialize_element(&(SerializeAsWrap::<K, KAs>::new(k),
+                                       SerializeAsWrap::<V, VAs>::new(v)))?;
+        }
+        state.end()
+    }
+}
+
+impl<'de, K, KAs, V, VAs> DeserializeAs<'de, LinearMap<K, V>> for Vec<(KAs, VAs)>
+    where K: PartialEq,
+          KAs: DeserializeAs<'de, K>,
+          VAs: DeserializeAs<'de, V>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<LinearMap<K, V>, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(DeserializeAsWrap<K, KAs>, DeserializeAsWrap<V, VAs>)>::deserialize(
+            deserializer)?;
+        Ok(pairs.into_iter().map(|(k, v)| (k.into_inner(), v.into_inner())).collect())
+    }
+}
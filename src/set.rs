@@ -12,6 +12,9 @@ use super::{LinearMap, Keys};
 /// An implementation of a set using the underlying representation of a
 /// LinearMap where the value is ().
 ///
+/// Since `()` is zero-sized, each entry occupies exactly as much memory as its key. A set
+/// converts to and from a `LinearMap<T, ()>` with `From` without touching or copying its keys.
+///
 /// # Examples
 ///
 /// ```
@@ -67,7 +70,7 @@ use super::{LinearMap, Keys};
 /// ```
 #[derive(Clone)]
 pub struct LinearSet<T> {
-    map: LinearMap<T, ()>
+    pub(crate) map: LinearMap<T, ()>
 }
 
 impl<T: Eq> LinearSet<T> {
@@ -80,8 +83,7 @@ impl<T: Eq> LinearSet<T> {
     /// let mut set: LinearSet<i32> = LinearSet::new();
     /// ```
     #[inline]
-
-    pub fn new() -> LinearSet<T> {
+    pub const fn new() -> LinearSet<T> {
         LinearSet { map: LinearMap::new() }
     }
 
@@ -113,7 +115,6 @@ impl<T> LinearSet<T>
     /// assert!(set.capacity() >= 100);
     /// ```
     #[inline]
-
     pub fn capacity(&self) -> usize {
         self.map.capacity()
     }
@@ -133,7 +134,6 @@ impl<T> LinearSet<T>
     /// let mut set: LinearSet<i32> = LinearSet::new();
     /// set.reserve(10);
     /// ```
-
     pub fn reserve(&mut self, additional: usize) {
         self.map.reserve(additional)
     }
@@ -154,7 +154,6 @@ impl<T> LinearSet<T>
     /// set.shrink_to_fit();
     /// assert!(set.capacity() >= 2);
     /// ```
-
     pub fn shrink_to_fit(&mut self) {
         self.map.shrink_to_fit()
     }
@@ -175,8 +174,7 @@ impl<T> LinearSet<T>
     ///     println!("{}", x);
     /// }
     /// ```
-
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter { iter: self.map.keys() }
     }
 
@@ -202,11 +200,10 @@ impl<T> LinearSet<T>
     /// let diff: LinearSet<_> = b.difference(&a).cloned().collect();
     /// assert_eq!(diff, [4].iter().cloned().collect());
     /// ```
-
     pub fn difference<'a>(&'a self, other: &'a LinearSet<T>) -> Difference<'a, T> {
         Difference {
             iter: self.iter(),
-            other: other,
+            other,
         }
     }
 
@@ -230,7 +227,6 @@ impl<T> LinearSet<T>
     /// assert_eq!(diff1, diff2);
     /// assert_eq!(diff1, [1, 4].iter().cloned().collect());
     /// ```
-
     pub fn symmetric_difference<'a>(&'a self, other: &'a LinearSet<T>)
         -> SymmetricDifference<'a, T> {
         SymmetricDifference { iter: self.difference(other).chain(other.difference(self)) }
@@ -253,11 +249,10 @@ impl<T> LinearSet<T>
     /// let intersection: LinearSet<_> = a.intersection(&b).cloned().collect();
     /// assert_eq!(intersection, [2, 3].iter().cloned().collect());
     /// ```
-
     pub fn intersection<'a>(&'a self, other: &'a LinearSet<T>) -> Intersection<'a, T> {
         Intersection {
             iter: self.iter(),
-            other: other,
+            other,
         }
     }
 
@@ -278,11 +273,95 @@ impl<T> LinearSet<T>
     /// let union: LinearSet<_> = a.union(&b).cloned().collect();
     /// assert_eq!(union, [1, 2, 3, 4].iter().cloned().collect());
     /// ```
-
     pub fn union<'a>(&'a self, other: &'a LinearSet<T>) -> Union<'a, T> {
         Union { iter: self.iter().chain(other.difference(self)) }
     }
 
+    /// Adds the values of `other` to the set, in place.
+    ///
+    /// Space for all of `other` is reserved up front, then each value not yet in the set is
+    /// cloned and appended in a single pass over `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use linear_map::set::LinearSet;
+    /// let mut a: LinearSet<_> = [1, 2, 3].iter().cloned().collect();
+    /// let b: LinearSet<_> = [4, 2, 3, 4].iter().cloned().collect();
+    ///
+    /// a.union_with(&b);
+    /// assert_eq!(a, [1, 2, 3, 4].iter().cloned().collect());
+    /// ```
+    pub fn union_with(&mut self, other: &LinearSet<T>) where T: Clone {
+        let len = self.len();
+        self.map.reserve(other.len());
+        for value in other {
+            // Values of `other` are distinct, so only the original values need to be checked.
+            if !self.map.storage[..len].iter().any(|(v, _)| v == value) {
+                self.map.invalidate_positions();
+                self.map.storage.push((value.clone(), ()));
+            }
+        }
+    }
+
+    /// Removes the values not in `other` from the set, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use linear_map::set::LinearSet;
+    /// let mut a: LinearSet<_> = [1, 2, 3].iter().cloned().collect();
+    /// let b: LinearSet<_> = [4, 2, 3, 4].iter().cloned().collect();
+    ///
+    /// a.intersect_with(&b);
+    /// assert_eq!(a, [2, 3].iter().cloned().collect());
+    /// ```
+    pub fn intersect_with(&mut self, other: &LinearSet<T>) {
+        self.retain(|v| other.contains(v));
+    }
+
+    /// Removes the values in `other` from the set, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use linear_map::set::LinearSet;
+    /// let mut a: LinearSet<_> = [1, 2, 3].iter().cloned().collect();
+    /// let b: LinearSet<_> = [4, 2, 3, 4].iter().cloned().collect();
+    ///
+    /// a.difference_with(&b);
+    /// assert_eq!(a, [1].iter().cloned().collect());
+    /// ```
+    pub fn difference_with(&mut self, other: &LinearSet<T>) {
+        self.retain(|v| !other.contains(v));
+    }
+
+    /// Replaces the set with its symmetric difference with `other`, in place.
+    ///
+    /// Values of `other` not in the set are cloned and appended in a single pass over `other`,
+    /// with space for them reserved up front; the values common to both are then removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use linear_map::set::LinearSet;
+    /// let mut a: LinearSet<_> = [1, 2, 3].iter().cloned().collect();
+    /// let b: LinearSet<_> = [4, 2, 3, 4].iter().cloned().collect();
+    ///
+    /// a.symmetric_difference_with(&b);
+    /// assert_eq!(a, [1, 4].iter().cloned().collect());
+    /// ```
+    pub fn symmetric_difference_with(&mut self, other: &LinearSet<T>) where T: Clone {
+        let len = self.len();
+        self.union_with(other);
+        let mut index = 0;
+        self.map.invalidate_positions();
+        self.map.storage.retain(|(v, _)| {
+            index += 1;
+            index > len || !other.contains(v)
+        });
+    }
+
     /// Returns the number of elements in the set.
     ///
     /// # Examples
@@ -295,7 +374,6 @@ impl<T> LinearSet<T>
     /// v.insert(1);
     /// assert_eq!(v.len(), 1);
     /// ```
-
     pub fn len(&self) -> usize { self.map.len() }
 
     /// Returns true if the set contains no elements.
@@ -310,12 +388,11 @@ impl<T> LinearSet<T>
     /// v.insert(1);
     /// assert!(!v.is_empty());
     /// ```
-
     pub fn is_empty(&self) -> bool { self.map.is_empty() }
 
     /// Clears the set, returning all elements in an iterator.
     #[inline]
-    pub fn drain(&mut self) -> Drain<T> {
+    pub fn drain(&mut self) -> Drain<'_, T> {
         Drain { iter: self.map.drain() }
     }
 
@@ -357,8 +434,8 @@ impl<T> LinearSet<T>
     /// assert_eq!(set.contains(&1), true);
     /// assert_eq!(set.contains(&4), false);
     /// ```
-    pub fn contains<Q: ?Sized>(&self, value: &Q) -> bool
-        where T: Borrow<Q>, Q: Eq
+    pub fn contains<Q>(&self, value: &Q) -> bool
+        where T: Borrow<Q>, Q: ?Sized + Eq
     {
         self.map.contains_key(value)
     }
@@ -380,7 +457,6 @@ impl<T> LinearSet<T>
     /// b.insert(1);
     /// assert_eq!(a.is_disjoint(&b), false);
     /// ```
-
     pub fn is_disjoint(&self, other: &LinearSet<T>) -> bool {
         self.iter().all(|v| !other.contains(v))
     }
@@ -401,7 +477,6 @@ impl<T> LinearSet<T>
     /// set.insert(4);
     /// assert_eq!(set.is_subset(&sup), false);
     /// ```
-
     pub fn is_subset(&self, other: &LinearSet<T>) -> bool {
         self.iter().all(|v| other.contains(v))
     }
@@ -426,7 +501,6 @@ impl<T> LinearSet<T>
     /// assert_eq!(set.is_superset(&sub), true);
     /// ```
     #[inline]
-
     pub fn is_superset(&self, other: &LinearSet<T>) -> bool {
         other.is_subset(self)
     }
@@ -448,7 +522,6 @@ impl<T> LinearSet<T>
     /// assert_eq!(set.insert(2), false);
     /// assert_eq!(set.len(), 1);
     /// ```
-
     pub fn insert(&mut self, value: T) -> bool { self.map.insert(value, ()).is_none() }
 
     /// Removes a value from the set. Returns `true` if the value was
@@ -469,9 +542,8 @@ impl<T> LinearSet<T>
     /// assert_eq!(set.remove(&2), true);
     /// assert_eq!(set.remove(&2), false);
     /// ```
-
-    pub fn remove<Q: ?Sized>(&mut self, value: &Q) -> bool
-        where T: Borrow<Q>, Q: Eq
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+        where T: Borrow<Q>, Q: ?Sized + Eq
     {
         self.map.remove(value).is_some()
     }
@@ -538,16 +610,26 @@ impl<T> Default for LinearSet<T>
     }
 }
 
-impl<K: Eq> Into<Vec<K>> for LinearSet<K> {
-    fn into(self) -> Vec<K> {
-        unsafe {
-            use std::mem;
-            mem::transmute(self)
-        }
+impl<K: Eq> From<LinearSet<K>> for Vec<K> {
+    fn from(set: LinearSet<K>) -> Vec<K> {
+        // `(K, ())` has the layout of `K`, so collecting can reuse the allocation.
+        set.map.storage.into_iter().map(|(k, ())| k).collect()
+    }
+}
+
+impl<T> From<LinearSet<T>> for LinearMap<T, ()> {
+    fn from(set: LinearSet<T>) -> LinearMap<T, ()> {
+        set.map
+    }
+}
+
+impl<T> From<LinearMap<T, ()>> for LinearSet<T> {
+    fn from(map: LinearMap<T, ()>) -> LinearSet<T> {
+        LinearSet { map }
     }
 }
 
-impl<'a, 'b, T> BitOr<&'b LinearSet<T>> for &'a LinearSet<T>
+impl<T> BitOr<&LinearSet<T>> for &LinearSet<T>
     where T: Eq + Clone
 {
     type Output = LinearSet<T>;
@@ -577,7 +659,7 @@ impl<'a, 'b, T> BitOr<&'b LinearSet<T>> for &'a LinearSet<T>
     }
 }
 
-impl<'a, 'b, T> BitAnd<&'b LinearSet<T>> for &'a LinearSet<T>
+impl<T> BitAnd<&LinearSet<T>> for &LinearSet<T>
     where T: Eq + Clone
 {
     type Output = LinearSet<T>;
@@ -607,7 +689,7 @@ impl<'a, 'b, T> BitAnd<&'b LinearSet<T>> for &'a LinearSet<T>
     }
 }
 
-impl<'a, 'b, T> BitXor<&'b LinearSet<T>> for &'a LinearSet<T>
+impl<T> BitXor<&LinearSet<T>> for &LinearSet<T>
     where T: Eq + Clone
 {
     type Output = LinearSet<T>;
@@ -637,7 +719,7 @@ impl<'a, 'b, T> BitXor<&'b LinearSet<T>> for &'a LinearSet<T>
     }
 }
 
-impl<'a, 'b, T> Sub<&'b LinearSet<T>> for &'a LinearSet<T>
+impl<T> Sub<&LinearSet<T>> for &LinearSet<T>
     where T: Eq + Clone
 {
     type Output = LinearSet<T>;
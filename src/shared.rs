@@ -0,0 +1,239 @@
+//! Maps sharing one table of keys.
+//!
+//! See the [`SharedKeysMap`](struct.SharedKeysMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::slice;
+use std::sync::Arc;
+
+use errors::UnknownKeyError;
+
+use super::LinearMap;
+
+/// A reference-counted table of unique keys, shared by any number of
+/// [`SharedKeysMap`](struct.SharedKeysMap.html)s.
+///
+/// Cloning the table is cheap: it only increments a reference count.
+pub struct SharedKeys<K> {
+    keys: Arc<[K]>,
+}
+
+impl<K: Eq> SharedKeys<K> {
+    /// Creates a table from the given keys, ignoring repeated keys.
+    pub fn new<I: IntoIterator<Item = K>>(keys: I) -> Self {
+        let mut unique: Vec<K> = Vec::new();
+        for key in keys {
+            if !unique.contains(&key) {
+                unique.push(key);
+            }
+        }
+        SharedKeys { keys: unique.into() }
+    }
+
+    /// Returns the number of keys in the table.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if the table contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the table's keys, in the order in which they were given.
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Returns the position of the key equal to the given key.
+    pub fn index_of<Q: ?Sized + Eq>(&self, key: &Q) -> Option<usize> where K: Borrow<Q> {
+        self.keys.iter().position(|k| k.borrow() == key)
+    }
+
+    /// Creates an empty map using this table.
+    pub fn new_map<V>(&self) -> SharedKeysMap<K, V> {
+        SharedKeysMap::new(self)
+    }
+}
+
+impl<K> Clone for SharedKeys<K> {
+    fn clone(&self) -> Self {
+        SharedKeys { keys: self.keys.clone() }
+    }
+}
+
+impl<K: Debug> Debug for SharedKeys<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.keys.iter()).finish()
+    }
+}
+
+/// A map whose keys are drawn from a [`SharedKeys`](struct.SharedKeys.html) table.
+///
+/// The map stores only a column of optional values, one slot per key of the table, and a
+/// reference to the table itself. Many maps with the same fixed set of keys, such as records of
+/// one schema, thus share a single copy of the keys. Lookups search the table linearly, like a
+/// `LinearMap`; only keys of the table can be inserted.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::shared::SharedKeys;
+///
+/// let schema = SharedKeys::new(vec!["id".to_string(), "name".to_string()]);
+/// let mut records = Vec::new();
+/// for i in 0..3 {
+///     let mut record = schema.new_map();
+///     record.insert("id", i.to_string()).unwrap();
+///     records.push(record);
+/// }
+/// assert_eq!(records[2].get("id").map(|s| &s[..]), Some("2"));
+/// assert_eq!(records[2].get("name"), None);
+/// assert!(records[0].insert("email", String::new()).is_err());
+/// ```
+pub struct SharedKeysMap<K, V> {
+    keys: SharedKeys<K>,
+    values: Vec<Option<V>>,
+}
+
+impl<K: Eq, V> SharedKeysMap<K, V> {
+    /// Creates an empty map using the given table.
+    pub fn new(keys: &SharedKeys<K>) -> Self {
+        SharedKeysMap {
+            keys: keys.clone(),
+            values: iter::repeat_with(|| None).take(keys.len()).collect(),
+        }
+    }
+
+    /// Returns the table of keys the map uses.
+    pub fn shared_keys(&self) -> &SharedKeys<K> {
+        &self.keys
+    }
+
+    /// Returns the number of keys that have a value.
+    pub fn len(&self) -> usize {
+        self.values.iter().filter(|v| v.is_some()).count()
+    }
+
+    /// Returns true if no key has a value.
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(|v| v.is_none())
+    }
+
+    /// Removes all values from the map.
+    pub fn clear(&mut self) {
+        for v in &mut self.values { *v = None; }
+    }
+
+    /// Returns an iterator yielding references to the keys that have a value and their values,
+    /// in the table's order.
+    ///
+    /// The iterator's item type is `(&K, &V)`.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { iter: self.keys.keys.iter().zip(self.values.iter()) }
+    }
+
+    /// Returns a reference to the value of the key equal to the given key.
+    ///
+    /// Returns `None` if the table contains no such key or if it has no value in this map.
+    pub fn get<Q: ?Sized + Eq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        self.keys.index_of(key).and_then(|i| self.values[i].as_ref())
+    }
+
+    /// Returns a mutable reference to the value of the key equal to the given key.
+    pub fn get_mut<Q: ?Sized + Eq>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+        match self.keys.index_of(key) {
+            Some(i) => self.values[i].as_mut(),
+            None => None,
+        }
+    }
+
+    /// Checks if the key equal to the given key has a value in this map.
+    pub fn contains_key<Q: ?Sized + Eq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.get(key).is_some()
+    }
+
+    /// Sets the value of the key equal to the given key, returning its previous value.
+    ///
+    /// Fails, handing the value back, if the table contains no such key.
+    pub fn insert<Q: ?Sized + Eq>(&mut self, key: &Q, value: V)
+        -> Result<Option<V>, UnknownKeyError<V>> where K: Borrow<Q> {
+        match self.keys.index_of(key) {
+            Some(i) => Ok(self.values[i].replace(value)),
+            None => Err(UnknownKeyError { value }),
+        }
+    }
+
+    /// Removes the value of the key equal to the given key and returns it.
+    pub fn remove<Q: ?Sized + Eq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        match self.keys.index_of(key) {
+            Some(i) => self.values[i].take(),
+            None => None,
+        }
+    }
+
+    /// Copies the keys that have a value into a `LinearMap`, moving the values.
+    pub fn into_linear_map(self) -> LinearMap<K, V> where K: Clone {
+        let keys = self.keys;
+        keys.keys.iter().zip(self.values)
+            .filter_map(|(k, v)| v.map(|v| (k.clone(), v)))
+            .collect()
+    }
+}
+
+impl<K, V: Clone> Clone for SharedKeysMap<K, V> {
+    fn clone(&self) -> Self {
+        SharedKeysMap { keys: self.keys.clone(), values: self.values.clone() }
+    }
+}
+
+impl<K: Eq + Debug, V: Debug> Debug for SharedKeysMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Compares the maps' entries, regardless of whether they share a table.
+impl<K: Eq, V: PartialEq> PartialEq for SharedKeysMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for SharedKeysMap<K, V> {}
+
+impl<'a, K: Eq, V> IntoIterator for &'a SharedKeysMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// An iterator yielding references to a `SharedKeysMap`'s keys and their values.
+///
+/// See [`SharedKeysMap::iter`](struct.SharedKeysMap.html#method.iter) for details.
+pub struct Iter<'a, K: 'a, V: 'a> {
+    iter: iter::Zip<slice::Iter<'a, K>, slice::Iter<'a, Option<V>>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.by_ref().filter_map(|(k, v)| v.as_ref().map(|v| (k, v))).next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    fn clone(&self) -> Self {
+        Iter { iter: self.iter.clone() }
+    }
+}
@@ -0,0 +1,487 @@
+//! A map that keeps its entries sorted by key.
+//!
+//! See the [`SortedLinearMap`](struct.SortedLinearMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::mem;
+use std::ops::{Bound, Deref};
+
+use super::{LinearMap, IntoIter, Iter, IterMut, ValuesMut};
+
+/// A map implemented by a vector of entries kept sorted by key.
+///
+/// Lookups use binary search and run in `O(log n)` time, while insertions and removals shift the
+/// entries after the affected position and run in `O(n)` time.
+///
+/// The map dereferences to a `LinearMap` whose entries are in ascending key order, so its
+/// iterators and other read-only methods visit the entries in that order. Methods that change
+/// the map are defined here, so that they keep the order.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::sorted::SortedLinearMap;
+///
+/// let mut map = SortedLinearMap::new();
+/// map.insert(3, "c");
+/// map.insert(1, "a");
+/// map.insert(2, "b");
+/// assert_eq!(map.get(&2), Some(&"b"));
+/// assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+/// ```
+pub struct SortedLinearMap<K, V> {
+    map: LinearMap<K, V>,
+}
+
+impl<K: Ord, V> SortedLinearMap<K, V> {
+    /// Creates an empty map. This method does not allocate.
+    pub const fn new() -> Self {
+        SortedLinearMap { map: LinearMap::new() }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        SortedLinearMap { map: LinearMap::with_capacity(capacity) }
+    }
+
+    /// Like [`LinearMap::clear`](../struct.LinearMap.html#method.clear).
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Like [`LinearMap::retain`](../struct.LinearMap.html#method.retain), visiting the entries
+    /// in ascending key order.
+    pub fn retain<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        self.entries_mut().retain_mut(|(k, v)| keep_fn(k, v));
+    }
+
+    /// Like [`LinearMap::iter_mut`](../struct.LinearMap.html#method.iter_mut), in ascending key
+    /// order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.map.iter_mut()
+    }
+
+    /// Like [`LinearMap::values_mut`](../struct.LinearMap.html#method.values_mut), in ascending
+    /// key order.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.map.values_mut()
+    }
+
+    /// Searches for the key, returning its position if found, or the position where it would be
+    /// inserted otherwise.
+    fn search<Q: ?Sized + Ord>(&self, key: &Q) -> Result<usize, usize> where K: Borrow<Q> {
+        self.map.storage.binary_search_by(|(k, _)| k.borrow().cmp(key))
+    }
+
+    /// Like [`LinearMap::get`](../struct.LinearMap.html#method.get), using binary search.
+    ///
+    /// The given key may be any borrowed form of the map's key type, but `Ord` on the borrowed
+    /// form *must* match that of the key type.
+    pub fn get<Q: ?Sized + Ord>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        self.search(key).ok().map(|i| &self.map.storage[i].1)
+    }
+
+    /// Like [`LinearMap::get_mut`](../struct.LinearMap.html#method.get_mut), using binary search.
+    pub fn get_mut<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+        match self.search(key) {
+            Ok(i) => Some(&mut self.map.storage[i].1),
+            Err(_) => None,
+        }
+    }
+
+    /// Like [`LinearMap::contains_key`](../struct.LinearMap.html#method.contains_key), using
+    /// binary search.
+    pub fn contains_key<Q: ?Sized + Ord>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.search(key).is_ok()
+    }
+
+    /// Like [`LinearMap::insert`](../struct.LinearMap.html#method.insert), inserting a new key at
+    /// its sorted position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(i) => Some(mem::replace(&mut self.map.storage[i].1, value)),
+            Err(i) => {
+                self.entries_mut().insert(i, (key, value));
+                None
+            }
+        }
+    }
+
+    /// Like [`LinearMap::shift_remove`](../struct.LinearMap.html#method.shift_remove), using
+    /// binary search.
+    pub fn remove<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        match self.search(key) {
+            Ok(i) => Some(self.entries_mut().remove(i).1),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns the entry with the smallest key, or `None` if the map is empty.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.map.storage.first().map(|e| (&e.0, &e.1))
+    }
+
+    /// Returns the entry with the largest key, or `None` if the map is empty.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.map.storage.last().map(|e| (&e.0, &e.1))
+    }
+
+    /// Removes and returns the entry with the smallest key, or `None` if the map is empty.
+    ///
+    /// This shifts all remaining entries and takes `O(n)` time.
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.map.is_empty() { None } else { Some(self.entries_mut().remove(0)) }
+    }
+
+    /// Removes and returns the entry with the largest key, or `None` if the map is empty.
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        self.entries_mut().pop()
+    }
+
+    /// Returns a cursor positioned just before the first entry above the given bound.
+    ///
+    /// With `Bound::Included(x)` the cursor's next entry is the first whose key is at least `x`;
+    /// with `Bound::Excluded(x)`, the first whose key is greater than `x`; with
+    /// `Bound::Unbounded`, the first entry of the map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use linear_map::sorted::SortedLinearMap;
+    ///
+    /// let map: SortedLinearMap<_, _> = vec![(10, 'a'), (20, 'b'), (30, 'c')]
+    ///     .into_iter().collect();
+    /// let mut cursor = map.lower_bound(Bound::Included(&15));
+    /// assert_eq!(cursor.peek_prev(), Some((&10, &'a')));
+    /// assert_eq!(cursor.next(), Some((&20, &'b')));
+    /// assert_eq!(cursor.next(), Some((&30, &'c')));
+    /// assert_eq!(cursor.next(), None);
+    /// ```
+    pub fn lower_bound<Q: ?Sized + Ord>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where K: Borrow<Q> {
+        let index = match bound {
+            Bound::Included(x) => self.map.storage.partition_point(|(k, _)| k.borrow() < x),
+            Bound::Excluded(x) => self.map.storage.partition_point(|(k, _)| k.borrow() <= x),
+            Bound::Unbounded => 0,
+        };
+        Cursor { storage: &self.map.storage, index }
+    }
+
+    /// Returns a cursor positioned just after the last entry below the given bound.
+    ///
+    /// With `Bound::Included(x)` the cursor's previous entry is the last whose key is at most
+    /// `x`; with `Bound::Excluded(x)`, the last whose key is less than `x`; with
+    /// `Bound::Unbounded`, the last entry of the map.
+    pub fn upper_bound<Q: ?Sized + Ord>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where K: Borrow<Q> {
+        let index = match bound {
+            Bound::Included(x) => self.map.storage.partition_point(|(k, _)| k.borrow() <= x),
+            Bound::Excluded(x) => self.map.storage.partition_point(|(k, _)| k.borrow() < x),
+            Bound::Unbounded => self.map.len(),
+        };
+        Cursor { storage: &self.map.storage, index }
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// A vacant entry remembers the position where the key belongs, so inserting through it
+    /// keeps the map sorted without searching again.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.search(&key) {
+            Ok(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            Err(index) => Entry::Vacant(VacantEntry { map: self, key, index }),
+        }
+    }
+
+    /// Merges two maps into one in a single linear pass over both, like the merge step of merge
+    /// sort.
+    ///
+    /// Keys present in only one map keep their value. For keys present in both, `resolve` is
+    /// called with the key, the value from `self` and the value from `other`, and its result is
+    /// kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use linear_map::sorted::SortedLinearMap;
+    ///
+    /// let a: SortedLinearMap<_, _> = vec![(1, 10), (3, 30)].into_iter().collect();
+    /// let b: SortedLinearMap<_, _> = vec![(2, 2), (3, 3)].into_iter().collect();
+    /// let merged = a.merge_sorted(b, |_, x, y| x + y);
+    /// assert_eq!(merged.iter().collect::<Vec<_>>(), [(&1, &10), (&2, &2), (&3, &33)]);
+    /// ```
+    pub fn merge_sorted<F>(self, other: Self, mut resolve: F) -> Self
+    where F: FnMut(&K, V, V) -> V {
+        let mut storage = Vec::with_capacity(self.len() + other.len());
+        let mut left = self.map.into_iter().peekable();
+        let mut right = other.map.into_iter().peekable();
+        loop {
+            let order = match (left.peek(), right.peek()) {
+                (Some(l), Some(r)) => l.0.cmp(&r.0),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            let entry = match order {
+                Ordering::Less => left.next(),
+                Ordering::Greater => right.next(),
+                Ordering::Equal => match (left.next(), right.next()) {
+                    (Some((k, l)), Some((_, r))) => {
+                        let value = resolve(&k, l, r);
+                        Some((k, value))
+                    }
+                    _ => unreachable!(),
+                },
+            };
+            storage.extend(entry);
+        }
+        SortedLinearMap { map: LinearMap::from_storage(storage) }
+    }
+}
+
+impl<K, V> SortedLinearMap<K, V> {
+    /// Returns the entries for a change that inserts or removes some of them.
+    fn entries_mut(&mut self) -> &mut Vec<(K, V)> {
+        self.map.invalidate_positions();
+        &mut self.map.storage
+    }
+}
+
+/// A cursor over a `SortedLinearMap`, positioned in the gap between two entries or at either
+/// end of the map.
+///
+/// See [`SortedLinearMap::lower_bound`](struct.SortedLinearMap.html#method.lower_bound) for
+/// details.
+pub struct Cursor<'a, K: 'a, V: 'a> {
+    storage: &'a [(K, V)],
+    index: usize,
+}
+
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Returns the entry after the cursor without moving it.
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        self.storage.get(self.index).map(|e| (&e.0, &e.1))
+    }
+
+    /// Returns the entry before the cursor without moving it.
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let index = self.index.checked_sub(1)?;
+        self.storage.get(index).map(|e| (&e.0, &e.1))
+    }
+
+    /// Moves the cursor back past the previous entry and returns it, or returns `None` at the
+    /// start of the map.
+    pub fn prev(&mut self) -> Option<(&'a K, &'a V)> {
+        let entry = self.peek_prev()?;
+        self.index -= 1;
+        Some(entry)
+    }
+}
+
+/// Moves the cursor forward past each entry in turn.
+impl<'a, K, V> Iterator for Cursor<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.peek_next()?;
+        self.index += 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.storage.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<'a, K, V> Clone for Cursor<'a, K, V> {
+    fn clone(&self) -> Self {
+        Cursor { storage: self.storage, index: self.index }
+    }
+}
+
+/// A view into a single occupied location in a `SortedLinearMap`.
+///
+/// See [`SortedLinearMap::entry`](struct.SortedLinearMap.html#method.entry) for details.
+pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
+    map: &'a mut SortedLinearMap<K, V>,
+    index: usize,
+}
+
+/// A view into a single vacant location in a `SortedLinearMap`.
+///
+/// See [`SortedLinearMap::entry`](struct.SortedLinearMap.html#method.entry) for details.
+pub struct VacantEntry<'a, K: 'a, V: 'a> {
+    map: &'a mut SortedLinearMap<K, V>,
+    key: K,
+    index: usize,
+}
+
+/// A view into a single entry in a `SortedLinearMap`.
+///
+/// See [`SortedLinearMap::entry`](struct.SortedLinearMap.html#method.entry) for details.
+pub enum Entry<'a, K: 'a, V: 'a> {
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V>),
+
+    /// A vacant entry.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    /// Ensures that the entry is occupied by inserting the given value if it is vacant.
+    ///
+    /// Returns a mutable reference to the entry's value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures that the entry is occupied by inserting the the result of the given function if it
+    /// is vacant.
+    ///
+    /// Returns a mutable reference to the entry's value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// Returns a reference to the entry's key.
+    pub fn key(&self) -> &K {
+        &self.map.map.storage[self.index].0
+    }
+
+    /// Returns a reference to the entry's value.
+    pub fn get(&self) -> &V {
+        &self.map.map.storage[self.index].1
+    }
+
+    /// Returns a mutable reference to the entry's value.
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.map.storage[self.index].1
+    }
+
+    /// Returns a mutable reference to the entry's value with the same lifetime as the map.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.map.storage[self.index].1
+    }
+
+    /// Replaces the entry's value with the given one and returns the previous value.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry from the map and returns its value.
+    pub fn remove(self) -> V {
+        self.map.entries_mut().remove(self.index).1
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    /// Returns a reference to the key that would be inserted.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Inserts the entry into the map at its sorted position with the given value.
+    ///
+    /// Returns a mutable reference to the entry's value with the same lifetime as the map.
+    pub fn insert(self, value: V) -> &'a mut V {
+        let entries = self.map.entries_mut();
+        entries.insert(self.index, (self.key, value));
+        &mut entries[self.index].1
+    }
+}
+
+impl<K, V> Deref for SortedLinearMap<K, V> {
+    type Target = LinearMap<K, V>;
+
+    fn deref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> AsRef<LinearMap<K, V>> for SortedLinearMap<K, V> {
+    fn as_ref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for SortedLinearMap<K, V> {
+    fn clone(&self) -> Self {
+        SortedLinearMap { map: self.map.clone() }
+    }
+}
+
+impl<K: Ord + Debug, V: Debug> Debug for SortedLinearMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> Default for SortedLinearMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SortedLinearMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K: Ord, V> iter::FromIterator<(K, V)> for SortedLinearMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+impl<K: Ord, V: PartialEq> PartialEq for SortedLinearMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        // Equal maps hold their entries in the same order.
+        self.map.storage == other.map.storage
+    }
+}
+
+impl<K: Ord, V: Eq> Eq for SortedLinearMap<K, V> {}
+
+impl<K: Ord, V> IntoIterator for SortedLinearMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a SortedLinearMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.map.iter()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a mut SortedLinearMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,286 @@
+//! A map that counts how often each of its keys is looked up.
+//!
+//! See the [`CountingLinearMap`](struct.CountingLinearMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::mem;
+use std::ops::Deref;
+
+use super::{LinearMap, IntoIter, Iter};
+
+/// Access statistics for one key of a [`CountingLinearMap`](struct.CountingLinearMap.html).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct KeyStats {
+    /// The number of lookups and overwrites that found the key.
+    pub hits: u64,
+    /// When the key was last inserted, found or overwritten, as a tick of the map's access clock:
+    /// larger values are more recent.
+    pub last_access: u64,
+}
+
+/// A map implemented by searching linearly in a vector, which records per-key access statistics.
+///
+/// Every lookup (`get`, `get_mut`, `contains_key`) that finds a key, and every insertion that
+/// overwrites one, counts as a hit for that key and moves it to the front of the access order.
+/// Lookups that find nothing are counted as misses. This is meant for checking, before reaching
+/// for a cache or a move-to-front layout, whether a few keys really dominate a map's traffic.
+///
+/// The wrapper dereferences to the underlying `LinearMap`, so iteration and the other read-only
+/// methods are available; only the methods defined here count accesses.
+///
+/// The statistics live in `Cell`s so that lookups through `&self` can update them, which makes
+/// this type `!Sync`.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::stats::CountingLinearMap;
+///
+/// let mut routes = CountingLinearMap::new();
+/// routes.insert("/", "index");
+/// routes.insert("/about", "about");
+/// for _ in 0..3 {
+///     routes.get("/");
+/// }
+/// routes.get("/about");
+/// routes.get("/missing");
+///
+/// let stats = routes.access_stats();
+/// assert_eq!(stats[0].0, &"/");
+/// assert_eq!(stats[0].1.hits, 3);
+/// assert_eq!(routes.misses(), 1);
+/// ```
+pub struct CountingLinearMap<K, V> {
+    map: LinearMap<K, V>,
+    // Parallel to the map's entries.
+    stats: Vec<Cell<KeyStats>>,
+    clock: Cell<u64>,
+    misses: Cell<u64>,
+}
+
+impl<K: PartialEq, V> CountingLinearMap<K, V> {
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        CountingLinearMap {
+            map: LinearMap::new(),
+            stats: Vec::new(),
+            clock: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+
+    /// Clears the map, removing all elements and their statistics. The miss count is kept.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.stats.clear();
+    }
+
+    /// Returns each key with its statistics, the most hit keys first and, among keys with as many
+    /// hits, the most recently accessed first.
+    pub fn access_stats(&self) -> Vec<(&K, KeyStats)> {
+        let mut stats: Vec<_> = self.map.keys().zip(self.stats.iter().map(Cell::get)).collect();
+        stats.sort_by(|a, b| {
+            (b.1.hits, b.1.last_access).cmp(&(a.1.hits, a.1.last_access))
+        });
+        stats
+    }
+
+    /// Returns the statistics of the given key, without counting this as an access.
+    pub fn stats_of<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<KeyStats> where K: Borrow<Q> {
+        self.map.position(key).map(|i| self.stats[i].get())
+    }
+
+    /// Returns the number of lookups that did not find their key.
+    pub fn misses(&self) -> u64 {
+        self.misses.get()
+    }
+
+    /// Resets the hit counts, access order and miss count, keeping the entries.
+    pub fn reset_stats(&mut self) {
+        for stats in &self.stats {
+            stats.set(KeyStats::default());
+        }
+        self.clock.set(0);
+        self.misses.set(0);
+    }
+
+    fn tick(&self) -> u64 {
+        let now = self.clock.get() + 1;
+        self.clock.set(now);
+        now
+    }
+
+    fn hit<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<usize> where K: Borrow<Q> {
+        match self.map.position(key) {
+            Some(i) => {
+                self.record_hit(i);
+                Some(i)
+            }
+            None => {
+                self.misses.set(self.misses.get() + 1);
+                None
+            }
+        }
+    }
+
+    fn record_hit(&self, i: usize) {
+        let stats = &self.stats[i];
+        stats.set(KeyStats { hits: stats.get().hits + 1, last_access: self.tick() });
+    }
+
+    /// Keeps the entries for which `keep_fn` returns `true`, like
+    /// [`LinearMap::retain`](../struct.LinearMap.html#method.retain), along with their
+    /// statistics. The closure's visits do not count as accesses.
+    pub fn retain<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        // Entries and statistics are moved in lockstep, so they stay parallel even if `keep_fn`
+        // panics.
+        self.map.invalidate_positions();
+        let len = self.map.storage.len();
+        let mut del = 0;
+        for i in 0..len {
+            let (ref k, ref mut v) = self.map.storage[i];
+            if !keep_fn(k, v) {
+                del += 1;
+            } else if del > 0 {
+                self.map.storage.swap(i - del, i);
+                self.stats.swap(i - del, i);
+            }
+        }
+        self.map.storage.truncate(len - del);
+        self.stats.truncate(len - del);
+    }
+
+    /// Like [`LinearMap::get`](../struct.LinearMap.html#method.get), counting a hit or a miss.
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        self.hit(key).map(|i| &self.map.storage[i].1)
+    }
+
+    /// Like [`LinearMap::get_mut`](../struct.LinearMap.html#method.get_mut), counting a hit or a
+    /// miss.
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V>
+    where K: Borrow<Q> {
+        let i = self.hit(key)?;
+        Some(&mut self.map.storage[i].1)
+    }
+
+    /// Like [`LinearMap::contains_key`](../struct.LinearMap.html#method.contains_key), counting a
+    /// hit or a miss.
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.hit(key).is_some()
+    }
+
+    /// Like [`LinearMap::insert`](../struct.LinearMap.html#method.insert). Overwriting the value
+    /// of an existing key counts as a hit; a new key starts with no hits.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.map.position(&key) {
+            Some(i) => {
+                self.record_hit(i);
+                Some(mem::replace(&mut self.map.storage[i].1, value))
+            }
+            None => {
+                let now = self.tick();
+                self.map.invalidate_positions();
+                self.map.storage.push((key, value));
+                self.stats.push(Cell::new(KeyStats { hits: 0, last_access: now }));
+                None
+            }
+        }
+    }
+
+    /// Like [`LinearMap::remove`](../struct.LinearMap.html#method.remove), also dropping the
+    /// key's statistics.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let i = self.map.position(key)?;
+        self.stats.swap_remove(i);
+        self.map.invalidate_positions();
+        Some(self.map.storage.swap_remove(i).1)
+    }
+
+    /// Consumes the wrapper and returns the underlying map, discarding the statistics.
+    pub fn into_inner(self) -> LinearMap<K, V> {
+        self.map
+    }
+}
+
+impl<K, V> Deref for CountingLinearMap<K, V> {
+    type Target = LinearMap<K, V>;
+
+    fn deref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> AsRef<LinearMap<K, V>> for CountingLinearMap<K, V> {
+    fn as_ref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+/// Clones the map's entries and statistics.
+impl<K: Clone, V: Clone> Clone for CountingLinearMap<K, V> {
+    fn clone(&self) -> Self {
+        CountingLinearMap {
+            map: self.map.clone(),
+            stats: self.stats.clone(),
+            clock: self.clock.clone(),
+            misses: self.misses.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for CountingLinearMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: PartialEq, V> Default for CountingLinearMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq, V> Extend<(K, V)> for CountingLinearMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K: PartialEq, V> iter::FromIterator<(K, V)> for CountingLinearMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+/// Compares the maps' entries; their statistics are ignored.
+impl<K: PartialEq, V: PartialEq> PartialEq for CountingLinearMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for CountingLinearMap<K, V> {}
+
+impl<K, V> IntoIterator for CountingLinearMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a CountingLinearMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.map.iter()
+    }
+}
@@ -0,0 +1,360 @@
+//! A map with a smaller header, storing its length and capacity as `u32`.
+//!
+//! See the [`ThinLinearMap`](struct.ThinLinearMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
+use std::ptr::NonNull;
+use std::slice;
+
+use super::{LinearMap, IntoIter, Iter, IterMut, Keys, Values, ValuesMut};
+
+/// A map implemented by searching linearly in a vector, with a 16-byte header.
+///
+/// A `LinearMap` is as large as a `Vec`: a pointer plus a `usize` length and capacity, 24 bytes
+/// on 64-bit targets. `ThinLinearMap` keeps the pointer but stores the length and capacity as
+/// `u32`, bringing the header down to 16 bytes (and `Option<ThinLinearMap>` too), which adds up
+/// when a map is embedded in every node of a large structure. The price is a limit of
+/// `u32::MAX` entries; exceeding it panics.
+///
+/// Converting to and from a `LinearMap` reuses the allocation.
+///
+/// # Example
+///
+/// ```
+/// use std::mem::size_of;
+/// use linear_map::thin::ThinLinearMap;
+///
+/// let mut attrs = ThinLinearMap::new();
+/// attrs.insert("id", 7);
+/// attrs.insert("depth", 2);
+/// assert_eq!(attrs.get("depth"), Some(&2));
+/// assert!(size_of::<ThinLinearMap<&str, i32>>() <= 2 * size_of::<usize>());
+/// ```
+pub struct ThinLinearMap<K, V> {
+    ptr: NonNull<(K, V)>,
+    len: u32,
+    cap: u32,
+    marker: PhantomData<(K, V)>,
+}
+
+// The map owns its entries, just like a `Vec<(K, V)>`.
+unsafe impl<K: Send, V: Send> Send for ThinLinearMap<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for ThinLinearMap<K, V> {}
+
+impl<K, V> ThinLinearMap<K, V> {
+    /// Creates an empty map. This method does not allocate.
+    pub const fn new() -> Self {
+        ThinLinearMap {
+            ptr: NonNull::dangling(),
+            len: 0,
+            cap: if mem::size_of::<(K, V)>() == 0 { u32::MAX } else { 0 },
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity exceeds `u32::MAX`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_vec(Vec::with_capacity(checked(capacity)))
+    }
+
+    fn from_vec(vec: Vec<(K, V)>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+        let len = checked(vec.len());
+        if vec.capacity() > u32::MAX as usize && mem::size_of::<(K, V)>() != 0 {
+            vec.shrink_to(u32::MAX as usize);
+        }
+        ThinLinearMap {
+            // A vector's pointer is never null, even when it has not allocated.
+            ptr: unsafe { NonNull::new_unchecked(vec.as_mut_ptr()) },
+            len: len as u32,
+            cap: vec.capacity().min(u32::MAX as usize) as u32,
+            marker: PhantomData,
+        }
+    }
+
+    fn into_vec(self) -> Vec<(K, V)> {
+        let this = ManuallyDrop::new(self);
+        unsafe { this.raw_vec() }
+    }
+
+    // Safety: the returned vector aliases the map's allocation; exactly one of the two may be
+    // dropped.
+    unsafe fn raw_vec(&self) -> Vec<(K, V)> {
+        let cap = if mem::size_of::<(K, V)>() == 0 { usize::MAX } else { self.cap as usize };
+        Vec::from_raw_parts(self.ptr.as_ptr(), self.len as usize, cap)
+    }
+
+    /// Runs `f` on the map's storage as a `Vec`, storing the vector's new parts before returning,
+    /// or while unwinding if `f` panics.
+    fn with_vec<R, F: FnOnce(&mut Vec<(K, V)>) -> R>(&mut self, f: F) -> R {
+        let vec = ManuallyDrop::new(unsafe { self.raw_vec() });
+        let mut parts = StoreParts { map: self, vec };
+        f(&mut parts.vec)
+    }
+
+    fn as_slice(&self) -> &[(K, V)] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len as usize) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [(K, V)] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len as usize) }
+    }
+
+    /// Returns the number of elements the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.cap as usize
+    }
+
+    /// Reserves capacity for at least `additional` more to be inserted in the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `u32::MAX`.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = checked(self.len().checked_add(additional).expect("capacity overflow"));
+        if required > self.cap as usize {
+            // Grow like `Vec`, but never past what the header can record.
+            let target = required.max(2 * self.cap as usize).max(4).min(u32::MAX as usize);
+            self.with_vec(|vec| {
+                let len = vec.len();
+                vec.reserve_exact(target - len);
+            });
+        }
+    }
+
+    /// Shrinks the capacity of the map as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.with_vec(|vec| vec.shrink_to_fit());
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clears the map, removing all elements. Keeps the allocated memory for
+    /// reuse.
+    pub fn clear(&mut self) {
+        self.with_vec(|vec| vec.clear());
+    }
+
+    /// Scan through the map and keep those key-value pairs where the
+    /// closure returns `true`.
+    ///
+    /// The order the elements are visited is not specified.
+    pub fn retain<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        self.with_vec(|vec| vec.retain_mut(|(k, v)| keep_fn(k, v)));
+    }
+
+    /// Returns an iterator yielding references to the map's keys and their corresponding values in
+    /// arbitrary order.
+    ///
+    /// The iterator's item type is `(&K, &V)`.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.as_slice().iter())
+    }
+
+    /// Returns an iterator yielding references to the map's keys and mutable references to their
+    /// corresponding values in arbitrary order.
+    ///
+    /// The iterator's item type is `(&K, &mut V)`.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(self.as_mut_slice().iter_mut())
+    }
+
+    /// Returns an iterator yielding references to the map's keys in arbitrary order.
+    ///
+    /// The iterator's item type is `&K`.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.iter())
+    }
+
+    /// Returns an iterator yielding references to the map's values in arbitrary order.
+    ///
+    /// The iterator's item type is `&V`.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(self.iter())
+    }
+
+    /// Returns an iterator yielding mutable references to the map's values in arbitrary order.
+    ///
+    /// The iterator's item type is `&mut V`.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(self.iter_mut())
+    }
+}
+
+impl<K: PartialEq, V> ThinLinearMap<K, V> {
+    fn position<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<usize> where K: Borrow<Q> {
+        self.as_slice().iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    /// Returns a reference to the value in the map whose key is equal to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        self.position(key).map(|i| &self.as_slice()[i].1)
+    }
+
+    /// Returns a mutable reference to the value in the map whose key is equal to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+        let i = self.position(key)?;
+        Some(&mut self.as_mut_slice()[i].1)
+    }
+
+    /// Checks if the map contains a key that is equal to the given key.
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.position(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// Returns `None` if the map did not contain a key that is equal to the given key. Otherwise
+    /// the key's value is replaced with the given value, and the old value is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map already holds `u32::MAX` entries.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(i) = self.position(&key) {
+            return Some(mem::replace(&mut self.as_mut_slice()[i].1, value));
+        }
+        self.reserve(1);
+        self.with_vec(|vec| vec.push((key, value)));
+        None
+    }
+
+    /// Removes the key in the map that is equal to the given key and returns its corresponding
+    /// value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let i = self.position(key)?;
+        Some(self.with_vec(|vec| vec.swap_remove(i)).1)
+    }
+}
+
+fn checked(len: usize) -> usize {
+    assert!(len <= u32::MAX as usize, "capacity overflow");
+    len
+}
+
+/// Writes a vector's parts back into the map it was built from when dropped.
+struct StoreParts<'a, K: 'a, V: 'a> {
+    map: &'a mut ThinLinearMap<K, V>,
+    vec: ManuallyDrop<Vec<(K, V)>>,
+}
+
+impl<'a, K, V> Drop for StoreParts<'a, K, V> {
+    fn drop(&mut self) {
+        let vec = mem::take(&mut *self.vec);
+        // The old header aliases the vector's former allocation, so it must not be dropped.
+        mem::forget(mem::replace(self.map, ThinLinearMap::from_vec(vec)));
+    }
+}
+
+impl<K, V> Drop for ThinLinearMap<K, V> {
+    fn drop(&mut self) {
+        unsafe { drop(self.raw_vec()) }
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for ThinLinearMap<K, V> {
+    fn clone(&self) -> Self {
+        Self::from_vec(self.as_slice().to_vec())
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for ThinLinearMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> Default for ThinLinearMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq, V> Extend<(K, V)> for ThinLinearMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K: PartialEq, V> iter::FromIterator<(K, V)> for ThinLinearMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for ThinLinearMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for ThinLinearMap<K, V> {}
+
+impl<K, V> From<LinearMap<K, V>> for ThinLinearMap<K, V> {
+    /// Converts the map, reusing its allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map holds more than `u32::MAX` entries.
+    fn from(map: LinearMap<K, V>) -> Self {
+        Self::from_vec(map.storage)
+    }
+}
+
+impl<K, V> From<ThinLinearMap<K, V>> for LinearMap<K, V> {
+    fn from(map: ThinLinearMap<K, V>) -> Self {
+        LinearMap::from_storage(map.into_vec())
+    }
+}
+
+impl<K, V> IntoIterator for ThinLinearMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter { iter: self.into_vec().into_iter() }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ThinLinearMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut ThinLinearMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
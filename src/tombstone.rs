@@ -0,0 +1,359 @@
+//! An insertion-ordered map whose removals leave tombstones, reclaimed later by `compact`.
+//!
+//! See the [`TombstoneLinearMap`](struct.TombstoneLinearMap.html) type for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::iter;
+use std::mem;
+use std::slice;
+use std::vec;
+
+use super::LinearMap;
+
+/// A map implemented by searching linearly in a vector, which keeps its entries in insertion
+/// order and removes them lazily.
+///
+/// `LinearMap::shift_remove` keeps the order of entries but moves every entry after the removed
+/// one. Here, removing an entry instead marks its slot dead in `O(1)` time once the key is
+/// found, and iteration skips dead slots. The space they occupy is reclaimed, in one `O(n)`
+/// pass, by [`compact`](#method.compact), which the map never calls on its own; use
+/// [`tombstones`](#method.tombstones) to decide when it is worthwhile. Lookups still scan dead
+/// slots until then.
+///
+/// Inserting a new key always appends it, and overwriting the value of an existing key keeps
+/// its position.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::tombstone::TombstoneLinearMap;
+///
+/// let mut steps = TombstoneLinearMap::new();
+/// steps.insert("fetch", 1);
+/// steps.insert("build", 2);
+/// steps.insert("test", 3);
+///
+/// steps.remove("build");
+/// assert_eq!(steps.keys().collect::<Vec<_>>(), [&"fetch", &"test"]);
+/// assert_eq!(steps.tombstones(), 1);
+///
+/// steps.compact();
+/// assert_eq!(steps.tombstones(), 0);
+/// ```
+pub struct TombstoneLinearMap<K, V> {
+    slots: Vec<Option<(K, V)>>,
+    len: usize,
+}
+
+impl<K, V> TombstoneLinearMap<K, V> {
+    /// Creates an empty map. This method does not allocate.
+    pub const fn new() -> Self {
+        TombstoneLinearMap { slots: Vec::new(), len: 0 }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        TombstoneLinearMap { slots: Vec::with_capacity(capacity), len: 0 }
+    }
+
+    /// Returns the number of slots, live or dead, the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Returns the number of live entries in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the map has no live entries, whatever its tombstones.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of dead slots left behind by removals since the last compaction.
+    pub fn tombstones(&self) -> usize {
+        self.slots.len() - self.len
+    }
+
+    /// Drops all dead slots, moving the remaining entries together in their current order.
+    pub fn compact(&mut self) {
+        self.slots.retain(Option::is_some);
+    }
+
+    /// Removes all entries and tombstones, keeping the slots' memory for reuse.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.len = 0;
+    }
+
+    /// Scan through the map in order and keep those key-value pairs where the closure returns
+    /// `true`. This also compacts the map.
+    pub fn retain<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(&K, &mut V) -> bool {
+        // Counting removals as they happen keeps `len` right if `keep_fn` panics part way.
+        let len = &mut self.len;
+        self.slots.retain_mut(|slot| match *slot {
+            Some((ref k, ref mut v)) => {
+                let keep = keep_fn(k, v);
+                if !keep {
+                    *len -= 1;
+                }
+                keep
+            }
+            None => false,
+        });
+    }
+
+    /// Like [`LinearMap::iter`](../struct.LinearMap.html#method.iter), visiting the live entries in
+    /// insertion order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { iter: self.slots.iter(), len: self.len }
+    }
+
+    /// Like [`LinearMap::iter_mut`](../struct.LinearMap.html#method.iter_mut), visiting the live
+    /// entries in insertion order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut { iter: self.slots.iter_mut(), len: self.len }
+    }
+
+    /// Returns an iterator over the live keys in insertion order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.iter() }
+    }
+
+    /// Returns an iterator over the live values in insertion order.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.iter() }
+    }
+}
+
+impl<K: PartialEq, V> TombstoneLinearMap<K, V> {
+    fn position<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<usize> where K: Borrow<Q> {
+        self.slots.iter().position(|slot| slot.as_ref().is_some_and(|(k, _)| k.borrow() == key))
+    }
+
+    /// Like [`LinearMap::get`](../struct.LinearMap.html#method.get). Dead slots are scanned but
+    /// never match.
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        self.position(key).and_then(|i| self.slots[i].as_ref()).map(|(_, v)| v)
+    }
+
+    /// Like [`LinearMap::get_mut`](../struct.LinearMap.html#method.get_mut).
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+        let i = self.position(key)?;
+        self.slots[i].as_mut().map(|(_, v)| v)
+    }
+
+    /// Like [`LinearMap::contains_key`](../struct.LinearMap.html#method.contains_key).
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.position(key).is_some()
+    }
+
+    /// Like [`LinearMap::insert`](../struct.LinearMap.html#method.insert). A new key is appended
+    /// after every slot, dead or alive; an existing key's value is replaced in place.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(v) = self.get_mut(&key) {
+            return Some(mem::replace(v, value));
+        }
+        self.slots.push(Some((key, value)));
+        self.len += 1;
+        None
+    }
+
+    /// Like [`LinearMap::shift_remove`](../struct.LinearMap.html#method.shift_remove), but leaves a
+    /// tombstone in the entry's slot instead of moving the entries after it.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let i = self.position(key)?;
+        self.len -= 1;
+        self.slots[i].take().map(|(_, v)| v)
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for TombstoneLinearMap<K, V> {
+    fn clone(&self) -> Self {
+        TombstoneLinearMap { slots: self.slots.clone(), len: self.len }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for TombstoneLinearMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> Default for TombstoneLinearMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq, V> Extend<(K, V)> for TombstoneLinearMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<K: PartialEq, V> iter::FromIterator<(K, V)> for TombstoneLinearMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for TombstoneLinearMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for TombstoneLinearMap<K, V> {}
+
+impl<K, V> From<LinearMap<K, V>> for TombstoneLinearMap<K, V> {
+    fn from(map: LinearMap<K, V>) -> Self {
+        let len = map.len();
+        TombstoneLinearMap { slots: map.storage.into_iter().map(Some).collect(), len }
+    }
+}
+
+impl<K, V> From<TombstoneLinearMap<K, V>> for LinearMap<K, V> {
+    fn from(map: TombstoneLinearMap<K, V>) -> Self {
+        LinearMap::from_storage(map.slots.into_iter().flatten().collect())
+    }
+}
+
+impl<K, V> IntoIterator for TombstoneLinearMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter { iter: self.slots.into_iter(), len: self.len }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a TombstoneLinearMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut TombstoneLinearMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+/// A borrowing iterator over the live entries of a `TombstoneLinearMap`.
+///
+/// See [`TombstoneLinearMap::iter`](struct.TombstoneLinearMap.html#method.iter) for details.
+pub struct Iter<'a, K: 'a, V: 'a> {
+    iter: slice::Iter<'a, Option<(K, V)>>,
+    len: usize,
+}
+
+/// A mutable borrowing iterator over the live entries of a `TombstoneLinearMap`.
+///
+/// See [`TombstoneLinearMap::iter_mut`](struct.TombstoneLinearMap.html#method.iter_mut) for
+/// details.
+pub struct IterMut<'a, K: 'a, V: 'a> {
+    iter: slice::IterMut<'a, Option<(K, V)>>,
+    len: usize,
+}
+
+/// An owning iterator over the live entries of a `TombstoneLinearMap`.
+///
+/// See [`TombstoneLinearMap::into_iter`](struct.TombstoneLinearMap.html#method.into_iter) for
+/// details.
+pub struct IntoIter<K, V> {
+    iter: vec::IntoIter<Option<(K, V)>>,
+    len: usize,
+}
+
+/// An iterator over the keys of a `TombstoneLinearMap`.
+///
+/// See [`TombstoneLinearMap::keys`](struct.TombstoneLinearMap.html#method.keys) for details.
+pub struct Keys<'a, K: 'a, V: 'a> {
+    iter: Iter<'a, K, V>,
+}
+
+/// An iterator over the values of a `TombstoneLinearMap`.
+///
+/// See [`TombstoneLinearMap::values`](struct.TombstoneLinearMap.html#method.values) for details.
+pub struct Values<'a, K: 'a, V: 'a> {
+    iter: Iter<'a, K, V>,
+}
+
+macro_rules! impl_live_iter {($typ:ident [$($lt:lifetime),*], $item:ty, $f:expr) => {
+    impl<$($lt,)* K, V> Iterator for $typ<$($lt,)* K, V> {
+        type Item = $item;
+
+        fn next(&mut self) -> Option<$item> {
+            let item = self.iter.by_ref().flatten().next().map($f)?;
+            self.len -= 1;
+            Some(item)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.len, Some(self.len))
+        }
+    }
+
+    impl<$($lt,)* K, V> DoubleEndedIterator for $typ<$($lt,)* K, V> {
+        fn next_back(&mut self) -> Option<$item> {
+            let item = self.iter.by_ref().flatten().next_back().map($f)?;
+            self.len -= 1;
+            Some(item)
+        }
+    }
+
+    impl<$($lt,)* K, V> ExactSizeIterator for $typ<$($lt,)* K, V> {}
+}}
+
+impl_live_iter!{Iter ['a], (&'a K, &'a V), |e: &'a (K, V)| (&e.0, &e.1)}
+impl_live_iter!{IterMut ['a], (&'a K, &'a mut V), |e: &'a mut (K, V)| (&e.0, &mut e.1)}
+impl_live_iter!{IntoIter [], (K, V), |e| e}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    fn clone(&self) -> Self {
+        Iter { iter: self.iter.clone(), len: self.len }
+    }
+}
+
+macro_rules! impl_proj_iter {($typ:ident, $item:ty, $f:expr) => {
+    impl<'a, K, V> Iterator for $typ<'a, K, V> {
+        type Item = $item;
+
+        fn next(&mut self) -> Option<$item> {
+            self.iter.next().map($f)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+
+    impl<'a, K, V> DoubleEndedIterator for $typ<'a, K, V> {
+        fn next_back(&mut self) -> Option<$item> {
+            self.iter.next_back().map($f)
+        }
+    }
+
+    impl<'a, K, V> ExactSizeIterator for $typ<'a, K, V> {}
+
+    impl<'a, K, V> Clone for $typ<'a, K, V> {
+        fn clone(&self) -> Self {
+            $typ { iter: self.iter.clone() }
+        }
+    }
+}}
+
+impl_proj_iter!{Keys, &'a K, |e: (&'a K, &'a V)| e.0}
+impl_proj_iter!{Values, &'a V, |e: (&'a K, &'a V)| e.1}
@@ -0,0 +1,426 @@
+//! A map keyed by small integers, stored directly in a vector.
+//!
+//! See the [`VecMap`](struct.VecMap.html) type for details.
+
+use std::fmt::{self, Debug};
+use std::iter;
+use std::ops;
+use std::slice;
+use std::vec;
+
+/// A map keyed by small `usize` indices, stored densely in a vector.
+///
+/// A value with key `k` lives in slot `k` of the underlying vector, so `get`, `get_mut`,
+/// `insert`, and `remove` run in `O(1)` time. The vector grows to one past the largest key ever
+/// inserted, so this map is only suitable when keys are small and reasonably dense, e.g. entity
+/// ids handed out from a counter. Iteration visits entries in ascending key order.
+///
+/// # Example
+///
+/// ```
+/// use linear_map::vec_map::VecMap;
+///
+/// let mut names = VecMap::new();
+/// names.insert(0, "zero");
+/// names.insert(3, "three");
+///
+/// assert_eq!(names.get(3), Some(&"three"));
+/// assert_eq!(names.get(1), None);
+/// assert_eq!(names.len(), 2);
+///
+/// for (id, name) in &names {
+///     println!("{}: {}", id, name);
+/// }
+/// ```
+pub struct VecMap<V> {
+    storage: Vec<Option<V>>,
+    len: usize,
+}
+
+impl<V> VecMap<V> {
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        VecMap { storage: vec![], len: 0 }
+    }
+
+    /// Creates an empty map with room for keys `0..capacity` without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        VecMap { storage: Vec::with_capacity(capacity), len: 0 }
+    }
+
+    /// Returns the number of key slots the map can hold without reallocating.
+    ///
+    /// Any key smaller than the capacity can be inserted without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.storage.capacity()
+    }
+
+    /// Reserves capacity so that keys up to `len` can be inserted without reallocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new allocation size overflows `usize`.
+    pub fn reserve_len(&mut self, len: usize) {
+        let cur = self.storage.len();
+        if len > cur {
+            self.storage.reserve(len - cur);
+        }
+    }
+
+    /// Shrinks the capacity of the map as much as possible.
+    ///
+    /// Trailing empty slots are released, but the vector still spans up to the largest key
+    /// present in the map.
+    pub fn shrink_to_fit(&mut self) {
+        while let Some(&None) = self.storage.last() {
+            self.storage.pop();
+        }
+        self.storage.shrink_to_fit();
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clears the map, removing all elements. Keeps the allocated memory for
+    /// reuse.
+    pub fn clear(&mut self) {
+        self.storage.clear();
+        self.len = 0;
+    }
+
+    /// Scan through the map and keep those key-value pairs where the
+    /// closure returns `true`.
+    ///
+    /// Elements are visited in ascending key order.
+    pub fn retain<F>(&mut self, mut keep_fn: F)
+    where F: FnMut(usize, &mut V) -> bool {
+        for (key, slot) in self.storage.iter_mut().enumerate() {
+            let keep = match *slot {
+                Some(ref mut value) => keep_fn(key, value),
+                None => true,
+            };
+            if !keep {
+                *slot = None;
+                self.len -= 1;
+            }
+        }
+    }
+
+    /// Returns an iterator yielding the map's keys and references to their corresponding values
+    /// in ascending key order.
+    ///
+    /// The iterator's item type is `(usize, &V)`.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter { iter: self.storage.iter().enumerate(), len: self.len }
+    }
+
+    /// Returns an iterator yielding the map's keys and mutable references to their corresponding
+    /// values in ascending key order.
+    ///
+    /// The iterator's item type is `(usize, &mut V)`.
+    pub fn iter_mut(&mut self) -> IterMut<'_, V> {
+        IterMut { iter: self.storage.iter_mut().enumerate(), len: self.len }
+    }
+
+    /// Returns an iterator yielding the map's keys in ascending order.
+    ///
+    /// The iterator's item type is `usize`.
+    pub fn keys(&self) -> Keys<'_, V> {
+        Keys { iter: self.iter() }
+    }
+
+    /// Returns an iterator yielding references to the map's values in ascending key order.
+    ///
+    /// The iterator's item type is `&V`.
+    pub fn values(&self) -> Values<'_, V> {
+        Values { iter: self.iter() }
+    }
+
+    /// Returns an iterator yielding mutable references to the map's values in ascending key
+    /// order.
+    ///
+    /// The iterator's item type is `&mut V`.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        ValuesMut { iter: self.iter_mut() }
+    }
+
+    /// Returns a reference to the value corresponding to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get(&self, key: usize) -> Option<&V> {
+        self.storage.get(key).and_then(|slot| slot.as_ref())
+    }
+
+    /// Returns a mutable reference to the value corresponding to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_mut(&mut self, key: usize) -> Option<&mut V> {
+        self.storage.get_mut(key).and_then(|slot| slot.as_mut())
+    }
+
+    /// Checks if the map contains the given key.
+    pub fn contains_key(&self, key: usize) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// Returns `None` if the map did not contain the given key. Otherwise the key's value is
+    /// replaced with the given value, and the old value is returned.
+    ///
+    /// The underlying vector is grown to hold `key` if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is `usize::MAX`, since no vector can hold that many slots.
+    pub fn insert(&mut self, key: usize, value: V) -> Option<V> {
+        if key >= self.storage.len() {
+            let end = key.checked_add(1).expect("capacity overflow");
+            let additional = end - self.storage.len();
+            self.storage.extend(iter::repeat_with(|| None).take(additional));
+        }
+        let old = self.storage[key].replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Removes the given key from the map and returns its corresponding value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove(&mut self, key: usize) -> Option<V> {
+        let old = self.storage.get_mut(key).and_then(|slot| slot.take());
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+}
+
+impl<V: Clone> Clone for VecMap<V> {
+    fn clone(&self) -> Self {
+        VecMap { storage: self.storage.clone(), len: self.len }
+    }
+
+    fn clone_from(&mut self, other: &Self) {
+        self.storage.clone_from(&other.storage);
+        self.len = other.len;
+    }
+}
+
+impl<V: Debug> Debug for VecMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<V> Default for VecMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> Extend<(usize, V)> for VecMap<V> {
+    fn extend<I: IntoIterator<Item = (usize, V)>>(&mut self, key_values: I) {
+        for (key, value) in key_values { self.insert(key, value); }
+    }
+}
+
+impl<V> iter::FromIterator<(usize, V)> for VecMap<V> {
+    fn from_iter<I: IntoIterator<Item = (usize, V)>>(key_values: I) -> Self {
+        let mut map = Self::new();
+        map.extend(key_values);
+        map
+    }
+}
+
+impl<V> ops::Index<usize> for VecMap<V> {
+    type Output = V;
+
+    fn index(&self, key: usize) -> &V {
+        self.get(key).expect("key not found")
+    }
+}
+
+impl<V> ops::IndexMut<usize> for VecMap<V> {
+    fn index_mut(&mut self, key: usize) -> &mut V {
+        self.get_mut(key).expect("key not found")
+    }
+}
+
+impl<V: PartialEq> PartialEq for VecMap<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<V: Eq> Eq for VecMap<V> {}
+
+/// A consuming iterator over a `VecMap`, in ascending key order.
+///
+/// Acquire through [`IntoIterator`](struct.VecMap.html#method.into_iter).
+pub struct IntoIter<V> {
+    iter: iter::Enumerate<vec::IntoIter<Option<V>>>,
+    len: usize,
+}
+
+/// An iterator yielding the keys of a `VecMap` and references to their corresponding values.
+///
+/// See [`VecMap::iter`](struct.VecMap.html#method.iter) for details.
+pub struct Iter<'a, V: 'a> {
+    iter: iter::Enumerate<slice::Iter<'a, Option<V>>>,
+    len: usize,
+}
+
+/// An iterator yielding the keys of a `VecMap` and mutable references to their corresponding
+/// values.
+///
+/// See [`VecMap::iter_mut`](struct.VecMap.html#method.iter_mut) for details.
+pub struct IterMut<'a, V: 'a> {
+    iter: iter::Enumerate<slice::IterMut<'a, Option<V>>>,
+    len: usize,
+}
+
+/// An iterator yielding the keys of a `VecMap`.
+///
+/// See [`VecMap::keys`](struct.VecMap.html#method.keys) for details.
+pub struct Keys<'a, V: 'a> {
+    iter: Iter<'a, V>,
+}
+
+/// An iterator yielding references to the values of a `VecMap`.
+///
+/// See [`VecMap::values`](struct.VecMap.html#method.values) for details.
+pub struct Values<'a, V: 'a> {
+    iter: Iter<'a, V>,
+}
+
+/// An iterator yielding mutable references to the values of a `VecMap`.
+///
+/// See [`VecMap::values_mut`](struct.VecMap.html#method.values_mut) for details.
+pub struct ValuesMut<'a, V: 'a> {
+    iter: IterMut<'a, V>,
+}
+
+macro_rules! impl_slot_iter {([$($gen:tt)*] $typ:ty, $item:ty) => {
+    impl<$($gen)*> Iterator for $typ {
+        type Item = $item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            for (key, slot) in self.iter.by_ref() {
+                if let Some(value) = slot {
+                    self.len -= 1;
+                    return Some((key, value));
+                }
+            }
+            None
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.len, Some(self.len))
+        }
+    }
+
+    impl<$($gen)*> DoubleEndedIterator for $typ {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            while let Some((key, slot)) = self.iter.next_back() {
+                if let Some(value) = slot {
+                    self.len -= 1;
+                    return Some((key, value));
+                }
+            }
+            None
+        }
+    }
+
+    impl<$($gen)*> ExactSizeIterator for $typ {
+        fn len(&self) -> usize {
+            self.len
+        }
+    }
+}}
+impl_slot_iter!{[V] IntoIter<V>,  (usize, V)}
+impl_slot_iter!{['a, V] Iter<'a, V>,  (usize, &'a V)}
+impl_slot_iter!{['a, V] IterMut<'a, V>,  (usize, &'a mut V)}
+
+macro_rules! impl_proj_iter {($typ:ty, $item:ty, $map:expr) => {
+    impl<'a, V> Iterator for $typ {
+        type Item = $item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.iter.next().map($map)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+
+    impl<'a, V> DoubleEndedIterator for $typ {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.iter.next_back().map($map)
+        }
+    }
+
+    impl<'a, V> ExactSizeIterator for $typ {
+        fn len(&self) -> usize {
+            self.iter.len()
+        }
+    }
+}}
+impl_proj_iter!{Keys<'a, V>,  usize,  |e| e.0 }
+impl_proj_iter!{Values<'a, V>,  &'a V,  |e| e.1 }
+impl_proj_iter!{ValuesMut<'a, V>,  &'a mut V,  |e| e.1 }
+
+impl<'a, V> Clone for Iter<'a, V> {
+    fn clone(&self) -> Self {
+        Iter { iter: self.iter.clone(), len: self.len }
+    }
+}
+
+impl<'a, V> Clone for Keys<'a, V> {
+    fn clone(&self) -> Self {
+        Keys { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, V> Clone for Values<'a, V> {
+    fn clone(&self) -> Self {
+        Values { iter: self.iter.clone() }
+    }
+}
+
+impl<V> IntoIterator for VecMap<V> {
+    type Item = (usize, V);
+    type IntoIter = IntoIter<V>;
+
+    fn into_iter(self) -> IntoIter<V> {
+        IntoIter { iter: self.storage.into_iter().enumerate(), len: self.len }
+    }
+}
+
+impl<'a, V> IntoIterator for &'a VecMap<V> {
+    type Item = (usize, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    fn into_iter(self) -> Iter<'a, V> {
+        self.iter()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a mut VecMap<V> {
+    type Item = (usize, &'a mut V);
+    type IntoIter = IterMut<'a, V>;
+
+    fn into_iter(self) -> IterMut<'a, V> {
+        self.iter_mut()
+    }
+}
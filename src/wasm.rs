@@ -0,0 +1,65 @@
+//! Optional conversions between `LinearMap` and JavaScript objects and maps.
+//!
+//! Maps with `String` keys convert to and from both `js_sys::Map` and plain JavaScript objects,
+//! preserving the order of entries.
+
+extern crate js_sys;
+extern crate wasm_bindgen;
+
+use self::js_sys::{Array, Map, Object, Reflect};
+use self::wasm_bindgen::{JsCast, JsValue};
+
+use super::LinearMap;
+
+impl<V: Into<JsValue>> From<LinearMap<String, V>> for Map {
+    fn from(map: LinearMap<String, V>) -> Map {
+        let js = Map::new();
+        for (k, v) in map {
+            js.set(&JsValue::from(k), &v.into());
+        }
+        js
+    }
+}
+
+impl LinearMap<String, JsValue> {
+    /// Builds a map from the entries of a JavaScript `Map`, in iteration order.
+    ///
+    /// Returns the first key that is not a string as the error.
+    pub fn try_from_js_map(js: &Map) -> Result<Self, JsValue> {
+        let mut map = LinearMap::with_capacity(js.size() as usize);
+        for entry in js.entries() {
+            let entry: Array = entry?.unchecked_into();
+            let key = entry.get(0);
+            match key.as_string() {
+                Some(k) => { map.insert(k, entry.get(1)); }
+                None => return Err(key),
+            }
+        }
+        Ok(map)
+    }
+
+    /// Builds a map from the own enumerable string-keyed properties of a JavaScript object.
+    pub fn from_js_object(js: &Object) -> Self {
+        let entries = Object::entries(js);
+        let mut map = LinearMap::with_capacity(entries.length() as usize);
+        for entry in entries.iter() {
+            let entry: Array = entry.unchecked_into();
+            if let Some(k) = entry.get(0).as_string() {
+                map.insert(k, entry.get(1));
+            }
+        }
+        map
+    }
+}
+
+impl<V: Into<JsValue>> LinearMap<String, V> {
+    /// Converts the map into a plain JavaScript object with one property per entry.
+    pub fn into_js_object(self) -> Object {
+        let js = Object::new();
+        for (k, v) in self {
+            // Setting a property on a fresh plain object cannot fail.
+            let _ = Reflect::set(&js, &JsValue::from(k), &v.into());
+        }
+        js
+    }
+}
@@ -0,0 +1,222 @@
+//! A map that reports when it grows past a configured length.
+//!
+//! See the [`WatchedLinearMap`](struct.WatchedLinearMap.html) type for details.
+
+use std::fmt::{self, Debug};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use super::{LinearMap, IntoIter, Iter};
+
+/// A map implemented by searching linearly in a vector, which reports when it holds more entries
+/// than a threshold.
+///
+/// Linear search degrades quietly as a map grows. This wrapper tells you which maps outgrew it:
+/// every time the map goes from at most `threshold` entries to more, its
+/// [`exceeded_count`](#method.exceeded_count) goes up, and the first time it also calls the
+/// map's callback, if any, with the map's name and new length.
+///
+/// The wrapper dereferences to the underlying `LinearMap` for reading. Changes go through
+/// [`insert`](#method.insert), `extend`, or the guard returned by
+/// [`map_mut`](#method.map_mut), which checks the threshold when it is dropped.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use linear_map::watch::WatchedLinearMap;
+///
+/// let reports = Arc::new(AtomicUsize::new(0));
+/// let counter = reports.clone();
+/// let mut map = WatchedLinearMap::with_callback("sessions", 2, move |name, len| {
+///     assert_eq!((name, len), ("sessions", 3));
+///     counter.fetch_add(1, Ordering::Relaxed);
+/// });
+/// for i in 0..5 {
+///     map.insert(i, ());
+/// }
+/// map.map_mut().retain(|&k, _| k < 2);
+/// map.insert(2, ());
+/// map.insert(3, ());
+/// assert_eq!(map.len(), 4);
+/// assert_eq!(map.exceeded_count(), 2);
+/// assert_eq!(reports.load(Ordering::Relaxed), 1);
+/// ```
+pub struct WatchedLinearMap<K, V> {
+    map: LinearMap<K, V>,
+    name: &'static str,
+    threshold: usize,
+    exceeded: usize,
+    callback: Option<Arc<dyn Fn(&'static str, usize) + Send + Sync>>,
+}
+
+impl<K, V> WatchedLinearMap<K, V> {
+    /// Creates an empty map that counts how often it grows past `threshold` entries. This method
+    /// does not allocate.
+    pub fn new(name: &'static str, threshold: usize) -> Self {
+        WatchedLinearMap { map: LinearMap::new(), name, threshold, exceeded: 0, callback: None }
+    }
+
+    /// Creates an empty map that also calls `callback` with its name and length the first time
+    /// it holds more than `threshold` entries.
+    pub fn with_callback<F>(name: &'static str, threshold: usize, callback: F) -> Self
+    where F: Fn(&'static str, usize) + Send + Sync + 'static {
+        WatchedLinearMap { callback: Some(Arc::new(callback)), ..Self::new(name, threshold) }
+    }
+
+    /// Returns the name passed to the callback.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the length past which the map reports itself.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns how many times the map has grown from at most `threshold` entries to more.
+    pub fn exceeded_count(&self) -> usize {
+        self.exceeded
+    }
+
+    /// Returns true if the map has held more than `threshold` entries at some point.
+    pub fn has_exceeded(&self) -> bool {
+        self.exceeded > 0
+    }
+
+    /// Returns a guard giving mutable access to the underlying map. The threshold is checked
+    /// when the guard is dropped.
+    pub fn map_mut(&mut self) -> WatchedMut<'_, K, V> {
+        let was_over = self.map.len() > self.threshold;
+        WatchedMut { map: self, was_over }
+    }
+
+    /// Consumes the wrapper and returns the underlying map.
+    pub fn into_inner(self) -> LinearMap<K, V> {
+        self.map
+    }
+
+    fn check(&mut self, was_over: bool) {
+        let len = self.map.len();
+        if was_over || len <= self.threshold {
+            return;
+        }
+        self.exceeded += 1;
+        if self.exceeded == 1 {
+            if let Some(ref callback) = self.callback {
+                callback(self.name, len);
+            }
+        }
+    }
+}
+
+impl<K: PartialEq, V> WatchedLinearMap<K, V> {
+    /// Inserts a key-value pair into the map like
+    /// [`LinearMap::insert`](../struct.LinearMap.html#method.insert), reporting the map if this
+    /// takes it past its threshold.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.map_mut().insert(key, value)
+    }
+}
+
+impl<K, V> Deref for WatchedLinearMap<K, V> {
+    type Target = LinearMap<K, V>;
+
+    fn deref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> AsRef<LinearMap<K, V>> for WatchedLinearMap<K, V> {
+    fn as_ref(&self) -> &LinearMap<K, V> {
+        &self.map
+    }
+}
+
+/// Clones the map's entries and callback; the clone has not exceeded its threshold yet.
+impl<K: Clone, V: Clone> Clone for WatchedLinearMap<K, V> {
+    fn clone(&self) -> Self {
+        WatchedLinearMap {
+            map: self.map.clone(),
+            name: self.name,
+            threshold: self.threshold,
+            exceeded: 0,
+            callback: self.callback.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for WatchedLinearMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<K: PartialEq, V> Extend<(K, V)> for WatchedLinearMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, key_values: I) {
+        self.map_mut().extend(key_values);
+    }
+}
+
+/// Compares the maps' entries; their names, thresholds and callbacks are ignored.
+impl<K: PartialEq, V: PartialEq> PartialEq for WatchedLinearMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for WatchedLinearMap<K, V> {}
+
+impl<K, V> IntoIterator for WatchedLinearMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        self.map.into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a WatchedLinearMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.map.iter()
+    }
+}
+
+/// Mutable access to a `WatchedLinearMap`'s entries, which checks the map's threshold when
+/// dropped.
+///
+/// See [`WatchedLinearMap::map_mut`](struct.WatchedLinearMap.html#method.map_mut) for details.
+pub struct WatchedMut<'a, K: 'a, V: 'a> {
+    map: &'a mut WatchedLinearMap<K, V>,
+    was_over: bool,
+}
+
+impl<'a, K, V> Deref for WatchedMut<'a, K, V> {
+    type Target = LinearMap<K, V>;
+
+    fn deref(&self) -> &LinearMap<K, V> {
+        &self.map.map
+    }
+}
+
+impl<'a, K, V> DerefMut for WatchedMut<'a, K, V> {
+    fn deref_mut(&mut self) -> &mut LinearMap<K, V> {
+        &mut self.map.map
+    }
+}
+
+impl<'a, K, V> Drop for WatchedMut<'a, K, V> {
+    fn drop(&mut self) {
+        self.map.check(self.was_over);
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Debug for WatchedMut<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.map.fmt(f)
+    }
+}
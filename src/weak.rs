@@ -0,0 +1,369 @@
+//! Maps holding weak references, which do not keep their referents alive.
+//!
+//! See the [`WeakValueMap`](struct.WeakValueMap.html) and
+//! [`WeakKeyLinearMap`](struct.WeakKeyLinearMap.html) types for details.
+
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
+use std::mem;
+use std::ops::Deref;
+use std::rc::{self, Rc};
+use std::slice;
+use std::sync::{self, Arc};
+
+use super::LinearMap;
+use super::Iter as MapIter;
+
+/// A weak reference that can be upgraded to a strong one, such as `rc::Weak` or `sync::Weak`.
+pub trait WeakRef {
+    /// The strong reference type, such as `Rc<T>` or `Arc<T>`.
+    type Strong;
+
+    /// Creates a weak reference to the referent of `strong`.
+    fn downgrade(strong: &Self::Strong) -> Self;
+
+    /// Returns a strong reference, or `None` if the referent has been dropped.
+    fn upgrade(&self) -> Option<Self::Strong>;
+
+    /// Returns `true` if the referent has been dropped.
+    fn is_dead(&self) -> bool;
+}
+
+impl<T: ?Sized> WeakRef for rc::Weak<T> {
+    type Strong = Rc<T>;
+
+    fn downgrade(strong: &Rc<T>) -> Self {
+        Rc::downgrade(strong)
+    }
+
+    fn upgrade(&self) -> Option<Rc<T>> {
+        rc::Weak::upgrade(self)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.strong_count() == 0
+    }
+}
+
+impl<T: ?Sized> WeakRef for sync::Weak<T> {
+    type Strong = Arc<T>;
+
+    fn downgrade(strong: &Arc<T>) -> Self {
+        Arc::downgrade(strong)
+    }
+
+    fn upgrade(&self) -> Option<Arc<T>> {
+        sync::Weak::upgrade(self)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.strong_count() == 0
+    }
+}
+
+/// A map from keys to weak references, implemented by searching linearly in a vector.
+///
+/// The map does not keep its values alive: values are inserted as strong references (`Rc` or
+/// `Arc`) but stored as the corresponding weak reference `W`. Lookups upgrade on access and treat
+/// entries whose value has been dropped as absent. Such dead entries still occupy space until
+/// they are overwritten, removed, or cleaned up with [`purge`](#method.purge).
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+/// use linear_map::weak::RcWeakValueMap;
+///
+/// let mut registry = RcWeakValueMap::new();
+/// let window = Rc::new("main window");
+/// registry.insert(1, &window);
+/// assert_eq!(registry.get(&1), Some(window.clone()));
+///
+/// drop(window);
+/// assert_eq!(registry.get(&1), None);
+/// assert_eq!(registry.purge(), 1);
+/// ```
+pub struct WeakValueMap<K, W> {
+    map: LinearMap<K, W>,
+}
+
+/// A `WeakValueMap` holding `rc::Weak<V>` values.
+pub type RcWeakValueMap<K, V> = WeakValueMap<K, rc::Weak<V>>;
+
+/// A `WeakValueMap` holding `sync::Weak<V>` values.
+pub type ArcWeakValueMap<K, V> = WeakValueMap<K, sync::Weak<V>>;
+
+impl<K: PartialEq, W: WeakRef> WeakValueMap<K, W> {
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        WeakValueMap { map: LinearMap::new() }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        WeakValueMap { map: LinearMap::with_capacity(capacity) }
+    }
+
+    /// Returns the number of entries whose value is still alive.
+    pub fn len(&self) -> usize {
+        self.map.values().filter(|w| !w.is_dead()).count()
+    }
+
+    /// Returns true if the map contains no entry whose value is still alive.
+    pub fn is_empty(&self) -> bool {
+        self.map.values().all(|w| w.is_dead())
+    }
+
+    /// Clears the map, removing all entries. Keeps the allocated memory for
+    /// reuse.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Removes all entries whose value has been dropped and returns how many were removed.
+    pub fn purge(&mut self) -> usize {
+        let before = self.map.len();
+        self.map.retain(|_, w| !w.is_dead());
+        before - self.map.len()
+    }
+
+    /// Returns an iterator yielding references to the keys of live entries and strong
+    /// references to their values, in arbitrary order.
+    ///
+    /// The iterator's item type is `(&K, W::Strong)`.
+    pub fn iter(&self) -> Iter<'_, K, W> {
+        Iter { iter: self.map.iter() }
+    }
+
+    /// Returns a strong reference to the value whose key is equal to the given key.
+    ///
+    /// Returns `None` if the map contains no such key, or if its value has been dropped.
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<W::Strong> where K: Borrow<Q> {
+        self.map.get(key).and_then(|w| w.upgrade())
+    }
+
+    /// Checks if the map contains a live entry whose key is equal to the given key.
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.map.get(key).is_some_and(|w| !w.is_dead())
+    }
+
+    /// Inserts a weak reference to `value` under the given key.
+    ///
+    /// Returns the previous value for the key if it was still alive.
+    pub fn insert(&mut self, key: K, value: &W::Strong) -> Option<W::Strong> {
+        self.map.insert(key, W::downgrade(value)).and_then(|w| w.upgrade())
+    }
+
+    /// Removes the key in the map that is equal to the given key and returns its value if it
+    /// was still alive.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<W::Strong>
+    where K: Borrow<Q> {
+        self.map.remove(key).and_then(|w| w.upgrade())
+    }
+}
+
+impl<K: PartialEq, W: WeakRef> Default for WeakValueMap<K, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq + Debug, W: WeakRef> Debug for WeakValueMap<K, W> where W::Strong: Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the live entries of a `WeakValueMap`.
+///
+/// See [`WeakValueMap::iter`](struct.WeakValueMap.html#method.iter) for details.
+pub struct Iter<'a, K: 'a, W: 'a> {
+    iter: MapIter<'a, K, W>,
+}
+
+impl<'a, K, W: WeakRef> Iterator for Iter<'a, K, W> {
+    type Item = (&'a K, W::Strong);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.by_ref().filter_map(|(k, w)| w.upgrade().map(|v| (k, v))).next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, K: PartialEq, W: WeakRef> IntoIterator for &'a WeakValueMap<K, W> {
+    type Item = (&'a K, W::Strong);
+    type IntoIter = Iter<'a, K, W>;
+
+    fn into_iter(self) -> Iter<'a, K, W> {
+        self.iter()
+    }
+}
+
+/// A map from weak references to values, implemented by searching linearly in a vector.
+///
+/// The map does not keep its keys alive: keys are inserted as strong references (`Rc` or `Arc`)
+/// but stored as the corresponding weak reference `W`, and lookups take a reference to the
+/// referent itself. Entries whose key has been dropped are skipped by lookups and iteration, and
+/// still occupy space (along with their values) until they are removed with
+/// [`purge`](#method.purge).
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+/// use linear_map::weak::RcWeakKeyLinearMap;
+///
+/// let mut observers = RcWeakKeyLinearMap::new();
+/// let session = Rc::new("session-1".to_string());
+/// observers.insert(&session, 3);
+/// assert_eq!(observers.get("session-1"), Some(&3));
+///
+/// drop(session);
+/// assert_eq!(observers.get("session-1"), None);
+/// assert_eq!(observers.purge(), 1);
+/// ```
+pub struct WeakKeyLinearMap<W, V> {
+    storage: Vec<(W, V)>,
+}
+
+/// The referent of a weak reference, which keys of a `WeakKeyLinearMap` are compared by.
+type Target<W> = <<W as WeakRef>::Strong as Deref>::Target;
+
+/// A `WeakKeyLinearMap` holding `rc::Weak<K>` keys.
+pub type RcWeakKeyLinearMap<K, V> = WeakKeyLinearMap<rc::Weak<K>, V>;
+
+/// A `WeakKeyLinearMap` holding `sync::Weak<K>` keys.
+pub type ArcWeakKeyLinearMap<K, V> = WeakKeyLinearMap<sync::Weak<K>, V>;
+
+impl<W: WeakRef, V> WeakKeyLinearMap<W, V> where W::Strong: Deref {
+    /// Creates an empty map. This method does not allocate.
+    pub fn new() -> Self {
+        WeakKeyLinearMap { storage: Vec::new() }
+    }
+
+    /// Creates an empty map with the given initial capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        WeakKeyLinearMap { storage: Vec::with_capacity(capacity) }
+    }
+
+    /// Returns the number of entries whose key is still alive.
+    pub fn len(&self) -> usize {
+        self.storage.iter().filter(|&(w, _)| !w.is_dead()).count()
+    }
+
+    /// Returns true if the map contains no entry whose key is still alive.
+    pub fn is_empty(&self) -> bool {
+        self.storage.iter().all(|(w, _)| w.is_dead())
+    }
+
+    /// Clears the map, removing all entries. Keeps the allocated memory for
+    /// reuse.
+    pub fn clear(&mut self) {
+        self.storage.clear();
+    }
+
+    /// Removes all entries whose key has been dropped and returns how many were removed.
+    pub fn purge(&mut self) -> usize {
+        let before = self.storage.len();
+        self.storage.retain(|(w, _)| !w.is_dead());
+        before - self.storage.len()
+    }
+
+    /// Returns an iterator yielding strong references to the live keys and references to their
+    /// values, in arbitrary order.
+    ///
+    /// The iterator's item type is `(W::Strong, &V)`.
+    pub fn iter(&self) -> KeyIter<'_, W, V> {
+        KeyIter { iter: self.storage.iter() }
+    }
+
+    fn position<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<usize> where Target<W>: Borrow<Q> {
+        self.storage.iter().position(|(w, _)| w.upgrade().is_some_and(|k| (*k).borrow() == key))
+    }
+
+    /// Returns a reference to the value whose live key is equal to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V> where Target<W>: Borrow<Q> {
+        self.position(key).map(|i| &self.storage[i].1)
+    }
+
+    /// Returns a mutable reference to the value whose live key is equal to the given key.
+    ///
+    /// Returns `None` if the map contains no such key.
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V>
+    where Target<W>: Borrow<Q> {
+        self.position(key).map(move |i| &mut self.storage[i].1)
+    }
+
+    /// Checks if the map contains a live key equal to the given key.
+    pub fn contains_key<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool where Target<W>: Borrow<Q> {
+        self.position(key).is_some()
+    }
+
+    /// Inserts a value under a weak reference to `key`.
+    ///
+    /// Returns `None` if the map contained no live key equal to the given key. Otherwise the
+    /// key's value is replaced with the given value, and the old value is returned; the stored
+    /// key is left unchanged.
+    pub fn insert(&mut self, key: &W::Strong, value: V) -> Option<V> where Target<W>: PartialEq {
+        match self.position(&**key) {
+            Some(i) => Some(mem::replace(&mut self.storage[i].1, value)),
+            None => {
+                self.storage.push((W::downgrade(key), value));
+                None
+            }
+        }
+    }
+
+    /// Removes the live key in the map that is equal to the given key and returns its value.
+    ///
+    /// Returns `None` if the map contained no such key.
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V>
+    where Target<W>: Borrow<Q> {
+        self.position(key).map(|i| self.storage.swap_remove(i).1)
+    }
+}
+
+impl<W: WeakRef, V> Default for WeakKeyLinearMap<W, V> where W::Strong: Deref {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: WeakRef, V: Debug> Debug for WeakKeyLinearMap<W, V> where W::Strong: Deref + Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the live entries of a `WeakKeyLinearMap`.
+///
+/// See [`WeakKeyLinearMap::iter`](struct.WeakKeyLinearMap.html#method.iter) for details.
+pub struct KeyIter<'a, W: 'a, V: 'a> {
+    iter: slice::Iter<'a, (W, V)>,
+}
+
+impl<'a, W: WeakRef, V> Iterator for KeyIter<'a, W, V> {
+    type Item = (W::Strong, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.by_ref().filter_map(|(w, v)| w.upgrade().map(|k| (k, v))).next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, W: WeakRef, V> IntoIterator for &'a WeakKeyLinearMap<W, V> where W::Strong: Deref {
+    type Item = (W::Strong, &'a V);
+    type IntoIter = KeyIter<'a, W, V>;
+
+    fn into_iter(self) -> KeyIter<'a, W, V> {
+        self.iter()
+    }
+}
@@ -0,0 +1,45 @@
+extern crate linear_map;
+
+use linear_map::adaptive::AdaptiveLinearMap;
+
+#[test]
+fn test_switches_to_sorted() {
+    let mut map = AdaptiveLinearMap::with_threshold(3);
+    assert_eq!(map.threshold(), 3);
+    for &k in &[5, 1, 3] {
+        assert_eq!(map.insert(k, k * 2), None);
+    }
+    assert!(!map.is_sorted());
+    assert_eq!(map.insert(3, 0), Some(6));
+    assert!(!map.is_sorted());
+
+    map.insert(2, 4);
+    assert!(map.is_sorted());
+    map.insert(4, 8);
+    map.insert(0, 0);
+    assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+
+    assert_eq!(map.get(&5), Some(&10));
+    assert_eq!(map.get(&6), None);
+    assert_eq!(map.remove(&2), Some(4));
+    assert_eq!(map.remove(&2), None);
+    assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [0, 1, 3, 4, 5]);
+
+    map.retain(|&k, _| k < 2);
+    assert!(map.is_sorted());
+    assert!(map.contains_key(&1));
+    map.clear();
+    assert!(!map.is_sorted());
+}
+
+#[test]
+fn test_eq_ignores_mode() {
+    let small: AdaptiveLinearMap<String, i32> =
+        (0..10).map(|i| (i.to_string(), i)).collect();
+    let mut large = AdaptiveLinearMap::with_threshold(2);
+    large.extend((0..10).rev().map(|i| (i.to_string(), i)));
+    assert!(!small.is_sorted());
+    assert!(large.is_sorted());
+    assert_eq!(small, large);
+    assert_eq!(large.get("7"), Some(&7));
+}
@@ -0,0 +1,30 @@
+extern crate linear_map;
+
+use linear_map::any::LinearAnyMap;
+
+#[derive(Debug, PartialEq)]
+struct Config { verbose: bool }
+
+#[test]
+fn test_insert_get_remove() {
+    let mut map = LinearAnyMap::new();
+    assert_eq!(map.insert(Config { verbose: false }), None);
+    assert_eq!(map.insert(42u32), None);
+    assert_eq!(map.insert(7u32), Some(42));
+    assert_eq!(map.len(), 2);
+
+    map.get_mut::<Config>().unwrap().verbose = true;
+    assert_eq!(map.get::<Config>(), Some(&Config { verbose: true }));
+    assert!(map.contains::<u32>());
+    assert!(!map.contains::<u64>());
+
+    *map.get_or_insert_with(|| 0u64) += 5;
+    *map.get_or_insert_with(|| 100u64) += 5;
+    assert_eq!(map.get::<u64>(), Some(&10));
+
+    assert_eq!(map.remove::<u32>(), Some(7));
+    assert_eq!(map.remove::<u32>(), None);
+    assert_eq!(map.len(), 2);
+    map.clear();
+    assert!(map.is_empty());
+}
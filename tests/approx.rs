@@ -0,0 +1,55 @@
+extern crate linear_map;
+
+use std::time::{Duration, Instant};
+
+use linear_map::{Entry, LinearMap};
+
+#[test]
+fn test_float_keys() {
+    let mut map = LinearMap::new();
+    map.insert(1.0f64, 'a');
+    map.insert(2.0, 'b');
+
+    assert_eq!(map.get_approx(&1.05, 0.1), Some(&'a'));
+    assert_eq!(map.get_approx(&1.5, 0.1), None);
+    assert_eq!(map.get_approx(&1.5, 0.5), Some(&'a'));
+    assert_eq!(map.get_key_value_approx(&1.99, 0.1), Some((&2.0, &'b')));
+    *map.get_approx_mut(&2.01, 0.1).unwrap() = 'B';
+    assert_eq!(map[&2.0], 'B');
+    assert_eq!(map.get_approx(&f64::NAN, 1.0), None);
+}
+
+#[test]
+fn test_entry_approx() {
+    let mut counts = LinearMap::new();
+    for &reading in &[10i32, 11, 9, 20, 25, 19] {
+        *counts.entry_approx(reading, 2).or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[&10], 3);
+    assert_eq!(counts[&20], 2);
+    assert_eq!(counts[&25], 1);
+}
+
+#[test]
+fn test_entry_approx_invalid_epsilon() {
+    let mut map = LinearMap::new();
+    map.insert(1.5, 'a');
+    for &epsilon in &[-1.0, f64::NAN] {
+        assert!(matches!(map.entry_approx(1.5, epsilon), Entry::Occupied(_)));
+        *map.entry_approx(1.5, epsilon).or_insert('z') = 'b';
+        assert_eq!(map.get_approx(&1.5, epsilon), Some(&'b'));
+        assert_eq!(map.get_approx(&1.6, epsilon), None);
+    }
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_time_keys() {
+    let start = Instant::now();
+    let mut events = LinearMap::new();
+    events.insert(start, "start");
+    let later = start + Duration::from_millis(5);
+    assert_eq!(events.get_approx(&later, Duration::from_millis(10)), Some(&"start"));
+    assert_eq!(events.get_approx(&later, Duration::from_millis(1)), None);
+}
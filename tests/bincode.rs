@@ -0,0 +1,51 @@
+#![cfg(feature = "bincode")]
+
+extern crate bincode;
+extern crate linear_map;
+
+use bincode::config;
+use linear_map::LinearMap;
+use linear_map::set::LinearSet;
+
+#[test]
+fn test_map_round_trip() {
+    let mut map = LinearMap::new();
+    map.insert(3u32, "three".to_string());
+    map.insert(1, "one".to_string());
+
+    let bytes = bincode::encode_to_vec(&map, config::standard()).unwrap();
+    let (decoded, len): (LinearMap<u32, String>, _) =
+        bincode::decode_from_slice(&bytes, config::standard()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded, map);
+    assert_eq!(decoded.keys().collect::<Vec<_>>(), [&3, &1]);
+
+    // The layout is that of a sequence of pairs.
+    let pairs: Vec<(u32, String)> =
+        bincode::decode_from_slice(&bytes, config::standard()).unwrap().0;
+    assert_eq!(pairs, [(3, "three".to_string()), (1, "one".to_string())]);
+}
+
+#[test]
+fn test_borrowed_and_set() {
+    let mut map = LinearMap::new();
+    map.insert("k", "v");
+    let bytes = bincode::encode_to_vec(&map, config::standard()).unwrap();
+    let decoded: LinearMap<&str, &str> =
+        bincode::borrow_decode_from_slice(&bytes, config::standard()).unwrap().0;
+    assert_eq!(decoded, map);
+
+    let set: LinearSet<u8> = [5, 2, 9].iter().copied().collect();
+    let bytes = bincode::encode_to_vec(&set, config::standard()).unwrap();
+    let decoded: LinearSet<u8> = bincode::decode_from_slice(&bytes, config::standard()).unwrap().0;
+    assert_eq!(decoded, set);
+}
+
+#[test]
+fn test_truncated_input() {
+    let map: LinearMap<u64, u64> = (0..4).map(|i| (i, i)).collect();
+    let bytes = bincode::encode_to_vec(&map, config::standard()).unwrap();
+    let result: Result<(LinearMap<u64, u64>, _), _> =
+        bincode::decode_from_slice(&bytes[..bytes.len() - 1], config::standard());
+    assert!(result.is_err());
+}
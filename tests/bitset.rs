@@ -0,0 +1,35 @@
+extern crate linear_map;
+
+use linear_map::bitset::BitsetLinearMap;
+
+#[test]
+fn test_insert_remove_get() {
+    let mut map = BitsetLinearMap::new();
+    assert_eq!(map.insert(1u32, 'a'), None);
+    assert_eq!(map.insert(129, 'b'), None); // shares a bit with 1
+    assert_eq!(map.insert(1, 'A'), Some('a'));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&1), Some(&'A'));
+    assert_eq!(map.get(&129), Some(&'b'));
+    assert_eq!(map.get(&257), None);
+    assert_eq!(map.get(&2), None);
+
+    assert_eq!(map.remove(&1), Some('A'));
+    assert_eq!(map.get(&129), Some(&'b'));
+    assert_eq!(map.remove(&1), None);
+    assert_eq!(map.remove(&129), Some('b'));
+    assert!(map.is_empty());
+    assert!(!map.contains_key(&129));
+}
+
+#[test]
+fn test_retain_and_eq() {
+    let mut a: BitsetLinearMap<u8, u8> = (0..200).map(|i| (i, i)).collect();
+    a.retain(|&k, _| k % 3 == 0);
+    let b: BitsetLinearMap<u8, u8> = (0..200).rev().filter(|i| i % 3 == 0).map(|i| (i, i)).collect();
+    assert_eq!(a, b);
+    assert!(a.contains_key(&129));
+    assert!(!a.contains_key(&130));
+    a.clear();
+    assert!(!a.contains_key(&0));
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "bumpalo")]
+
+extern crate linear_map;
+
+use linear_map::bump::{Bump, BumpLinearMap};
+
+#[test]
+fn test_insert_remove_get() {
+    let bump = Bump::new();
+    let mut map = BumpLinearMap::new_in(&bump);
+    assert!(map.is_empty());
+    assert_eq!(map.insert(1, 10), None);
+    assert_eq!(map.insert(2, 20), None);
+    assert_eq!(map.insert(1, 11), Some(10));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&1), Some(&11));
+    *map.get_mut(&2).unwrap() += 1;
+    assert_eq!(map.get(&2), Some(&21));
+    assert_eq!(map.remove(&1), Some(11));
+    assert_eq!(map.remove(&1), None);
+    assert!(!map.contains_key(&1));
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(&2, &21)]);
+}
+
+#[test]
+fn test_retain_and_eq() {
+    let bump = Bump::new();
+    let mut a = BumpLinearMap::with_capacity_in(10, &bump);
+    assert!(a.capacity() >= 10);
+    a.extend((0..10).map(|i| (i, i * 10)));
+    a.retain(|&k, _| k % 2 == 0);
+    let mut b = BumpLinearMap::new_in(&bump);
+    b.extend((0..10).rev().filter(|i| i % 2 == 0).map(|i| (i, i * 10)));
+    assert_eq!(a, b);
+    a.clear();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn test_per_frame_maps() {
+    let mut bump = Bump::new();
+    for frame in 0..3 {
+        {
+            let mut map = BumpLinearMap::with_capacity_in(4, &bump);
+            map.extend((0..4).map(|i| (i, frame)));
+            assert_eq!(map.values().sum::<i32>(), 4 * frame);
+        }
+        // Dropping the map gives nothing back to the arena; resetting it reclaims everything.
+        assert!(bump.allocated_bytes() > 0);
+        bump.reset();
+    }
+}
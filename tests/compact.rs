@@ -0,0 +1,37 @@
+extern crate linear_map;
+
+use linear_map::compact::CompactLinearMap;
+
+#[test]
+fn test_insert_remove() {
+    let mut map = CompactLinearMap::with_capacity(4, 16);
+    assert_eq!(map.insert("alpha", 1), None);
+    assert_eq!(map.insert("beta", 2), None);
+    assert_eq!(map.insert("gamma", 3), None);
+    assert_eq!(map.insert("beta", 20), Some(2));
+    assert_eq!(map.key_bytes(), 14);
+
+    assert_eq!(map.remove("alpha"), Some(1));
+    assert_eq!(map.remove("alpha"), None);
+    assert_eq!(map.key_bytes(), 9);
+    assert_eq!(map.get("beta"), Some(&20));
+    assert_eq!(map.get("gamma"), Some(&3));
+    assert!(!map.contains_key("gam"));
+
+    for (_, v) in map.iter_mut() {
+        *v *= 10;
+    }
+    *map.get_mut("gamma").unwrap() += 1;
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort();
+    assert_eq!(entries, [("beta", &200), ("gamma", &31)]);
+    assert_eq!(map.values().sum::<i32>(), 231);
+}
+
+#[test]
+fn test_collect_and_eq() {
+    let a: CompactLinearMap<u8> = vec![("x", 1), ("yy", 2)].into_iter().collect();
+    let b: CompactLinearMap<u8> = vec![(String::from("yy"), 2), (String::from("x"), 1)].into_iter().collect();
+    assert_eq!(a, b);
+    assert_eq!(format!("{:?}", a), r#"{"x": 1, "yy": 2}"#);
+}
@@ -0,0 +1,66 @@
+extern crate linear_map;
+
+use linear_map::compare::{EqCompare, KeyCompare, LinearMapBy};
+
+#[derive(Default)]
+struct Modulo10;
+
+impl KeyCompare<u32> for Modulo10 {
+    fn eq(&self, a: &u32, b: &u32) -> bool {
+        a % 10 == b % 10
+    }
+}
+
+#[test]
+fn test_custom_comparator() {
+    let mut map: LinearMapBy<u32, &str, Modulo10> = LinearMapBy::new();
+    assert_eq!(map.insert(1, "one"), None);
+    assert_eq!(map.insert(2, "two"), None);
+    assert_eq!(map.insert(11, "eleven"), Some("one"));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&21), Some(&"eleven"));
+    assert!(map.contains_key(&32));
+    assert!(!map.contains_key(&3));
+    *map.get_mut(&12).unwrap() = "twelve";
+    assert_eq!(map.remove(&42), Some("twelve"));
+    assert_eq!(map.remove(&42), None);
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(&1, &"eleven")]);
+}
+
+#[test]
+fn test_closure_comparator() {
+    let mut map = LinearMapBy::with_capacity_and_comparator(4, |a: &i32, b: &i32| a.abs() == b.abs());
+    assert!(map.capacity() >= 4);
+    map.extend(vec![(1, 'a'), (-1, 'b'), (2, 'c')]);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&-1], 'b');
+    map.retain(|&k, _| k != 2);
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), [(1, 'b')]);
+}
+
+#[test]
+fn test_eq_compare() {
+    let a: LinearMapBy<String, i32, EqCompare> =
+        vec![("a".to_string(), 1), ("b".to_string(), 2)].into_iter().collect();
+    let b: LinearMapBy<String, i32, EqCompare> =
+        vec![("b".to_string(), 2), ("a".to_string(), 1)].into_iter().collect();
+    assert_eq!(a, b);
+    assert_eq!(a.get("a"), Some(&1));
+}
+
+#[test]
+fn test_case_insensitive() {
+    use linear_map::compare::{AsciiCaseInsensitiveMap, UnicodeCaseInsensitiveMap};
+
+    let mut ascii = AsciiCaseInsensitiveMap::new();
+    ascii.insert("Accept", 1);
+    assert_eq!(ascii.insert("ACCEPT", 2), Some(1));
+    assert_eq!(ascii.get("accept"), Some(&2));
+    assert_eq!(ascii.get("ÀCCEPT"), None);
+    assert_eq!(ascii.keys().collect::<Vec<_>>(), [&"Accept"]);
+
+    let mut unicode = UnicodeCaseInsensitiveMap::new();
+    unicode.insert("Größe".to_string(), 1);
+    assert_eq!(unicode.get("GRÖßE"), Some(&1));
+    assert_eq!(unicode.get("grösse"), None);
+}
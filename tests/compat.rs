@@ -0,0 +1,22 @@
+#![cfg(feature = "hashmap-compat")]
+
+extern crate linear_map;
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+use linear_map::LinearMap;
+use linear_map::compat::DefaultHashBuilder;
+
+#[test]
+fn test_hasher_constructors() {
+    let mut map: LinearMap<u8, u8> = LinearMap::with_hasher(RandomState::new());
+    map.insert(1, 1);
+    assert_eq!(map.len(), 1);
+
+    let map: LinearMap<u8, u8> = LinearMap::with_capacity_and_hasher(8, DefaultHashBuilder);
+    assert!(map.capacity() >= 8);
+
+    let builder = *map.hasher();
+    assert_eq!(builder.hash_one(7u32), builder.hash_one(7u32));
+}
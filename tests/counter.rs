@@ -0,0 +1,43 @@
+extern crate linear_map;
+
+use linear_map::counter::LinearCounter;
+
+#[test]
+fn test_add_sub_count() {
+    let mut c = LinearCounter::new();
+    assert_eq!(c.increment('a'), 1);
+    assert_eq!(c.increment('a'), 2);
+    assert_eq!(c.increment_by('b', 5), 5);
+    assert_eq!(c.increment_by('z', 0), 0);
+    assert_eq!(c.len(), 2);
+    assert_eq!(c.total(), 7);
+
+    assert_eq!(c.decrement(&'a'), 1);
+    assert_eq!(c.decrement(&'a'), 0);
+    assert!(!c.keys().any(|&k| k == 'a'));
+    assert_eq!(c.decrement(&'a'), 0);
+    assert_eq!(c.decrement_by(&'b', 10), 0);
+    assert!(c.is_empty());
+}
+
+#[test]
+fn test_most_common() {
+    let c: LinearCounter<char> = "mississippi".chars().collect();
+    let top = c.most_common(2);
+    assert_eq!(top.len(), 2);
+    assert!(top.iter().all(|&(_, n)| n == 4));
+    assert_eq!(c.most_common(10).last(), Some(&(&'m', 1)));
+}
+
+#[test]
+fn test_arithmetic() {
+    let a: LinearCounter<&str> = vec!["x", "x", "y"].into_iter().collect();
+    let b: LinearCounter<&str> = vec!["x", "y", "y", "z"].into_iter().collect();
+
+    let sum = a.clone() + b.clone();
+    assert_eq!((sum.count("x"), sum.count("y"), sum.count("z")), (3, 3, 1));
+
+    let diff = a - b;
+    assert_eq!(diff.len(), 1);
+    assert_eq!(diff.count("x"), 1);
+}
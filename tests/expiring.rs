@@ -0,0 +1,64 @@
+extern crate linear_map;
+
+use std::cell::Cell;
+
+use linear_map::expiring::ExpiringLinearMap;
+
+#[test]
+fn test_ttl() {
+    let now = Cell::new(0u64);
+    let mut map = ExpiringLinearMap::with_clock(|| now.get());
+    assert_eq!(map.insert_with_ttl("a", 1, 10), None);
+    assert_eq!(map.insert_until("b", 2, 5), None);
+    assert_eq!(map.insert("c", 3), None);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.expiry("a"), Some(Some(10)));
+    assert_eq!(map.expiry("c"), Some(None));
+
+    now.set(5);
+    assert_eq!(map.get("b"), None);
+    assert!(!map.contains_key("b"));
+    assert_eq!(map.get("a"), Some(&1));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.expiry("b"), None);
+
+    // an expired value is not handed back when overwritten.
+    assert_eq!(map.insert_with_ttl("b", 20, 10), None);
+    assert_eq!(map.get("b"), Some(&20));
+
+    now.set(10);
+    assert_eq!(map.get_mut("a"), None);
+    assert_eq!(map.remove("b"), Some(20));
+    let mut live: Vec<_> = map.iter().collect();
+    live.sort();
+    assert_eq!(live, [(&"c", &3)]);
+}
+
+#[test]
+fn test_purge_expired() {
+    let now = Cell::new(0u32);
+    let mut map = ExpiringLinearMap::with_clock(|| now.get());
+    for i in 0..10 {
+        map.insert_with_ttl(i, i, i + 1);
+    }
+    now.set(5);
+    assert_eq!(map.purge_expired(), 5);
+    assert_eq!(map.purge_expired(), 0);
+    assert_eq!(map.len(), 5);
+    *map.get_mut(&9).unwrap() = 90;
+    assert_eq!(map.get(&9), Some(&90));
+    map.clear();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_system_clock() {
+    use std::time::Duration;
+
+    let mut map = ExpiringLinearMap::new();
+    map.insert_with_ttl("long", 1, Duration::from_secs(3600));
+    map.insert_with_ttl("gone", 2, Duration::from_secs(0));
+    assert_eq!(map.get("long"), Some(&1));
+    assert_eq!(map.get("gone"), None);
+    assert_eq!(format!("{:?}", map), r#"{"long": 1}"#);
+}
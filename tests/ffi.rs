@@ -0,0 +1,59 @@
+#![cfg(feature = "ffi")]
+
+extern crate linear_map;
+
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use linear_map::ffi::*;
+
+#[test]
+fn test_u64_map() {
+    unsafe {
+        let map = linear_map_u64_new();
+        let mut out = 0;
+        assert!(!linear_map_u64_insert(map, 1, 10, &mut out));
+        assert!(!linear_map_u64_insert(map, 2, 20, ptr::null_mut()));
+        assert!(linear_map_u64_insert(map, 1, 11, &mut out));
+        assert_eq!(out, 10);
+        assert_eq!(linear_map_u64_len(map), 2);
+
+        assert!(linear_map_u64_get(map, 2, &mut out));
+        assert_eq!(out, 20);
+        assert!(!linear_map_u64_get(map, 3, &mut out));
+        assert!(linear_map_u64_remove(map, 1, &mut out));
+        assert_eq!(out, 11);
+        assert!(!linear_map_u64_remove(map, 1, ptr::null_mut()));
+
+        linear_map_u64_clear(map);
+        assert_eq!(linear_map_u64_len(map), 0);
+        linear_map_u64_free(map);
+        linear_map_u64_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn test_cstr_map() {
+    let (lang, region, en, fr) = (c("lang"), c("region"), c("en"), c("fr"));
+    unsafe {
+        let map = linear_map_cstr_new();
+        assert!(!linear_map_cstr_insert(map, lang.as_ptr(), en.as_ptr()));
+        assert!(linear_map_cstr_insert(map, lang.as_ptr(), fr.as_ptr()));
+        assert_eq!(linear_map_cstr_len(map), 1);
+
+        let value = linear_map_cstr_get(map, lang.as_ptr());
+        assert_eq!(CStr::from_ptr(value), &*fr);
+        assert!(linear_map_cstr_get(map, region.as_ptr()).is_null());
+
+        assert!(linear_map_cstr_remove(map, lang.as_ptr()));
+        assert!(!linear_map_cstr_remove(map, lang.as_ptr()));
+        linear_map_cstr_insert(map, region.as_ptr(), en.as_ptr());
+        linear_map_cstr_clear(map);
+        assert_eq!(linear_map_cstr_len(map), 0);
+        linear_map_cstr_free(map);
+    }
+}
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
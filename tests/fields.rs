@@ -0,0 +1,56 @@
+#![cfg(feature = "derive")]
+
+extern crate linear_map;
+use linear_map::LinearMap;
+use linear_map::errors::FieldError;
+use linear_map::fields::{FieldValue, ToLinearMap};
+
+#[derive(Debug, PartialEq, ToLinearMap)]
+struct Config {
+    name: String,
+    port: u16,
+    offset: i32,
+    ratio: f64,
+    verbose: bool,
+}
+
+fn sample() -> Config {
+    Config { name: "server".to_string(), port: 8080, offset: -2, ratio: 0.5, verbose: true }
+}
+
+#[test]
+fn test_to_linear_map() {
+    let map = sample().to_linear_map();
+    let keys: Vec<_> = map.keys().cloned().collect();
+    assert_eq!(keys, ["name", "port", "offset", "ratio", "verbose"]);
+    assert_eq!(map["name"], FieldValue::Str("server".to_string()));
+    assert_eq!(map["port"], FieldValue::UInt(8080));
+    assert_eq!(map["offset"], FieldValue::Int(-2));
+    assert_eq!(map["ratio"], FieldValue::Float(0.5));
+    assert_eq!(map["verbose"], FieldValue::Bool(true));
+}
+
+#[test]
+fn test_from_linear_map() {
+    let mut map: LinearMap<&str, _> = sample().to_linear_map();
+    map.insert("unknown", FieldValue::Bool(false));
+    assert_eq!(Config::from_linear_map(map.clone()), Ok(sample()));
+
+    map.insert("port", FieldValue::UInt(70000));
+    assert_eq!(Config::from_linear_map(map.clone()), Err(FieldError::Invalid("port")));
+
+    map.remove("name");
+    assert_eq!(Config::from_linear_map(map), Err(FieldError::Missing("name")));
+}
+
+#[test]
+fn test_raw_identifier() {
+    #[derive(Debug, PartialEq, ToLinearMap)]
+    struct Token {
+        r#type: String,
+    }
+
+    let map = Token { r#type: "word".to_string() }.to_linear_map();
+    assert_eq!(map["type"], FieldValue::Str("word".to_string()));
+    assert_eq!(Token::from_linear_map(map), Ok(Token { r#type: "word".to_string() }));
+}
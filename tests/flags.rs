@@ -0,0 +1,52 @@
+extern crate linear_map;
+
+use linear_map::flags::LinearFlagMap;
+
+#[test]
+fn test_insert_remove_get() {
+    let mut map = LinearFlagMap::new();
+    assert_eq!(map.insert("a", true), None);
+    assert_eq!(map.insert("b", false), None);
+    assert_eq!(map.insert("a", false), Some(true));
+    assert_eq!(map.get("a"), Some(false));
+    assert_eq!(map.set("b", true), Some(false));
+    assert_eq!(map.set("c", true), None);
+    assert!(!map.contains_key("c"));
+
+    assert_eq!(map.remove("a"), Some(false));
+    assert_eq!(map.get("b"), Some(true));
+    assert_eq!(map.remove("a"), None);
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_all_any_across_words() {
+    let mut map: LinearFlagMap<u32> = (0..130).map(|i| (i, true)).collect();
+    assert!(map.all());
+    assert_eq!(map.count_true(), 130);
+
+    map.set(&129, false);
+    assert!(!map.all());
+    assert!(map.any());
+
+    // Removing an entry moves the last one's bit into its place.
+    assert_eq!(map.remove(&3), Some(true));
+    assert_eq!(map.get(&129), Some(false));
+    assert_eq!(map.count_true(), 128);
+
+    map.retain(|_, v| !v);
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(&129, false)]);
+    assert!(!map.any());
+    map.clear();
+    assert!(map.all());
+}
+
+#[test]
+fn test_iter_eq() {
+    let a: LinearFlagMap<u8> = (0..70).map(|i| (i, i % 3 == 0)).collect();
+    let b: LinearFlagMap<u8> = (0..70).rev().map(|i| (i, i % 3 == 0)).collect();
+    assert_eq!(a, b);
+    assert_eq!(a.iter().filter(|&(_, v)| v).count(), a.count_true());
+    assert_eq!(a.clone().into_iter().next_back(), Some((69, true)));
+    assert_eq!(format!("{:?}", a.iter().take(2).collect::<Vec<_>>()), "[(0, true), (1, false)]");
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "ordered-float")]
+
+extern crate linear_map;
+use linear_map::float::{F32Map, F64Map, OrderedFloat};
+
+#[test]
+fn test_float_keys() {
+    let mut map = F64Map::new();
+    assert_eq!(map.insert_float(1.5, 'a'), None);
+    assert_eq!(map.insert_float(f64::NAN, 'b'), None);
+    assert_eq!(map.insert_float(f64::NAN, 'c'), Some('b'));
+    assert_eq!(map.len(), 2);
+
+    assert!(map.contains_float(1.5));
+    assert!(!map.contains_float(2.5));
+    assert_eq!(map.get_float(f64::NAN), Some(&'c'));
+    *map.get_float_mut(1.5).unwrap() = 'z';
+    assert_eq!(map[&OrderedFloat(1.5)], 'z');
+
+    assert_eq!(map.remove_float(f64::NAN), Some('c'));
+    assert_eq!(map.remove_float(f64::NAN), None);
+}
+
+#[test]
+fn test_signed_zero() {
+    let mut map = F32Map::new();
+    map.insert_float(0.0, "zero");
+    assert_eq!(map.get_float(-0.0), Some(&"zero"));
+}
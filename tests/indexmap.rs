@@ -0,0 +1,47 @@
+#![cfg(feature = "indexmap")]
+
+extern crate indexmap;
+use indexmap::{IndexMap, IndexSet};
+
+extern crate linear_map;
+use linear_map::LinearMap;
+use linear_map::set::LinearSet;
+
+#[test]
+fn test_map_round_trip() {
+    let mut map = LinearMap::new();
+    map.insert("c", 3);
+    map.insert("a", 1);
+    map.insert("b", 2);
+
+    let index_map: IndexMap<_, _> = map.clone().into();
+    assert_eq!(index_map.keys().cloned().collect::<Vec<_>>(), ["c", "a", "b"]);
+    assert!(map.eq_indexmap(&index_map));
+
+    let back: LinearMap<_, _> = index_map.into();
+    assert_eq!(back.keys().cloned().collect::<Vec<_>>(), ["c", "a", "b"]);
+}
+
+#[test]
+fn test_map_eq_ignores_order() {
+    let map: LinearMap<_, _> = vec![(1, 'a'), (2, 'b')].into_iter().collect();
+    let mut index_map: IndexMap<_, _> = vec![(2, 'b'), (1, 'a')].into_iter().collect();
+    assert!(map.eq_indexmap(&index_map));
+
+    index_map.insert(1, 'z');
+    assert!(!map.eq_indexmap(&index_map));
+    index_map.insert(3, 'c');
+    assert!(!map.eq_indexmap(&index_map));
+}
+
+#[test]
+fn test_set_round_trip() {
+    let set: LinearSet<_> = vec![5, 1, 3].into_iter().collect();
+
+    let index_set: IndexSet<_> = set.clone().into();
+    assert_eq!(index_set.iter().cloned().collect::<Vec<_>>(), [5, 1, 3]);
+    assert!(set.eq_indexset(&index_set));
+
+    let back: LinearSet<_> = index_set.into();
+    assert_eq!(back.iter().cloned().collect::<Vec<_>>(), [5, 1, 3]);
+}
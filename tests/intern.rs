@@ -0,0 +1,37 @@
+#![cfg(feature = "lasso")]
+
+extern crate linear_map;
+use linear_map::intern::{InternedLinearMap, Rodeo};
+
+#[test]
+fn test_str_lookups() {
+    let mut interner = Rodeo::default();
+    let mut map = InternedLinearMap::new();
+    assert_eq!(map.insert_str(&mut interner, "a", 1), None);
+    assert_eq!(map.insert_str(&mut interner, "b", 2), None);
+    assert_eq!(map.insert_str(&mut interner, "a", 3), Some(1));
+    assert_eq!(map.len(), 2);
+
+    interner.get_or_intern("unused");
+    assert!(map.contains_str(&interner, "b"));
+    assert!(!map.contains_str(&interner, "unused"));
+    assert!(!map.contains_str(&interner, "never interned"));
+    *map.get_str_mut(&interner, "b").unwrap() += 10;
+    assert_eq!(map[&interner.get("b").unwrap()], 12);
+
+    let entries: Vec<_> = map.iter_str(&interner).collect();
+    assert_eq!(entries, [("a", &3), ("b", &12)]);
+
+    let resolver = interner.into_resolver();
+    assert_eq!(map.iter_str(&resolver).next_back(), Some(("b", &12)));
+}
+
+#[test]
+fn test_remove_str() {
+    let mut interner = Rodeo::default();
+    let mut map = InternedLinearMap::new();
+    map.insert_str(&mut interner, "x", 'x');
+    assert_eq!(map.remove_str(&interner, "y"), None);
+    assert_eq!(map.remove_str(&interner, "x"), Some('x'));
+    assert_eq!(map.get_str(&interner, "x"), None);
+}
@@ -0,0 +1,48 @@
+extern crate linear_map;
+
+use linear_map::interval::LinearIntervalMap;
+
+#[test]
+fn test_point_queries() {
+    let mut map = LinearIntervalMap::new();
+    assert_eq!(map.insert(0..10, 'a'), None);
+    assert_eq!(map.insert(10..20, 'b'), None);
+    assert_eq!(map.insert(15..15, 'e'), None);
+    assert_eq!(map.insert(10..20, 'B'), Some('b'));
+    assert_eq!(map.len(), 3);
+
+    assert_eq!(map.get_point(&0), Some(&'a'));
+    assert_eq!(map.get_point(&9), Some(&'a'));
+    assert_eq!(map.get_point(&10), Some(&'B'));
+    assert_eq!(map.get_point(&15), Some(&'B'));
+    assert_eq!(map.get_point(&20), None);
+    assert_eq!(map.get(&(0..10)), Some(&'a'));
+
+    *map.get_point_mut(&5).unwrap() = 'A';
+    assert_eq!(map.get(&(0..10)), Some(&'A'));
+}
+
+#[test]
+fn test_overlaps() {
+    let map: LinearIntervalMap<u32, u32> =
+        vec![(0..10, 1), (5..15, 2), (20..30, 3), (12..12, 4)].into_iter().collect();
+
+    let mut containing: Vec<_> = map.containing(&7).map(|(_, &v)| v).collect();
+    containing.sort();
+    assert_eq!(containing, [1, 2]);
+
+    let mut overlapping: Vec<_> = map.overlapping(&(9..21)).map(|(_, &v)| v).collect();
+    overlapping.sort();
+    assert_eq!(overlapping, [1, 2, 3]);
+    assert_eq!(map.overlapping(&(15..20)).count(), 0);
+    assert_eq!(map.overlapping(&(25..25)).count(), 0);
+}
+
+#[test]
+fn test_remove() {
+    let mut map: LinearIntervalMap<i32, i32> =
+        vec![(0..10, 1), (5..15, 2), (20..30, 3)].into_iter().collect();
+    assert_eq!(map.remove_point(&7), 2);
+    assert_eq!(map.remove(&(20..30)), Some(3));
+    assert!(map.is_empty());
+}
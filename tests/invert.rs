@@ -0,0 +1,34 @@
+extern crate linear_map;
+
+use linear_map::LinearMap;
+use linear_map::errors::DuplicateKeyError;
+use linear_map::invert::{Group, KeepFirst, KeepLast, Reject};
+
+fn colours() -> LinearMap<&'static str, u32> {
+    let mut map = LinearMap::new();
+    map.insert("red", 0xff0000);
+    map.insert("crimson", 0xdc143c);
+    map.insert("scarlet", 0xff0000);
+    map
+}
+
+#[test]
+fn test_invert_policies() {
+    assert_eq!(colours().invert(Reject), Err(DuplicateKeyError { key: 0xff0000, value: "scarlet" }));
+    assert_eq!(colours().invert(KeepFirst)[&0xff0000], "red");
+    assert_eq!(colours().invert(KeepLast)[&0xff0000], "scarlet");
+
+    let grouped = colours().invert(Group);
+    assert_eq!(grouped.len(), 2);
+    assert_eq!(grouped[&0xff0000], ["red", "scarlet"]);
+    assert_eq!(grouped[&0xdc143c], ["crimson"]);
+}
+
+#[test]
+fn test_invert_unique() {
+    let mut map = colours();
+    map.remove("scarlet");
+    let inverse = map.invert(Reject).unwrap();
+    assert_eq!(inverse[&0xdc143c], "crimson");
+    assert_eq!(inverse.len(), 2);
+}
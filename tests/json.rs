@@ -0,0 +1,32 @@
+#![cfg(feature = "serde_json")]
+
+extern crate linear_map;
+extern crate serde_json;
+
+use std::convert::TryFrom;
+
+use linear_map::LinearMap;
+use serde_json::{json, Map, Value};
+
+#[test]
+fn test_round_trip() {
+    let mut map = LinearMap::new();
+    map.insert("name".to_string(), json!("linear-map"));
+    map.insert("tags".to_string(), json!(["small", "map"]));
+
+    let object: Map<String, Value> = map.clone().into();
+    assert_eq!(object.len(), 2);
+    assert_eq!(object["tags"], json!(["small", "map"]));
+
+    let back: LinearMap<String, Value> = object.into();
+    assert_eq!(back, map);
+
+    let value: Value = map.clone().into();
+    assert_eq!(value, json!({ "name": "linear-map", "tags": ["small", "map"] }));
+    assert_eq!(LinearMap::try_from(value), Ok(map));
+}
+
+#[test]
+fn test_not_an_object() {
+    assert_eq!(LinearMap::try_from(json!([1, 2])), Err(json!([1, 2])));
+}
@@ -0,0 +1,37 @@
+extern crate linear_map;
+
+use linear_map::LinearMap;
+use linear_map::errors::OverflowError;
+
+fn shard<V: Clone>(entries: &[(&'static str, V)]) -> LinearMap<&'static str, V> {
+    entries.iter().cloned().collect()
+}
+
+#[test]
+fn test_merge_with_op() {
+    let mut a = shard(&[("x", 1.0f64), ("y", 2.0)]);
+    a.merge_with_op(shard(&[("y", 5.0), ("z", 7.0)]), |_, v, w| *v = v.max(w));
+    assert_eq!(a, shard(&[("x", 1.0), ("y", 5.0), ("z", 7.0)]));
+}
+
+#[test]
+fn test_merge_add() {
+    let mut a = shard(&[("hits", 200u8), ("misses", 3)]);
+    a.merge_add(shard(&[("hits", 50), ("errors", 1)]));
+    assert_eq!(a, shard(&[("hits", 250), ("misses", 3), ("errors", 1)]));
+
+    a.merge_add_saturating(shard(&[("hits", 50)]));
+    assert_eq!(a["hits"], 255);
+}
+
+#[test]
+fn test_merge_add_checked() {
+    let mut a = shard(&[("hits", 200u8), ("misses", 3)]);
+    let before = a.clone();
+    let err = a.merge_add_checked(shard(&[("misses", 1), ("hits", 100), ("new", 1)])).unwrap_err();
+    assert_eq!(err, OverflowError { key: "hits", value: 100 });
+    assert_eq!(a, before);
+
+    assert_eq!(a.merge_add_checked(shard(&[("misses", 1), ("new", 1)])), Ok(()));
+    assert_eq!(a, shard(&[("hits", 200), ("misses", 4), ("new", 1)]));
+}
@@ -0,0 +1,58 @@
+extern crate linear_map;
+
+use linear_map::multimap::LinearMultiMap;
+
+#[test]
+fn test_groups() {
+    let mut map: LinearMultiMap<_, _> = vec![("a", 1), ("b", 2), ("a", 3)].into_iter().collect();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.values_len(), 3);
+    assert_eq!(map.get_all("a"), [1, 3]);
+    assert_eq!(map.get_all("z"), [] as [i32; 0]);
+
+    map.get_all_mut("b")[0] = 20;
+    let groups: Vec<_> = map.iter_groups().collect();
+    assert_eq!(groups, [(&"a", &[1, 3][..]), (&"b", &[20][..])]);
+}
+
+#[test]
+fn test_remove_one() {
+    let mut map = LinearMultiMap::new();
+    map.insert('x', 1);
+    map.insert('x', 2);
+    map.insert('x', 1);
+
+    assert!(map.remove_one(&'x', &1));
+    assert_eq!(map.get_all(&'x'), [2, 1]);
+    assert!(!map.remove_one(&'x', &5));
+    assert!(!map.remove_one(&'y', &1));
+    assert!(map.remove_one(&'x', &2));
+    assert!(map.remove_one(&'x', &1));
+    assert!(!map.contains_key(&'x'));
+}
+
+#[test]
+fn test_retain_values() {
+    let mut map: LinearMultiMap<_, _> = (0..10).map(|i| (i % 3, i)).collect();
+    map.retain_values(|&k, v| {
+        *v *= 10;
+        k != 1 && *v < 60
+    });
+    assert_eq!(map.get_all(&0), [0, 30]);
+    assert!(!map.contains_key(&1));
+    assert_eq!(map.get_all(&2), [20, 50]);
+}
+
+#[test]
+fn test_group_entry() {
+    let mut map = LinearMultiMap::new();
+    assert_eq!(map.group("a").values(), [] as [i32; 0]);
+    assert_eq!(map.group("a").push(1), [1]);
+    assert_eq!(map.group("a").extend(vec![2, 3]), [1, 2, 3]);
+    assert_eq!(map.group("a").values(), [1, 2, 3]);
+
+    assert!(map.group("b").extend(vec![]).is_empty());
+    assert!(!map.contains_key("b"));
+    assert_eq!(map.remove("a"), Some(vec![1, 2, 3]));
+    assert!(map.is_empty());
+}
@@ -0,0 +1,29 @@
+extern crate linear_map;
+
+use linear_map::LinearMap;
+use linear_map::mutable_keys::MutableKeys;
+
+#[test]
+fn test_iter_full_mut() {
+    let mut map: LinearMap<(u32, String), u32> = LinearMap::new();
+    map.insert((1, "one".to_string()), 10);
+    map.insert((2, "two".to_string()), 20);
+    for (k, v) in map.iter_full_mut() {
+        k.1.push('!');
+        *v += 1;
+    }
+    assert_eq!(map.get(&(1, "one!".to_string())), Some(&11));
+    assert_eq!(map.iter_full_mut().len(), 2);
+}
+
+#[test]
+fn test_modify_keys() {
+    let mut map: LinearMap<i32, char> = (0..6).zip("abcdef".chars()).collect();
+    assert!(map.modify_keys(|k, _| *k += 100).is_empty());
+    assert_eq!(map.len(), 6);
+
+    let mut removed = map.modify_keys(|k, _| *k %= 3);
+    removed.sort();
+    assert_eq!(removed, [(0, 'f'), (1, 'd'), (2, 'e')]);
+    assert_eq!(map.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>(), [(1, 'a'), (2, 'b'), (0, 'c')]);
+}
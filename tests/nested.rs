@@ -0,0 +1,37 @@
+extern crate linear_map;
+
+use linear_map::LinearMap;
+
+#[test]
+fn test_nested_insert_get_remove() {
+    let mut map: LinearMap<&str, LinearMap<u32, char>> = LinearMap::new();
+    assert_eq!(map.insert_nested("a", 1, 'x'), None);
+    assert_eq!(map.insert_nested("a", 2, 'y'), None);
+    assert_eq!(map.insert_nested("a", 1, 'z'), Some('x'));
+    assert_eq!(map.insert_nested("b", 1, 'w'), None);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.nested_len(), 3);
+
+    assert_eq!(map.get_nested("a", &1), Some(&'z'));
+    assert_eq!(map.get_nested("a", &3), None);
+    assert_eq!(map.get_nested("c", &1), None);
+    *map.get_nested_mut("b", &1).unwrap() = 'v';
+    assert_eq!(map["b"][&1], 'v');
+
+    assert_eq!(map.remove_nested("b", &1), Some('v'));
+    assert!(!map.contains_key("b"));
+    assert_eq!(map.remove_nested("a", &1), Some('z'));
+    assert!(map.contains_key("a"));
+    assert_eq!(map.remove_nested("a", &1), None);
+}
+
+#[test]
+fn test_entry2() {
+    let mut counts = LinearMap::new();
+    for (x, y) in [(0, 0), (0, 1), (0, 0), (1, 0)] {
+        *counts.entry2(x, y).or_insert(0) += 1;
+    }
+    assert_eq!(counts.get_nested(&0, &0), Some(&2));
+    assert_eq!(counts.get_nested(&1, &0), Some(&1));
+    assert_eq!(counts.nested_len(), 3);
+}
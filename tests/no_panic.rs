@@ -0,0 +1,50 @@
+#![cfg(feature = "no-panic")]
+
+extern crate linear_map;
+
+use linear_map::LinearMap;
+use linear_map::no_panic::NoPanicLinearMap;
+
+#[test]
+fn test_insert_get_remove() {
+    let mut map = NoPanicLinearMap::try_with_capacity(2).unwrap();
+    assert!(map.as_ref().capacity() >= 2);
+    assert_eq!(map.insert(1, "a"), Ok(None));
+    assert_eq!(map.insert(2, "b"), Ok(None));
+    assert_eq!(map.insert(3, "c"), Ok(None));
+    assert_eq!(map.insert(1, "A"), Ok(Some("a")));
+    assert_eq!(map.as_ref().len(), 3);
+    assert_eq!(map.get(&1), Some(&"A"));
+    assert_eq!(map.get(&4), None);
+    assert_eq!(map.get_index(0), Some((&1, &"A")));
+    assert_eq!(map.get_index(3), None);
+
+    assert_eq!(map.remove(&1), Some("A"));
+    assert_eq!(map.get_index(0), Some((&3, &"c")));
+    assert_eq!(map.remove(&2), Some("b"));
+    assert_eq!(map.remove(&2), None);
+    assert_eq!(map.remove(&3), Some("c"));
+    assert!(map.as_ref().is_empty());
+}
+
+#[test]
+fn test_reserve_failure() {
+    let mut map: NoPanicLinearMap<u64, u64> = LinearMap::new().into();
+    assert!(map.try_reserve(usize::MAX).is_err());
+    assert!(map.try_reserve_exact(usize::MAX).is_err());
+    assert!(NoPanicLinearMap::<u64, u64>::try_with_capacity(usize::MAX).is_err());
+    map.try_reserve(4).unwrap();
+    assert!(map.as_ref().capacity() >= 4);
+}
+
+#[test]
+fn test_retain_and_convert() {
+    let mut map = NoPanicLinearMap::new();
+    for i in 0..6 {
+        map.insert(i, i * 10).unwrap();
+    }
+    map.retain(|&k, v| { *v += 1; k % 2 == 0 });
+    let map: LinearMap<_, _> = map.into();
+    let expected: LinearMap<_, _> = vec![(0, 1), (2, 21), (4, 41)].into_iter().collect();
+    assert_eq!(map, expected);
+}
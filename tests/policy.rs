@@ -0,0 +1,41 @@
+extern crate linear_map;
+
+use linear_map::policy::{Growth, PolicyLinearMap};
+
+#[test]
+fn test_growth() {
+    let mut exact = PolicyLinearMap::with_policy(Growth::Exact, false);
+    let mut chunked = PolicyLinearMap::with_policy(Growth::Chunked(8), false);
+    for i in 0..10 {
+        exact.insert(i, ());
+        chunked.insert(i, ());
+        assert_eq!(exact.capacity(), i + 1);
+        assert_eq!(chunked.capacity(), if i < 8 { 8 } else { 16 });
+    }
+    assert_eq!(exact.insert(3, ()), Some(()));
+    assert_eq!(exact.capacity(), 10);
+
+    let mut zero = PolicyLinearMap::with_policy(Growth::Chunked(0), false);
+    zero.insert(1, 1);
+    assert_eq!(zero.capacity(), 1);
+}
+
+#[test]
+fn test_auto_shrink() {
+    let mut map: PolicyLinearMap<u32, u32> = (0..64).map(|i| (i, i)).collect();
+    let peak = map.capacity();
+    for i in 0..60 {
+        map.remove(&i);
+    }
+    assert_eq!(map.capacity(), peak);
+
+    map.set_auto_shrink(true);
+    assert!(map.capacity() < 16);
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.get(&63), Some(&63));
+
+    map.clear();
+    assert_eq!(map.capacity(), 0);
+    assert!(map.auto_shrink());
+    assert_eq!(map.growth(), Growth::Amortized);
+}
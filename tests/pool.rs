@@ -0,0 +1,60 @@
+extern crate linear_map;
+
+use std::sync::Arc;
+use std::thread;
+
+use linear_map::pool::LinearMapPool;
+
+#[test]
+fn test_reuses_capacity() {
+    let pool = LinearMapPool::new();
+    {
+        let mut map = pool.get();
+        map.extend((0..100).map(|i| (i, i)));
+    }
+    assert_eq!(pool.idle(), 1);
+
+    let map = pool.get();
+    assert!(map.is_empty());
+    assert!(map.capacity() >= 100);
+    assert_eq!(pool.idle(), 0);
+}
+
+#[test]
+fn test_max_idle_and_detach() {
+    let pool = LinearMapPool::with_max_idle(1);
+    let mut a = pool.get();
+    let mut b = pool.get();
+    a.insert(1, 'a');
+    b.insert(2, 'b');
+    drop(a);
+    drop(b);
+    assert_eq!(pool.idle(), 1);
+
+    let mut c = pool.get();
+    c.insert(3, 'c');
+    let kept = c.detach();
+    assert_eq!(kept[&3], 'c');
+    assert_eq!(pool.idle(), 0);
+
+    drop(pool.get());
+    pool.clear();
+    assert_eq!(pool.idle(), 0);
+}
+
+#[test]
+fn test_shared_between_threads() {
+    let pool = Arc::new(LinearMapPool::new());
+    let handles: Vec<_> = (0..4).map(|t| {
+        let pool = pool.clone();
+        thread::spawn(move || {
+            let mut map = pool.get();
+            map.insert(t, t);
+            map.len()
+        })
+    }).collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 1);
+    }
+    assert!(pool.idle() >= 1);
+}
@@ -0,0 +1,32 @@
+extern crate linear_map;
+
+use linear_map::LinearMap;
+
+#[test]
+fn test_string_keys() {
+    let mut map = LinearMap::new();
+    map.insert("com.example.alpha".to_string(), 1);
+    map.insert("com.example.beta".to_string(), 2);
+    map.insert("".to_string(), 0);
+
+    assert_eq!(map.get_prefiltered("com.example.beta"), Some(&2));
+    assert_eq!(map.get_prefiltered("com.example.betb"), None);
+    assert_eq!(map.get_prefiltered(""), Some(&0));
+    assert!(!map.contains_key_prefiltered("com.example"));
+
+    *map.get_mut_prefiltered("com.example.alpha").unwrap() += 10;
+    assert_eq!(map.remove_prefiltered("com.example.alpha"), Some(11));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_byte_keys() {
+    let mut map: LinearMap<&[u8], u8> = LinearMap::new();
+    map.insert(b"\x00abc\xff", 1);
+    map.insert(b"\x00abd\xff", 2);
+    assert_eq!(map.get_prefiltered(b"\x00abd\xff"), Some(&2));
+    assert_eq!(map.get_prefiltered(&b"\x00abc\xff"[..]), Some(&1));
+
+    let owned: LinearMap<Vec<u8>, u8> = vec![(b"xyz".to_vec(), 3)].into_iter().collect();
+    assert!(owned.contains_key_prefiltered("xyz"));
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "rand")]
+
+extern crate linear_map;
+extern crate rand;
+
+use linear_map::LinearMap;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+#[test]
+fn test_choose_weighted() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let weights = vec![("a", 1.0), ("b", 3.0), ("none", 0.0), ("nan", f64::NAN)];
+    let map: LinearMap<_, _> = weights.into_iter().collect();
+
+    let mut counts = LinearMap::new();
+    for _ in 0..4000 {
+        let (k, _) = map.choose_weighted(&mut rng, |_, &w| w).unwrap();
+        *counts.entry(*k).or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 2);
+    assert!(counts[&"a"] > 800 && counts[&"a"] < 1200, "{:?}", counts);
+    assert!(counts[&"b"] > 2800 && counts[&"b"] < 3200, "{:?}", counts);
+}
+
+#[test]
+fn test_choose_weighted_empty() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut map = LinearMap::new();
+    assert_eq!(map.choose_weighted(&mut rng, |_, &w| w), None);
+    map.insert(1, 0.0);
+    map.insert(2, -1.0);
+    assert_eq!(map.choose_weighted(&mut rng, |_, &w| w), None);
+}
+
+#[test]
+fn test_shuffle() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let original: LinearMap<_, _> = (0..32).map(|i| (i, i * 2)).collect();
+    let mut map = original.clone();
+    map.shuffle(&mut rng);
+    assert_eq!(map, original);
+    assert!(!map.keys().eq(original.keys()));
+
+    let mut firsts = LinearMap::new();
+    let mut small: LinearMap<_, _> = (0..3).map(|i| (i, ())).collect();
+    for _ in 0..3000 {
+        small.shuffle(&mut rng);
+        *firsts.entry(*small.keys().next().unwrap()).or_insert(0) += 1;
+    }
+    assert!(firsts.values().all(|&n| n > 800 && n < 1200), "{:?}", firsts);
+}
@@ -2,9 +2,17 @@
 
 extern crate linear_map;
 use linear_map::LinearMap;
+use linear_map::serde::SortedKeys;
+
+extern crate serde;
+extern crate serde_derive;
+use serde::Deserialize;
+use serde::de::IntoDeserializer;
+use serde::de::value::{Error, MapDeserializer};
+use std::collections::BTreeMap;
 
 extern crate serde_test;
-use serde_test::{Token, assert_tokens};
+use serde_test::{Token, assert_de_tokens, assert_tokens};
 
 #[test]
 fn test_ser_de_empty() {
@@ -37,6 +45,85 @@ fn test_ser_de() {
     ]);
 }
 
+#[test]
+fn test_ser_sorted() {
+    let mut map = LinearMap::new();
+    map.insert('b', 20);
+    map.insert('a', 10);
+    map.insert('c', 30);
+
+    serde_test::assert_ser_tokens(&SortedKeys(&map), &[
+        Token::Map { len: Some(3) },
+            Token::Char('a'),
+            Token::I32(10),
+
+            Token::Char('b'),
+            Token::I32(20),
+
+            Token::Char('c'),
+            Token::I32(30),
+        Token::MapEnd,
+    ]);
+}
+
+#[test]
+fn test_de_borrowed() {
+    let mut map = LinearMap::new();
+    map.insert("name", "linear-map");
+    map.insert("kind", "crate");
+
+    assert_de_tokens(&map, &[
+        Token::Map { len: Some(2) },
+            Token::BorrowedStr("name"),
+            Token::BorrowedStr("linear-map"),
+
+            Token::BorrowedStr("kind"),
+            Token::BorrowedStr("crate"),
+        Token::MapEnd,
+    ]);
+}
+
+#[test]
+fn test_de_in_place() {
+    let mut map = LinearMap::with_capacity(8);
+    map.insert('z', 0);
+
+    let de = MapDeserializer::<_, Error>::new(vec![('a', 1), ('b', 2)].into_iter());
+    LinearMap::deserialize_in_place(de, &mut map).unwrap();
+
+    assert_eq!(map, vec![('a', 1), ('b', 2)].into_iter().collect());
+    assert!(map.capacity() >= 8);
+}
+
+#[test]
+fn test_into_deserializer() {
+    let mut map = LinearMap::new();
+    map.insert("port".to_string(), 8080u16);
+    map.insert("workers".to_string(), 4);
+
+    let de = IntoDeserializer::<Error>::into_deserializer(map);
+    let settings = BTreeMap::<String, u16>::deserialize(de).unwrap();
+    assert_eq!(settings.len(), 2);
+    assert_eq!(settings["port"], 8080);
+    assert_eq!(settings["workers"], 4);
+}
+
+#[test]
+fn test_into_deserializer_struct() {
+    #[derive(serde_derive::Deserialize, Debug, PartialEq)]
+    struct Settings {
+        port: u16,
+        workers: u16,
+    }
+
+    let mut map = LinearMap::new();
+    map.insert("workers", 4u16);
+    map.insert("port", 8080);
+
+    let de = IntoDeserializer::<Error>::into_deserializer(map);
+    assert_eq!(Settings::deserialize(de), Ok(Settings { port: 8080, workers: 4 }));
+}
+
 mod set {
     use serde_test::{Token, assert_tokens};
     use linear_map::set::LinearSet;
@@ -66,3 +153,121 @@ mod set {
         ]);
     }
 }
+
+mod sorted {
+    use serde_test::{Token, assert_de_tokens, assert_tokens};
+    use linear_map::sorted::SortedLinearMap;
+
+    #[test]
+    fn test_ser_de() {
+        let map: SortedLinearMap<char, i32> = vec![('b', 2), ('a', 1)].into_iter().collect();
+        assert_tokens(&map, &[
+            Token::Map { len: Some(2) },
+            Token::Char('a'),
+            Token::I32(1),
+            Token::Char('b'),
+            Token::I32(2),
+            Token::MapEnd,
+        ]);
+        assert_de_tokens(&map, &[
+            Token::Map { len: Some(2) },
+            Token::Char('b'),
+            Token::I32(2),
+            Token::Char('a'),
+            Token::I32(1),
+            Token::MapEnd,
+        ]);
+    }
+}
+
+mod multimap {
+    use serde_test::{Token, assert_de_tokens, assert_tokens};
+    use linear_map::multimap::LinearMultiMap;
+
+    #[test]
+    fn test_ser_de() {
+        let mut map = LinearMultiMap::new();
+        map.insert('a', 1);
+        map.insert('a', 2);
+        map.insert('b', 3);
+        assert_tokens(&map, &[
+            Token::Map { len: Some(2) },
+            Token::Char('a'),
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(2),
+            Token::SeqEnd,
+            Token::Char('b'),
+            Token::Seq { len: Some(1) },
+            Token::I32(3),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ]);
+    }
+
+    #[test]
+    fn test_de_repeated_and_empty_keys() {
+        let mut map = LinearMultiMap::new();
+        map.insert('a', 1);
+        map.insert('a', 2);
+        assert_de_tokens(&map, &[
+            Token::Map { len: Some(3) },
+            Token::Char('a'),
+            Token::Seq { len: Some(1) },
+            Token::I32(1),
+            Token::SeqEnd,
+            Token::Char('b'),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Char('a'),
+            Token::Seq { len: Some(1) },
+            Token::I32(2),
+            Token::SeqEnd,
+            Token::MapEnd,
+        ]);
+    }
+}
+
+mod thin {
+    use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+    use linear_map::thin::ThinLinearMap;
+
+    #[test]
+    fn test_ser_de() {
+        let mut map = ThinLinearMap::new();
+        map.insert('b', 2);
+        map.insert('a', 1);
+        assert_tokens(&map, &[
+            Token::Map { len: Some(2) },
+            Token::Char('b'),
+            Token::I32(2),
+            Token::Char('a'),
+            Token::I32(1),
+            Token::MapEnd,
+        ]);
+    }
+
+    #[test]
+    fn test_de_capacity_overflow() {
+        // The map announces its length up front, so the error comes before any entry is read.
+        let len = u32::MAX as usize + 1;
+        assert_de_tokens_error::<ThinLinearMap<char, i32>>(
+            &[Token::Map { len: Some(len) }],
+            "invalid length 4294967296, expected a map with at most 4294967295 entries",
+        );
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_de_size_hint_overflow() {
+        use serde::Deserialize;
+        use serde::de::value::{Error, MapDeserializer};
+
+        // The entries are produced lazily, so only the size hint is ever looked at.
+        let entries = (0..=u32::MAX as u64).map(|i| (i, ()));
+        let de = MapDeserializer::<_, Error>::new(entries);
+        let err = ThinLinearMap::<u64, ()>::deserialize(de).unwrap_err();
+        assert_eq!(err.to_string(),
+                   "invalid length 4294967296, expected a map with at most 4294967295 entries");
+    }
+}
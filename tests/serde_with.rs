@@ -0,0 +1,78 @@
+#![cfg(all(feature = "serde_with", feature = "serde_impl"))]
+
+extern crate linear_map;
+use linear_map::LinearMap;
+
+extern crate serde;
+use serde::{Deserialize, Deserializer};
+
+extern crate serde_test;
+use serde_test::{Token, assert_de_tokens, assert_ser_tokens};
+
+extern crate serde_with;
+use serde_with::{DeserializeAs, DisplayFromStr, Same};
+use serde_with::ser::SerializeAsWrap;
+
+type AsStringKeys = LinearMap<DisplayFromStr, Same>;
+type AsPairs = Vec<(Same, DisplayFromStr)>;
+
+#[derive(Debug, PartialEq)]
+struct MapAs(LinearMap<u32, bool>);
+
+impl<'de> Deserialize<'de> for MapAs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        AsStringKeys::deserialize_as(deserializer).map(MapAs)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct PairsAs(LinearMap<u32, bool>);
+
+impl<'de> Deserialize<'de> for PairsAs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        AsPairs::deserialize_as(deserializer).map(PairsAs)
+    }
+}
+
+fn sample() -> LinearMap<u32, bool> {
+    let mut map = LinearMap::new();
+    map.insert(7, true);
+    map.insert(3, false);
+    map
+}
+
+#[test]
+fn test_map_as() {
+    let tokens = [
+        Token::Map { len: Some(2) },
+            Token::Str("7"),
+            Token::Bool(true),
+
+            Token::Str("3"),
+            Token::Bool(false),
+        Token::MapEnd,
+    ];
+
+    assert_ser_tokens(&SerializeAsWrap::<_, AsStringKeys>::new(&sample()), &tokens);
+    assert_de_tokens(&MapAs(sample()), &tokens);
+}
+
+#[test]
+fn test_seq_as() {
+    let tokens = [
+        Token::Seq { len: Some(2) },
+            Token::Tuple { len: 2 },
+                Token::U32(7),
+                Token::Str("true"),
+            Token::TupleEnd,
+
+            Token::Tuple { len: 2 },
+                Token::U32(3),
+                Token::Str("false"),
+            Token::TupleEnd,
+        Token::SeqEnd,
+    ];
+
+    assert_ser_tokens(&SerializeAsWrap::<_, AsPairs>::new(&sample()), &tokens);
+    assert_de_tokens(&PairsAs(sample()), &tokens);
+}
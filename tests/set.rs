@@ -1,5 +1,6 @@
 extern crate linear_map;
 
+use linear_map::LinearMap;
 use linear_map::set::LinearSet;
 
 #[test]
@@ -250,6 +251,8 @@ fn test_trivial_drain() {
 }
 
 #[test]
+// The loop asserting that the set is empty must never run.
+#[allow(clippy::never_loop)]
 fn test_drain() {
     let mut s: LinearSet<_> = (1..100).collect();
 
@@ -313,6 +316,22 @@ fn test_into_vec() {
     assert_eq!(set_as_vec, vec![0, 5, 11, 7]);
 }
 
+#[test]
+fn test_map_conversions() {
+    assert_eq!(std::mem::size_of::<(u64, ())>(), std::mem::size_of::<u64>());
+
+    let mut set = LinearSet::with_capacity(10);
+    set.insert("a");
+    set.insert("b");
+    let map: LinearMap<_, ()> = set.into();
+    assert_eq!(map.capacity(), 10);
+    assert_eq!(map.keys().collect::<Vec<_>>(), [&"a", &"b"]);
+
+    let set = LinearSet::from(map);
+    assert!(set.contains("a") && set.contains("b"));
+    assert_eq!(set.len(), 2);
+}
+
 #[test]
 fn test_retain() {
     let xs = [1,2,3,4,5,6];
@@ -323,3 +342,32 @@ fn test_retain() {
     assert!(set.contains(&4));
     assert!(set.contains(&6));
 }
+
+#[test]
+fn test_in_place_ops() {
+    let a: LinearSet<_> = (0..6).collect();
+    let b: LinearSet<_> = (4..9).collect();
+    let expect = |xs: &[i32]| xs.iter().cloned().collect::<LinearSet<_>>();
+
+    let mut set = a.clone();
+    set.union_with(&b);
+    assert_eq!(set, expect(&[0, 1, 2, 3, 4, 5, 6, 7, 8]));
+    assert_eq!(set.len(), 9);
+
+    let mut set = a.clone();
+    set.intersect_with(&b);
+    assert_eq!(set, expect(&[4, 5]));
+
+    let mut set = a.clone();
+    set.difference_with(&b);
+    assert_eq!(set, expect(&[0, 1, 2, 3]));
+
+    let mut set = a.clone();
+    set.symmetric_difference_with(&b);
+    assert_eq!(set, expect(&[0, 1, 2, 3, 6, 7, 8]));
+    assert_eq!(set.len(), 7);
+
+    let mut set = a.clone();
+    set.symmetric_difference_with(&a);
+    assert!(set.is_empty());
+}
@@ -0,0 +1,31 @@
+extern crate linear_map;
+
+use linear_map::errors::UnknownKeyError;
+use linear_map::shared::{SharedKeys, SharedKeysMap};
+
+#[test]
+fn test_shared_table() {
+    let keys = SharedKeys::new(vec!["x", "y", "x", "z"]);
+    assert_eq!(keys.keys(), ["x", "y", "z"]);
+    assert_eq!(keys.index_of("z"), Some(2));
+
+    let mut a: SharedKeysMap<&str, i32> = keys.new_map();
+    let mut b = SharedKeysMap::new(&keys);
+    assert!(a.is_empty());
+    assert_eq!(a.insert("y", 1), Ok(None));
+    assert_eq!(a.insert("y", 2), Ok(Some(1)));
+    assert_eq!(a.insert("w", 3), Err(UnknownKeyError { value: 3 }));
+    b.insert("y", 2).unwrap();
+    assert_eq!(a, b);
+
+    b.insert("x", 0).unwrap();
+    assert_eq!(b.iter().collect::<Vec<_>>(), [(&"x", &0), (&"y", &2)]);
+    *b.get_mut("x").unwrap() += 5;
+    assert_eq!(b.remove("y"), Some(2));
+    assert_eq!(b.len(), 1);
+    assert!(!b.contains_key("y"));
+
+    let map = b.into_linear_map();
+    assert_eq!(map["x"], 5);
+    assert_eq!(map.len(), 1);
+}
@@ -0,0 +1,122 @@
+extern crate linear_map;
+
+use linear_map::sorted::SortedLinearMap;
+
+#[test]
+fn test_sorted_order() {
+    let mut map = SortedLinearMap::new();
+    for &k in &[5, 1, 4, 2, 3] {
+        assert_eq!(map.insert(k, k * 10), None);
+    }
+    assert_eq!(map.insert(4, 0), Some(40));
+    assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+
+    assert_eq!(map.get(&2), Some(&20));
+    assert_eq!(map.get(&6), None);
+    assert_eq!(map.remove(&3), Some(30));
+    assert_eq!(map.remove(&3), None);
+    assert_eq!(map.values().cloned().collect::<Vec<_>>(), [10, 20, 0, 50]);
+
+    map.retain(|&k, _| k % 2 == 0);
+    assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [2, 4]);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get_index(map.index_at(1).unwrap()), Some((&4, &0)));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "stale EntryIndex")]
+fn test_stale_index_after_insert() {
+    let mut map: SortedLinearMap<_, _> = vec![(2, 'b'), (3, 'c')].into_iter().collect();
+    let two = map.index_at(0).unwrap();
+    map.insert(1, 'a');
+    map.get_index(two);
+}
+
+#[test]
+fn test_merge_sorted() {
+    let left: SortedLinearMap<_, _> = vec![(1, "a"), (4, "d"), (6, "f")].into_iter().collect();
+    let right: SortedLinearMap<_, _> = vec![(2, "B"), (4, "D"), (7, "G")].into_iter().collect();
+
+    let mut calls = vec![];
+    let merged = left.clone().merge_sorted(right, |&k, l, r| {
+        calls.push(k);
+        if k % 2 == 0 { r } else { l }
+    });
+    assert_eq!(calls, [4]);
+    assert_eq!(merged.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>(),
+               [(1, "a"), (2, "B"), (4, "D"), (6, "f"), (7, "G")]);
+
+    let same = left.clone().merge_sorted(SortedLinearMap::new(), |_, l, _| l);
+    assert_eq!(same, left);
+}
+
+#[test]
+fn test_entry() {
+    use linear_map::sorted::Entry;
+
+    let mut map = SortedLinearMap::new();
+    for word in "the quick brown fox jumps over the lazy dog the end".split(' ') {
+        *map.entry(word).or_insert(0) += 1;
+    }
+    assert_eq!(map.keys().cloned().collect::<Vec<_>>(),
+               ["brown", "dog", "end", "fox", "jumps", "lazy", "over", "quick", "the"]);
+    assert_eq!(map.get("the"), Some(&3));
+
+    match map.entry("cat") {
+        Entry::Vacant(e) => {
+            assert_eq!(e.key(), &"cat");
+            assert_eq!(*e.insert(7), 7);
+        }
+        Entry::Occupied(_) => panic!(),
+    }
+    assert_eq!(map.keys().nth(1), Some(&"cat"));
+
+    match map.entry("fox") {
+        Entry::Occupied(mut e) => {
+            assert_eq!(e.key(), &"fox");
+            assert_eq!(e.insert(5), 1);
+            assert_eq!(e.remove(), 5);
+        }
+        Entry::Vacant(_) => panic!(),
+    }
+    assert!(!map.contains_key("fox"));
+    assert_eq!(*map.entry("ant").or_insert_with(|| 9), 9);
+    assert_eq!(map.keys().next(), Some(&"ant"));
+}
+
+#[test]
+fn test_first_last() {
+    let mut map: SortedLinearMap<_, _> = vec![(2, 'b'), (3, 'c'), (1, 'a')].into_iter().collect();
+    assert_eq!(map.first_key_value(), Some((&1, &'a')));
+    assert_eq!(map.last_key_value(), Some((&3, &'c')));
+    assert_eq!(map.pop_first(), Some((1, 'a')));
+    assert_eq!(map.pop_last(), Some((3, 'c')));
+    assert_eq!(map.pop_last(), Some((2, 'b')));
+    assert_eq!(map.pop_first(), None);
+    assert_eq!(map.first_key_value(), None);
+}
+
+#[test]
+fn test_bounds() {
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+
+    let map: SortedLinearMap<_, _> = vec![(10, 'a'), (20, 'b'), (30, 'c')].into_iter().collect();
+    let next = |b| map.lower_bound(b).peek_next().map(|(&k, _)| k);
+    assert_eq!(next(Included(&20)), Some(20));
+    assert_eq!(next(Excluded(&20)), Some(30));
+    assert_eq!(next(Unbounded), Some(10));
+    assert_eq!(next(Excluded(&30)), None);
+
+    let prev = |b| map.upper_bound(b).peek_prev().map(|(&k, _)| k);
+    assert_eq!(prev(Included(&20)), Some(20));
+    assert_eq!(prev(Excluded(&20)), Some(10));
+    assert_eq!(prev(Unbounded), Some(30));
+    assert_eq!(prev(Excluded(&10)), None);
+
+    let mut cursor = map.upper_bound(Included(&25));
+    assert_eq!(cursor.prev(), Some((&20, &'b')));
+    assert_eq!(cursor.prev(), Some((&10, &'a')));
+    assert_eq!(cursor.prev(), None);
+    assert_eq!(cursor.clone().next(), Some((&10, &'a')));
+}
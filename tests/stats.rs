@@ -0,0 +1,70 @@
+#![cfg(feature = "diagnostics")]
+
+extern crate linear_map;
+
+use std::panic;
+
+use linear_map::stats::{CountingLinearMap, KeyStats};
+
+#[test]
+fn test_counts_hits_and_misses() {
+    let mut map: CountingLinearMap<&str, u32> =
+        vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+    assert_eq!(map.stats_of("a"), Some(KeyStats { hits: 0, last_access: 1 }));
+
+    map.get("b");
+    map.get("c");
+    map.get("b");
+    *map.get_mut("a").unwrap() += 10;
+    assert!(map.contains_key("c"));
+    assert!(!map.contains_key("z"));
+    assert_eq!(map.insert("a", 0), Some(11));
+    assert_eq!(map.misses(), 1);
+
+    let order: Vec<_> = map.access_stats().into_iter().map(|(&k, s)| (k, s.hits)).collect();
+    assert_eq!(order, [("a", 2), ("c", 2), ("b", 2)]);
+
+    map.reset_stats();
+    assert_eq!(map.misses(), 0);
+    assert!(map.access_stats().iter().all(|&(_, s)| s == KeyStats::default()));
+}
+
+#[test]
+fn test_stats_follow_entries() {
+    let mut map: CountingLinearMap<u32, u32> = (0..6).map(|i| (i, i)).collect();
+    for i in 0..6 {
+        for _ in 0..i {
+            map.get(&i);
+        }
+    }
+    assert_eq!(map.remove(&1), Some(1));
+    assert_eq!(map.stats_of(&5).unwrap().hits, 5);
+
+    map.retain(|&k, _| k != 3);
+    for (&k, stats) in map.access_stats() {
+        assert_eq!(stats.hits, k as u64);
+    }
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.clone().into_inner(), (*map).clone());
+}
+
+#[test]
+fn test_retain_panic() {
+    let mut map: CountingLinearMap<u32, u32> = (0..6).map(|i| (i, i)).collect();
+    for i in 0..6 {
+        for _ in 0..i {
+            map.get(&i);
+        }
+    }
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        map.retain(|&k, _| {
+            assert!(k != 4, "boom");
+            k % 2 == 0
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(map.len(), 6);
+    for (&k, stats) in map.access_stats() {
+        assert_eq!(stats.hits, k as u64);
+    }
+}
@@ -1,7 +1,10 @@
 #[macro_use]
 extern crate linear_map;
 
-use linear_map::LinearMap;
+use std::panic;
+
+use linear_map::{EntryRef, EntryToken, LinearMap};
+use linear_map::errors::{DuplicateKeyError, RenameError};
 use linear_map::Entry::{Occupied, Vacant};
 
 const TEST_CAPACITY: usize = 10;
@@ -276,3 +279,824 @@ fn test_retain() {
     assert_eq!(map[&4], 40);
     assert_eq!(map[&6], 60);
 }
+
+#[test]
+fn test_values_mut() {
+    let mut map = linear_map!{ 1 => vec![1], 2 => vec![2], 3 => vec![3] };
+    let mut values = map.values_mut();
+    assert_eq!(values.len(), 3);
+    values.next_back().unwrap().push(30);
+    assert_eq!(values.len(), 2);
+    for v in values {
+        v.push(0);
+    }
+    assert_eq!(map[&1], [1, 0]);
+    assert_eq!(map[&2], [2, 0]);
+    assert_eq!(map[&3], [3, 30]);
+}
+
+#[test]
+fn test_remove_entry() {
+    let mut map = linear_map!{ "a".to_string() => 1, "b".to_string() => 2, "c".to_string() => 3 };
+    assert_eq!(map.remove_entry("a"), Some(("a".to_string(), 1)));
+    assert_eq!(map.remove_entry("a"), None);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.keys().next().map(|k| k.as_str()), Some("c"));
+}
+
+#[test]
+fn test_get_key_value() {
+    let map = linear_map!{ "Alpha".to_string() => 1, "beta".to_string() => 2 };
+    let (k, v) = map.get_key_value("Alpha").unwrap();
+    assert_eq!((k.as_str(), *v), ("Alpha", 1));
+    assert_eq!(map.get_key_value("alpha"), None);
+}
+
+#[test]
+fn test_into_keys() {
+    let map = linear_map!{ "a".to_string() => 1, "b".to_string() => 2 };
+    let mut keys = map.into_keys();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys.next_back(), Some("b".to_string()));
+    assert_eq!(keys.collect::<Vec<_>>(), ["a"]);
+}
+
+#[test]
+fn test_into_values() {
+    let map = linear_map!{ 1 => vec![1; 3], 2 => vec![2; 2] };
+    let mut values = map.into_values();
+    assert_eq!(values.len(), 2);
+    assert_eq!(values.next_back(), Some(vec![2, 2]));
+    assert_eq!(values.collect::<Vec<_>>(), [vec![1, 1, 1]]);
+}
+
+#[test]
+fn test_extract_if() {
+    let mut map: LinearMap<i32, i32> = (0..10).map(|x| (x, x)).collect();
+    let mut removed: Vec<_> = map.extract_if(|&k, v| { *v += 1; k % 3 == 0 }).collect();
+    removed.sort();
+    assert_eq!(removed, [(0, 1), (3, 4), (6, 7), (9, 10)]);
+    assert_eq!(map.len(), 6);
+    assert!(map.iter().all(|(&k, &v)| k % 3 != 0 && v == k + 1));
+
+    let mut map: LinearMap<i32, i32> = (0..10).map(|x| (x, x)).collect();
+    assert_eq!(map.extract_if(|_, _| true).next(), Some((0, 0)));
+    assert_eq!(map.len(), 9);
+    assert_eq!(map.extract_if(|_, _| false).count(), 0);
+    assert_eq!(map.len(), 9);
+}
+
+#[test]
+fn test_from_array_and_vec() {
+    let map = LinearMap::from([(1, "a"), (2, "b"), (1, "c")]);
+    assert_eq!(map, linear_map!{ 1 => "c", 2 => "b" });
+    let empty: LinearMap<i32, i32> = LinearMap::from([]);
+    assert!(empty.is_empty());
+
+    let pairs = vec![(1, "a"), (2, "b"), (1, "c"), (3, "d"), (2, "e"), (2, "f")];
+    let capacity = pairs.capacity();
+    let map = LinearMap::from(pairs);
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(&1, &"c"), (&2, &"f"), (&3, &"d")]);
+    assert_eq!(map.capacity(), capacity);
+}
+
+#[test]
+fn test_extend_borrowed() {
+    let mut map = linear_map!{ 1 => 'a', 2 => 'b' };
+    let other = linear_map!{ 2 => 'B', 3 => 'C' };
+    map.extend(other.iter());
+    map.extend(&other);
+    assert_eq!(map, linear_map!{ 1 => 'a', 2 => 'B', 3 => 'C' });
+}
+
+#[test]
+fn test_swap() {
+    let mut map = linear_map!{ 1 => "one", 2 => "two", 3 => "three" };
+    assert!(map.swap(&1, &3));
+    assert_eq!(map[&1], "three");
+    assert_eq!(map[&3], "one");
+    assert_eq!(map[&2], "two");
+    assert!(map.swap(&2, &2));
+    assert_eq!(map[&2], "two");
+    assert!(!map.swap(&1, &4));
+    assert!(!map.swap(&4, &1));
+    assert_eq!(map[&1], "three");
+}
+
+#[test]
+fn test_merge_maps() {
+    let shards = vec![
+        linear_map!{ 1 => "a", 2 => "b" },
+        linear_map!{ 2 => "B", 3 => "c" },
+        linear_map!{ 4 => "d" },
+    ];
+    let merged: LinearMap<_, _> = shards.clone().into_iter().collect();
+    assert_eq!(merged, linear_map!{ 1 => "a", 2 => "B", 3 => "c", 4 => "d" });
+    let summed: LinearMap<_, _> = shards.into_iter().sum();
+    assert_eq!(summed, merged);
+
+    let empty: LinearMap<i32, i32> = Vec::<LinearMap<i32, i32>>::new().into_iter().sum();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_sub_map() {
+    let map: LinearMap<String, i32> =
+        vec![("id".to_string(), 1), ("name".to_string(), 2), ("secret".to_string(), 3)]
+            .into_iter().collect();
+
+    let public = map.sub_map(vec!["id", "name", "missing"]);
+    assert_eq!(public.len(), 2);
+    assert_eq!(public["id"], 1);
+    assert_eq!(public["name"], 2);
+    assert!(!public.contains_key("secret"));
+
+    let extracted = map.extract_keys(["secret"].iter().cloned());
+    assert_eq!(extracted.len(), 1);
+    assert_eq!(extracted["secret"], 3);
+}
+
+#[test]
+fn test_rename_key() {
+    let mut map = linear_map!{ "a" => 1, "b" => 2, "c" => 3 };
+    let order: Vec<_> = map.values().cloned().collect();
+
+    assert_eq!(map.rename_key("b", "x"), Ok(()));
+    assert_eq!(map.get("x"), Some(&2));
+    assert!(!map.contains_key("b"));
+    assert_eq!(map.values().cloned().collect::<Vec<_>>(), order);
+
+    assert_eq!(map.rename_key("a", "c"), Err(RenameError::KeyExists("c")));
+    assert_eq!(map.rename_key("missing", "y"), Err(RenameError::NotFound("y")));
+    assert_eq!(map["a"], 1);
+    assert_eq!(map.len(), 3);
+
+    assert_eq!(map.rename_key("a", "a"), Ok(()));
+}
+
+#[test]
+fn test_entry_or_default() {
+    let mut map: LinearMap<&str, Vec<i32>> = LinearMap::new();
+    map.entry("a").or_default().push(1);
+    map.entry("a").or_default().push(2);
+    assert!(map.entry("b").or_default().is_empty());
+    assert_eq!(map["a"], [1, 2]);
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_entry_and_modify() {
+    let mut map = linear_map!{ 1 => 10 };
+    assert_eq!(*map.entry(1).and_modify(|v| *v += 1).or_insert(0), 11);
+    assert_eq!(*map.entry(2).and_modify(|v| *v += 1).or_insert(0), 0);
+    assert_eq!(map, linear_map!{ 1 => 11, 2 => 0 });
+}
+
+#[test]
+fn test_entry_key() {
+    let mut map = linear_map!{ "a".to_string() => 1 };
+    assert_eq!(map.entry("a".to_string()).key(), "a");
+    assert_eq!(map.entry("b".to_string()).key(), "b");
+    match map.entry("a".to_string()) {
+        Occupied(view) => assert_eq!(view.key(), "a"),
+        Vacant(_) => unreachable!(),
+    }
+    match map.entry("b".to_string()) {
+        Occupied(_) => unreachable!(),
+        Vacant(view) => {
+            assert_eq!(view.key(), "b");
+            assert_eq!(view.into_key(), "b");
+        }
+    }
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_occupied_remove_entry() {
+    let mut map = linear_map!{ "a".to_string() => 1, "b".to_string() => 2 };
+    match map.entry("a".to_string()) {
+        Occupied(view) => assert_eq!(view.remove_entry(), ("a".to_string(), 1)),
+        Vacant(_) => unreachable!(),
+    }
+    assert_eq!(map, linear_map!{ "b".to_string() => 2 });
+}
+
+#[test]
+fn test_try_insert() {
+    let mut map = LinearMap::new();
+    *map.try_insert("a", 1).unwrap() += 10;
+    assert_eq!(map["a"], 11);
+
+    {
+        let mut err = map.try_insert("a", 2).unwrap_err();
+        assert_eq!(err.entry.key(), &"a");
+        assert_eq!(err.value, 2);
+        assert_eq!(err.to_string(), r#"failed to insert 2, key "a" already exists with value 11"#);
+        *err.entry.get_mut() = 0;
+    }
+    assert_eq!(map, linear_map!{ "a" => 0 });
+}
+
+#[test]
+fn test_entry_ref() {
+    let mut map: LinearMap<String, Vec<i32>> = LinearMap::new();
+    map.entry_ref("a").or_default().push(1);
+    map.entry_ref("a").or_default().push(2);
+    assert_eq!(map.entry_ref("a").key(), "a");
+    assert_eq!(*map.entry_ref("b").and_modify(|v| v.push(0)).or_insert_with(|| vec![3]), [3]);
+    match map.entry_ref("c") {
+        EntryRef::Occupied(_) => unreachable!(),
+        EntryRef::Vacant(view) => {
+            assert_eq!(view.key(), "c");
+            view.insert(vec![4]);
+        }
+    }
+    assert_eq!(map["a"], [1, 2]);
+    assert_eq!(map["c"], [4]);
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn test_vacant_insert_entry() {
+    let mut map = linear_map!{ 1 => "a" };
+    match map.entry(2) {
+        Occupied(_) => unreachable!(),
+        Vacant(view) => {
+            let mut view = view.insert_entry("b");
+            assert_eq!(view.key(), &2);
+            assert_eq!(view.insert("c"), "b");
+        }
+    }
+    assert_eq!(map, linear_map!{ 1 => "a", 2 => "c" });
+}
+
+#[test]
+fn test_replace_key_and_entry() {
+    #[derive(Debug)]
+    struct Caseless(&'static str);
+    impl PartialEq for Caseless {
+        fn eq(&self, other: &Self) -> bool { self.0.eq_ignore_ascii_case(other.0) }
+    }
+
+    let mut map = LinearMap::new();
+    map.insert(Caseless("key"), 1);
+    match map.entry(Caseless("KEY")) {
+        Occupied(mut view) => assert_eq!(view.replace_key(Caseless("KEY")).0, "key"),
+        Vacant(_) => unreachable!(),
+    }
+    assert_eq!(map.keys().next().unwrap().0, "KEY");
+
+    match map.entry(Caseless("Key")) {
+        Occupied(view) => {
+            let (k, v) = view.replace_entry(Caseless("Key"), 2);
+            assert_eq!((k.0, v), ("KEY", 1));
+        }
+        Vacant(_) => unreachable!(),
+    }
+    assert_eq!(map.iter().map(|(k, &v)| (k.0, v)).collect::<Vec<_>>(), [("Key", 2)]);
+}
+
+#[test]
+fn test_replace_key_from_entry_ref() {
+    let mut map: LinearMap<String, i32> = LinearMap::new();
+    map.insert("a".to_string(), 1);
+    match map.entry_ref("a") {
+        EntryRef::Occupied(mut view) => assert_eq!(view.replace_key("a".to_string()), "a"),
+        EntryRef::Vacant(_) => unreachable!(),
+    }
+    assert_eq!(map["a"], 1);
+}
+
+#[test]
+fn test_entry_insert_entry() {
+    let mut map = linear_map!{ 1 => "a" };
+    assert_eq!(map.entry(1).insert_entry("b").get(), &"b");
+    let mut view = map.entry(2).insert_entry("c");
+    *view.get_mut() = "d";
+    assert_eq!(view.key(), &2);
+    assert_eq!(map, linear_map!{ 1 => "b", 2 => "d" });
+
+    // Both branches return a view that supports the whole `OccupiedEntry` API.
+    for &key in &[1, 3] {
+        let mut view = map.entry(key).insert_entry("e");
+        assert_eq!(view.replace_key(key), key);
+        assert_eq!(view.replace_entry(key, "f"), (key, "e"));
+    }
+    assert_eq!(map, linear_map!{ 1 => "f", 2 => "d", 3 => "f" });
+}
+
+#[test]
+fn test_entry_token() {
+    let mut map = LinearMap::new();
+    let (mut a, old) = map.insert_full("a", 1);
+    assert_eq!(old, None);
+    map.insert("b", 2);
+    let (mut c, old) = map.insert_full("c", 3);
+    assert_eq!(old, None);
+    assert_eq!(map.insert_full("a", 10), (a, Some(1)));
+
+    *map.get_mut_with_token(&mut c, "c").unwrap() += 1;
+    assert_eq!(map.get_with_token(&mut c, "c"), Some(&4));
+
+    // Removing "a" moves "c" into its place; the token follows after a search.
+    let stale: EntryToken = c;
+    map.remove("a");
+    assert_eq!(map.get_with_token(&mut c, "c"), Some(&4));
+    assert!(c != stale);
+    assert_eq!(map.get_token("c"), Some(c));
+    assert_eq!(map.get_with_token(&mut a, "a"), None);
+    assert_eq!(map.get_token("a"), None);
+}
+
+#[test]
+fn test_indexed_iter() {
+    let mut map = linear_map!{ "a" => 1, "b" => 2, "c" => 3 };
+
+    for (i, k, v) in map.indexed_iter_mut() {
+        assert_eq!(["a", "b", "c"][i.get()], *k);
+        *v += 10 * i.get() as i32;
+    }
+
+    let big: Vec<usize> =
+        map.indexed_iter().filter(|&(_, _, &v)| v > 10).map(|(i, _, _)| i.get()).collect();
+    assert_eq!(big, [1, 2]);
+    let b = map.index_at(1).unwrap();
+    assert_eq!(map.get_index(b), Some((&"b", &12)));
+    let c = map.index_at(2).unwrap();
+    *map.get_index_mut(c).unwrap().1 = 0;
+    // Changing a value leaves positions valid.
+    assert_eq!(map.get_index(b), Some((&"b", &12)));
+    assert_eq!(map.swap_remove_index(map.index_at(0).unwrap()), Some(("a", 1)));
+    assert_eq!(map.get_index(map.index_at(0).unwrap()), Some((&"c", &0)));
+    assert!(map.index_at(2).is_none());
+    assert_eq!(map.indexed_iter().len(), 2);
+}
+
+#[test]
+fn test_entry_index_survives_value_changes() {
+    let mut map = linear_map!{ "a" => 1, "b" => 2 };
+    let b = map.index_at(1).unwrap();
+
+    *map.get_mut("a").unwrap() = 10;
+    map.insert("b", 20);
+    *map.entry("a").or_insert(0) += 1;
+    assert_eq!(map.get_index(b), Some((&"b", &20)));
+
+    let copy = map.clone();
+    assert_eq!(copy.get_index(b), Some((&"b", &20)));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "stale EntryIndex 1")]
+fn test_entry_index_stale_after_insert() {
+    let mut map = linear_map!{ "a" => 1, "b" => 2 };
+    let b = map.index_at(1).unwrap();
+    map.insert("c", 3);
+    map.get_index(b);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "stale EntryIndex 2")]
+fn test_entry_index_stale_after_remove() {
+    let mut map = linear_map!{ "a" => 1, "b" => 2, "c" => 3 };
+    let positions: Vec<_> = map.indexed_iter().map(|(i, _, _)| i).collect();
+    map.swap_remove_index(positions[0]);
+    // The entry that was at position 2 now lives at position 0.
+    map.get_index_mut(positions[2]);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "stale EntryIndex 0")]
+fn test_entry_index_stale_after_retain() {
+    let mut map = linear_map!{ "a" => 1, "b" => 2 };
+    let a = map.index_at(0).unwrap();
+    map.retain(|_, _| true);
+    map.get_index(a);
+}
+
+#[test]
+fn test_try_from_iter() {
+    let map = LinearMap::try_from_iter(vec![("a", 1), ("b", 2)]).unwrap();
+    assert_eq!(map, linear_map!{ "a" => 1, "b" => 2 });
+
+    let err = LinearMap::try_from_iter(vec![("a", 1), ("b", 2), ("a", 3)]).unwrap_err();
+    assert_eq!(err, DuplicateKeyError { key: "a", value: 3 });
+    assert_eq!(err.to_string(), "duplicate key");
+    assert_eq!(err.into_inner(), ("a", 3));
+}
+
+#[test]
+fn test_retain_map() {
+    let mut map = linear_map!{ "a" => 1, "b" => -2, "c" => 3 };
+    map.retain_map(|_, v| if v > 0 { Some(v * 10) } else { None });
+    assert_eq!(map, linear_map!{ "a" => 10, "c" => 30 });
+
+    let mut names = linear_map!{ 1 => String::from("one"), 2 => String::from("two") };
+    names.retain_map(|&k, mut v| if k == 1 { v.push('!'); Some(v) } else { None });
+    assert_eq!(names, linear_map!{ 1 => String::from("one!") });
+
+    // The pairs are compacted within the existing buffer.
+    let mut map = LinearMap::with_capacity(8);
+    map.extend((0..4).map(|x| (x, x)));
+    map.retain_map(|_, v| if v % 2 == 1 { Some(v) } else { None });
+    assert_eq!(map.capacity(), 8);
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), [(1, 1), (3, 3)]);
+}
+
+#[test]
+fn test_retain_map_panic() {
+    let mut map: LinearMap<i32, i32> = (0..6).map(|x| (x, x)).collect();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        map.retain_map(|&k, v| {
+            assert!(k != 3, "boom");
+            if k % 2 == 0 { Some(v * 10) } else { None }
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.get(&0), Some(&0));
+    assert_eq!(map.get(&1), None);
+    assert_eq!(map.get(&3), None);
+    assert_eq!(map.get(&4), Some(&4));
+    assert_eq!(map.get(&5), Some(&5));
+}
+
+#[test]
+fn test_keys_eq() {
+    let schema = linear_map!{ "id" => (), "name" => () };
+    let record = linear_map!{ "name" => "Ann", "id" => "7" };
+    let partial = linear_map!{ "id" => 1 };
+    let extra = linear_map!{ "id" => 1, "age" => 2 };
+
+    assert!(record.keys_eq(&schema));
+    assert!(!partial.keys_eq(&schema));
+    assert!(partial.keys_subset_of(&schema));
+    assert!(record.keys_subset_of(&schema));
+    assert!(!extra.keys_subset_of(&schema));
+    assert!(!schema.keys_subset_of(&partial));
+    assert!(LinearMap::<&str, ()>::new().keys_subset_of(&partial));
+}
+
+#[test]
+fn test_swap_and_shift_remove() {
+    let mut map = linear_map!{ 1 => 'a', 2 => 'b', 3 => 'c', 4 => 'd' };
+    match map.entry(1) {
+        Occupied(e) => assert_eq!(e.shift_remove(), 'a'),
+        Vacant(_) => unreachable!(),
+    }
+    assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [2, 3, 4]);
+
+    match map.entry(2) {
+        Occupied(e) => assert_eq!(e.swap_remove(), 'b'),
+        Vacant(_) => unreachable!(),
+    }
+    assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [4, 3]);
+
+    map.insert(5, 'e');
+    assert_eq!(map.shift_remove(&4), Some('d'));
+    assert_eq!(map.shift_remove(&4), None);
+    assert_eq!(map.keys().cloned().collect::<Vec<_>>(), [3, 5]);
+}
+
+#[test]
+fn test_drain_leak() {
+    use std::mem;
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut map: LinearMap<i32, Rc<()>> = (0..4).map(|i| (i, rc.clone())).collect();
+    {
+        let mut drain = map.drain();
+        assert_eq!(drain.next().map(|(k, _)| k), Some(0));
+        assert_eq!(drain.next_back().map(|(k, _)| k), Some(3));
+        mem::forget(drain);
+    }
+    assert!(map.is_empty());
+    assert_eq!(Rc::strong_count(&rc), 3); // two values leaked
+
+    map.extend((0..4).map(|i| (i, rc.clone())));
+    {
+        let mut drain = map.drain();
+        drain.next();
+    }
+    assert!(map.is_empty());
+    assert_eq!(Rc::strong_count(&rc), 3);
+}
+
+#[test]
+fn test_macro_literal_keys() {
+    let empty: LinearMap<i32, i32> = linear_map!{};
+    assert_eq!(empty.capacity(), 0);
+
+    let ints = linear_map!{ 1 => 'a', -2 => 'b', 3 => 'c' };
+    assert_eq!(ints.capacity(), 3);
+    assert_eq!(ints[&-2], 'b');
+
+    let flags = linear_map!{ true => "yes", false => "no" };
+    assert_eq!(flags[&false], "no");
+
+    let name = "b";
+    let mixed = linear_map!{ "a" => 1, name => 2 };
+    assert_eq!(mixed.len(), 2);
+}
+
+#[test]
+#[forbid(unreachable_patterns)]
+fn test_macro_forbid_unreachable_patterns() {
+    let flags = linear_map!{ true => 1, false => 0 };
+    assert_eq!(flags[&true], 1);
+}
+
+#[test]
+fn test_const_new() {
+    use std::sync::Mutex;
+    use linear_map::set::LinearSet;
+
+    static MAP: Mutex<LinearMap<&str, i32>> = Mutex::new(LinearMap::new());
+    static SET: Mutex<LinearSet<i32>> = Mutex::new(LinearSet::new());
+
+    MAP.lock().unwrap().insert("a", 1);
+    SET.lock().unwrap().insert(1);
+    assert_eq!(MAP.lock().unwrap()["a"], 1);
+    assert!(SET.lock().unwrap().contains(&1));
+}
+
+#[test]
+fn test_get_expect() {
+    let mut map = linear_map!{ "host" => "localhost", "port" => "80" };
+    assert_eq!(*map.get_expect("host"), "localhost");
+    *map.get_expect_mut("port") = "8080";
+    assert_eq!(map["port"], "8080");
+}
+
+#[test]
+#[should_panic(expected = r#"key "user" not found in map with keys ["host", "port"]"#)]
+fn test_get_expect_missing() {
+    let map = linear_map!{ "host" => 1, "port" => 2 };
+    map.get_expect("user");
+}
+
+#[test]
+fn test_into_keys_values() {
+    let map = linear_map!{ "x" => 1.5, "y" => 2.5, "z" => 3.5 };
+    let pairs: Vec<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+    let (keys, values) = map.into_keys_values();
+    assert_eq!(keys.into_iter().zip(values).collect::<Vec<_>>(), pairs);
+
+    let (keys, values) = LinearMap::<i32, i32>::new().into_keys_values();
+    assert!(keys.is_empty() && values.is_empty());
+}
+
+#[test]
+fn test_non_eq_keys() {
+    #[derive(Debug)]
+    struct Opaque;
+
+    let mut map: LinearMap<Opaque, i32> = LinearMap::with_capacity(2);
+    assert!(map.is_empty());
+    assert!(map.capacity() >= 2);
+    assert_eq!(format!("{:?}", map), "{}");
+    map.retain(|_, _| true);
+    assert_eq!(map.iter().count(), 0);
+    assert_eq!(map.drain().count(), 0);
+    let pairs: Vec<(Opaque, i32)> = LinearMap::default().into();
+    assert!(pairs.is_empty());
+    assert!(map.into_iter().next().is_none());
+}
+
+#[test]
+fn test_partial_eq_keys() {
+    let mut map = LinearMap::new();
+    map.insert(1.5, "a");
+    map.insert(f64::NAN, "b");
+    assert_eq!(map.insert(1.5, "c"), Some("a"));
+    assert_eq!(map.get(&1.5), Some(&"c"));
+
+    assert_eq!(map.get(&f64::NAN), None);
+    assert_eq!(map.insert(f64::NAN, "d"), None);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.remove(&f64::NAN), None);
+}
+
+#[test]
+fn test_raw_parts() {
+    let mut map = LinearMap::with_capacity(4);
+    map.insert("a".to_string(), 1);
+    map.insert("b".to_string(), 2);
+
+    let (ptr, len, cap) = map.into_raw_parts();
+    assert_eq!(len, 2);
+    assert!(cap >= 4);
+
+    let mut map = unsafe { LinearMap::from_raw_parts(ptr, len, cap) };
+    assert_eq!(map["a"], 1);
+    map.insert("c".to_string(), 3);
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn test_validate_unique() {
+    let mut entries = std::mem::ManuallyDrop::new(vec![(1, 'a'), (2, 'b'), (1, 'c'), (1, 'd')]);
+    let (ptr, len, cap) = (entries.as_mut_ptr(), entries.len(), entries.capacity());
+    let mut map = unsafe { LinearMap::from_raw_parts(ptr, len, cap) };
+    assert_eq!(map.validate_unique(), Err(vec![&1]));
+
+    map.swap_remove_index(map.index_at(0).unwrap());
+    map.swap_remove_index(map.index_at(0).unwrap());
+    assert_eq!(map.validate_unique(), Ok(()));
+    assert_eq!(LinearMap::<u8, u8>::new().validate_unique(), Ok(()));
+}
+
+#[test]
+fn test_retain_with_control() {
+    use std::ops::ControlFlow;
+
+    let mut map: LinearMap<u32, u32> = (0..10).map(|i| (i, i)).collect();
+    let mut visited = 0;
+    let result = map.retain_with_control(|&k, v| {
+        visited += 1;
+        *v += 100;
+        if k == 6 { ControlFlow::Break(k) } else { ControlFlow::Continue(k % 2 == 0) }
+    });
+    assert_eq!(result, ControlFlow::Break(6));
+    assert_eq!(visited, 7);
+    assert_eq!(map.len(), 7);
+    assert!(!map.contains_key(&1) && !map.contains_key(&5));
+    assert_eq!(map[&6], 106);
+    assert_eq!(map[&9], 9);
+
+    let result: ControlFlow<()> = map.retain_with_control(|&k, _| ControlFlow::Continue(k < 4));
+    assert_eq!(result, ControlFlow::Continue(()));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_display_with() {
+    let mut map = LinearMap::new();
+    assert_eq!(map.display_with(": ", "; ").to_string(), "");
+    map.insert("x", 1.5);
+    assert_eq!(map.display_with(": ", "; ").to_string(), "x: 1.5");
+    map.insert("y", -2.0);
+    assert_eq!(format!("[{}]", map.display_with("=", ",")), "[x=1.5,y=-2]");
+}
+
+#[test]
+fn test_as_borrowed() {
+    let mut map = LinearMap::new();
+    map.insert("a".to_string(), vec![1]);
+    map.insert("b".to_string(), vec![2, 3]);
+
+    let view = map.as_borrowed();
+    assert_eq!(view.len(), 2);
+    assert_eq!(view.get(&"b".to_string()), Some(&&vec![2, 3]));
+    assert!(view.keys().copied().eq(map.keys()));
+    assert!(view.iter().all(|(k, v)| std::ptr::eq(*v, &map[*k])));
+}
+
+#[test]
+fn test_keys_set_values_vec() {
+    let mut map = LinearMap::new();
+    map.insert("b".to_string(), 2);
+    map.insert("a".to_string(), 1);
+    map.insert("c".to_string(), 2);
+
+    let keys = map.keys_set();
+    assert_eq!(keys.len(), 3);
+    assert!(keys.iter().eq(map.keys()));
+    assert_eq!(map.values_vec(), [2, 1, 2]);
+    assert!(LinearMap::<u8, u8>::new().keys_set().is_empty());
+}
+
+#[test]
+fn test_submaps() {
+    let mut big = LinearMap::new();
+    big.insert(1, 'a');
+    big.insert(2, 'b');
+    let mut small = LinearMap::new();
+    small.insert(2, 'b');
+
+    assert!(small.is_submap_of(&big));
+    assert!(small.is_proper_submap_of(&big));
+    assert!(big.is_submap_of(&big));
+    assert!(!big.is_proper_submap_of(&big));
+    assert!(LinearMap::new().is_proper_submap_of(&big));
+
+    small.insert(2, 'x');
+    assert!(!small.is_submap_of(&big));
+    assert!(small.keys_subset_of(&big));
+    assert!(small.keys_proper_subset_of(&big));
+    assert!(!big.keys_proper_subset_of(&big));
+}
+
+#[test]
+fn test_iter_copied_cloned() {
+    let mut map = LinearMap::new();
+    map.insert(1, 'a');
+    map.insert(2, 'b');
+    assert_eq!(map.iter_copied().collect::<Vec<_>>(), [(1, 'a'), (2, 'b')]);
+    assert_eq!(map.iter_copied().next_back(), Some((2, 'b')));
+
+    let mut map = LinearMap::new();
+    map.insert("k".to_string(), vec![1]);
+    let owned: Vec<(String, Vec<i32>)> = map.iter_cloned().collect();
+    assert_eq!(owned, [("k".to_string(), vec![1])]);
+    assert_eq!(map.iter_cloned().len(), 1);
+}
+
+#[test]
+fn test_get_index_unchecked() {
+    let mut map = LinearMap::new();
+    map.insert("a", 1);
+    map.insert("b", 2);
+
+    for i in 0..map.len() {
+        let i = map.index_at(i).unwrap();
+        let (k, v) = unsafe { map.get_index_unchecked(i) };
+        assert_eq!(map.get_index(i), Some((k, v)));
+        *unsafe { map.get_index_unchecked_mut(i) }.1 *= 10;
+    }
+    assert_eq!(map["a"], 10);
+    assert_eq!(map["b"], 20);
+}
+
+#[test]
+fn test_chunks() {
+    let mut map: LinearMap<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+    let sizes: Vec<_> = map.chunks(2).map(|c| c.len()).collect();
+    assert_eq!(sizes, [2, 2, 1]);
+    let first: Vec<_> = map.chunks(2).next().unwrap().collect();
+    assert_eq!(first, [(&0, &0), (&1, &10)]);
+
+    for (batch, chunk) in map.chunks_mut(2).enumerate() {
+        for (_, v) in chunk {
+            *v = batch;
+        }
+    }
+    assert_eq!(map.values().cloned().collect::<Vec<_>>(), [0, 0, 1, 1, 2]);
+    assert_eq!(map.chunks(10).len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_chunks_zero() {
+    let map: LinearMap<i32, i32> = LinearMap::new();
+    map.chunks(0);
+}
+
+#[test]
+fn test_collect_results() {
+    let ok: Result<LinearMap<_, _>, &str> = vec![Ok((1, 'a')), Ok((2, 'b'))].into_iter().collect();
+    assert_eq!(ok.unwrap().len(), 2);
+
+    let mut seen = 0;
+    let err: Result<LinearMap<i32, char>, &str> = vec![Ok((1, 'a')), Err("bad"), Ok((2, 'b'))]
+        .into_iter()
+        .inspect(|_| seen += 1)
+        .collect();
+    assert_eq!(err, Err("bad"));
+    assert_eq!(seen, 2);
+}
+
+#[test]
+fn test_extend_policies() {
+    let mut map = LinearMap::from_iter_keep_first(vec![(1, 'a'), (2, 'b'), (1, 'c')]);
+    assert_eq!(map[&1], 'a');
+    map.extend_keep_first(vec![(2, 'x'), (3, 'y'), (3, 'z')]);
+    assert_eq!(map[&2], 'b');
+    assert_eq!(map[&3], 'y');
+
+    assert_eq!(map.try_extend_unique(vec![(4, 'd'), (5, 'e')]), Ok(()));
+    assert_eq!(map.len(), 5);
+    let err = map.try_extend_unique(vec![(6, 'f'), (7, 'g'), (6, 'h')]).unwrap_err();
+    assert_eq!(err.into_inner(), (6, 'h'));
+    assert_eq!(map.len(), 5);
+    assert!(!map.contains_key(&6));
+    assert!(map.try_extend_unique(vec![(1, 'q')]).is_err());
+    assert_eq!(map[&1], 'a');
+}
+
+#[test]
+fn test_get_key_value_mut() {
+    let mut map = LinearMap::new();
+    map.insert("alpha".to_string(), 0);
+
+    let (k, v) = map.get_key_value_mut("alpha").unwrap();
+    *v = k.len();
+    assert_eq!(map["alpha"], 5);
+    assert!(map.get_key_value_mut("beta").is_none());
+
+    match map.entry("alpha".to_string()) {
+        Occupied(mut e) => {
+            let (k, v) = e.get_key_value_mut();
+            *v += k.len();
+            let (_, v) = e.into_key_value_mut();
+            *v += 1;
+        }
+        Vacant(_) => panic!(),
+    }
+    assert_eq!(map["alpha"], 11);
+}
@@ -0,0 +1,92 @@
+extern crate linear_map;
+
+use std::mem::size_of;
+use std::rc::Rc;
+
+use linear_map::LinearMap;
+use linear_map::thin::ThinLinearMap;
+
+#[test]
+fn test_header_size() {
+    assert_eq!(size_of::<ThinLinearMap<u64, u64>>(), size_of::<usize>() + 8);
+    assert_eq!(size_of::<Option<ThinLinearMap<u64, u64>>>(), size_of::<ThinLinearMap<u64, u64>>());
+    assert!(size_of::<ThinLinearMap<u64, u64>>() <= size_of::<LinearMap<u64, u64>>());
+}
+
+#[test]
+fn test_insert_get_remove() {
+    let mut map = ThinLinearMap::new();
+    for i in 0..100 {
+        assert_eq!(map.insert(i, i * 10), None);
+    }
+    assert_eq!(map.insert(5, 0), Some(50));
+    assert_eq!(map.len(), 100);
+    assert!(map.capacity() >= 100);
+    assert_eq!(map.get(&7), Some(&70));
+    *map.get_mut(&7).unwrap() += 1;
+    assert_eq!(map.remove(&7), Some(71));
+    assert_eq!(map.remove(&7), None);
+    assert!(!map.contains_key(&7));
+
+    map.retain(|&k, _| k % 2 == 0);
+    assert_eq!(map.len(), 50);
+    map.clear();
+    assert!(map.is_empty());
+    map.shrink_to_fit();
+    assert_eq!(map.capacity(), 0);
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn test_reserve_overflow() {
+    let mut map = ThinLinearMap::new();
+    map.insert(1, 1);
+    map.reserve(usize::MAX);
+}
+
+#[test]
+fn test_retain_panic_keeps_map_consistent() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let value = Rc::new(());
+    let mut map: ThinLinearMap<i32, _> = (0..4).map(|i| (i, value.clone())).collect();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        map.retain(|&k, _| if k == 2 { panic!() } else { k != 1 });
+    }));
+    assert!(result.is_err());
+    assert_eq!(map.len() + 1, Rc::strong_count(&value));
+    drop(map);
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_conversions_and_drop() {
+    let value = Rc::new(());
+    let mut map = LinearMap::with_capacity(8);
+    map.insert("a", value.clone());
+    map.insert("b", value.clone());
+
+    let thin = ThinLinearMap::from(map);
+    assert_eq!(thin.capacity(), 8);
+    assert_eq!(thin.keys().collect::<Vec<_>>(), [&"a", &"b"]);
+    let copy = thin.clone();
+    assert_eq!(thin, copy);
+    assert_eq!(Rc::strong_count(&value), 5);
+
+    let back = LinearMap::from(thin);
+    assert_eq!(back.capacity(), 8);
+    assert_eq!(back.len(), 2);
+    drop(back);
+    assert_eq!(copy.into_iter().count(), 2);
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_zero_sized_entries() {
+    let mut map = ThinLinearMap::new();
+    map.insert((), ());
+    map.insert((), ());
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.remove(&()), Some(()));
+    assert!(map.is_empty());
+}
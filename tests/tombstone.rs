@@ -0,0 +1,60 @@
+extern crate linear_map;
+
+use std::panic;
+
+use linear_map::LinearMap;
+use linear_map::tombstone::TombstoneLinearMap;
+
+#[test]
+fn test_remove_keeps_order() {
+    let mut map: TombstoneLinearMap<u32, char> = (0..6).zip("abcdef".chars()).collect();
+    assert_eq!(map.remove(&1), Some('b'));
+    assert_eq!(map.remove(&4), Some('e'));
+    assert_eq!(map.remove(&4), None);
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.tombstones(), 2);
+    assert!(!map.contains_key(&1));
+
+    assert_eq!(map.insert(2, 'C'), Some('c'));
+    assert_eq!(map.insert(1, 'B'), None);
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), [0, 2, 3, 5, 1]);
+    assert_eq!(map.values().next_back(), Some(&'B'));
+    assert_eq!(map.iter().len(), 5);
+
+    map.compact();
+    assert_eq!(map.tombstones(), 0);
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), [0, 2, 3, 5, 1]);
+}
+
+#[test]
+fn test_retain_and_conversions() {
+    let mut map: TombstoneLinearMap<u32, u32> = (0..10).map(|i| (i, i)).collect();
+    map.remove(&0);
+    for (_, v) in &mut map {
+        *v *= 10;
+    }
+    map.retain(|&k, _| k % 3 != 0);
+    assert_eq!(map.tombstones(), 0);
+    assert_eq!(map.get(&8), Some(&80));
+
+    let linear = LinearMap::from(map.clone());
+    assert_eq!(linear.keys().copied().collect::<Vec<_>>(), [1, 2, 4, 5, 7, 8]);
+    assert_eq!(TombstoneLinearMap::from(linear), map);
+    assert_eq!(map.into_iter().map(|(_, v)| v).sum::<u32>(), 270);
+}
+
+#[test]
+fn test_retain_panic() {
+    let mut map: TombstoneLinearMap<u32, u32> = (0..6).map(|i| (i, i)).collect();
+    map.remove(&1);
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        map.retain(|&k, _| {
+            assert!(k != 4, "boom");
+            k != 2
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), [0, 3, 4, 5]);
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.tombstones(), 0);
+}
@@ -0,0 +1,78 @@
+extern crate linear_map;
+
+use linear_map::vec_map::VecMap;
+
+#[test]
+fn test_insert_remove_get() {
+    let mut map = VecMap::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(5, "five"), None);
+    assert_eq!(map.insert(1, "one"), None);
+    assert_eq!(map.len(), 2);
+    assert!(map.capacity() >= 6);
+    assert_eq!(map.get(5), Some(&"five"));
+    assert_eq!(map.get(0), None);
+    assert_eq!(map.get(100), None);
+    assert!(map.contains_key(1));
+    assert_eq!(map.insert(5, "FIVE"), Some("five"));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[5], "FIVE");
+    map[1] = "ONE";
+    assert_eq!(map.remove(1), Some("ONE"));
+    assert_eq!(map.remove(1), None);
+    assert_eq!(map.remove(100), None);
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_iterators() {
+    let mut map: VecMap<_> = vec![(4, 40), (0, 0), (2, 20)].into_iter().collect();
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(0, &0), (2, &20), (4, &40)]);
+    assert_eq!(map.iter().rev().collect::<Vec<_>>(), [(4, &40), (2, &20), (0, &0)]);
+    assert_eq!(map.iter().len(), 3);
+    assert_eq!(map.keys().collect::<Vec<_>>(), [0, 2, 4]);
+    assert_eq!(map.values().collect::<Vec<_>>(), [&0, &20, &40]);
+    for v in map.values_mut() {
+        *v += 1;
+    }
+    for (k, v) in &mut map {
+        *v += k;
+    }
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), [(0, 1), (2, 23), (4, 45)]);
+}
+
+#[test]
+fn test_retain_and_shrink() {
+    let mut map: VecMap<usize> = (0..10).map(|i| (i, i * 10)).collect();
+    map.retain(|k, _| k < 3);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.keys().collect::<Vec<_>>(), [0, 1, 2]);
+    map.shrink_to_fit();
+    assert!(map.capacity() >= 3);
+    assert!(map.capacity() < 10);
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.iter().next(), None);
+}
+
+#[test]
+fn test_eq() {
+    let mut a = VecMap::new();
+    a.insert(3, 'c');
+    a.insert(1, 'a');
+    let mut b = VecMap::new();
+    b.insert(1, 'a');
+    b.insert(3, 'c');
+    b.insert(7, 'g');
+    assert!(a != b);
+    b.remove(7);
+    assert_eq!(a, b);
+    assert_eq!(format!("{:?}", a), "{1: 'a', 3: 'c'}");
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn test_insert_max_key() {
+    let mut map = VecMap::new();
+    map.insert(usize::MAX, ());
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "wasm")]
+
+extern crate js_sys;
+extern crate linear_map;
+extern crate wasm_bindgen;
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen_test;
+
+// Calling into JavaScript panics outside of WebAssembly, so other targets only check that the
+// conversions exist with the expected signatures.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_conversion_signatures() {
+    use js_sys::{Map, Object};
+    use linear_map::LinearMap;
+    use wasm_bindgen::JsValue;
+
+    fn into_map<V: Into<JsValue>>(map: LinearMap<String, V>) -> Map {
+        map.into()
+    }
+
+    let _: fn(LinearMap<String, u32>) -> Map = into_map;
+    let _: fn(LinearMap<String, String>) -> Map = into_map;
+    let _: fn(&Map) -> Result<LinearMap<String, JsValue>, JsValue> = LinearMap::try_from_js_map;
+    let _: fn(&Object) -> LinearMap<String, JsValue> = LinearMap::from_js_object;
+    let _: fn(LinearMap<String, f64>) -> Object = LinearMap::into_js_object;
+}
+
+#[cfg(target_arch = "wasm32")]
+mod js {
+    use js_sys::{Map, Object, Reflect};
+    use linear_map::LinearMap;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn sample() -> LinearMap<String, u32> {
+        let mut map = LinearMap::new();
+        map.insert("b".to_string(), 2);
+        map.insert("a".to_string(), 1);
+        map
+    }
+
+    #[wasm_bindgen_test]
+    fn test_js_map_round_trip() {
+        let js = Map::from(sample());
+        assert_eq!(js.size(), 2);
+        assert_eq!(js.get(&"b".into()), JsValue::from(2));
+
+        let back = LinearMap::try_from_js_map(&js).unwrap();
+        assert_eq!(back.keys().cloned().collect::<Vec<_>>(), ["b", "a"]);
+        assert_eq!(back["a"], JsValue::from(1));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_js_map_non_string_key() {
+        let js = Map::new();
+        js.set(&"a".into(), &JsValue::from(1));
+        js.set(&JsValue::from(7), &JsValue::from(2));
+        assert_eq!(LinearMap::try_from_js_map(&js).unwrap_err(), JsValue::from(7));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_js_object_round_trip() {
+        let js = sample().into_js_object();
+        assert_eq!(Reflect::get(&js, &"a".into()).unwrap(), JsValue::from(1));
+
+        let back = LinearMap::from_js_object(&js);
+        assert_eq!(back.keys().cloned().collect::<Vec<_>>(), ["b", "a"]);
+        assert_eq!(back["b"], JsValue::from(2));
+        assert!(LinearMap::from_js_object(&Object::new()).is_empty());
+    }
+}
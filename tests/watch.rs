@@ -0,0 +1,63 @@
+#![cfg(feature = "diagnostics")]
+
+extern crate linear_map;
+
+use std::sync::{Arc, Mutex};
+
+use linear_map::watch::WatchedLinearMap;
+
+#[test]
+fn test_reports_once() {
+    let reports = Arc::new(Mutex::new(vec![]));
+    let log = reports.clone();
+    let mut map = WatchedLinearMap::with_callback("cache", 3, move |name, len| {
+        log.lock().unwrap().push((name, len));
+    });
+    assert_eq!(map.name(), "cache");
+    assert_eq!(map.threshold(), 3);
+
+    map.extend((0..3).map(|i| (i, i)));
+    assert!(!map.has_exceeded());
+    assert_eq!(map.insert(2, 20), Some(2));
+    assert!(!map.has_exceeded());
+
+    map.insert(3, 3);
+    assert!(map.has_exceeded());
+    map.map_mut().remove(&3);
+    map.insert(4, 4);
+    map.insert(5, 5);
+
+    assert_eq!(*reports.lock().unwrap(), [("cache", 4)]);
+    assert_eq!(map.exceeded_count(), 2);
+    assert_eq!(map.get(&2), Some(&20));
+}
+
+#[test]
+fn test_map_mut_checks_on_drop() {
+    let mut map = WatchedLinearMap::new("batch", 2);
+    {
+        let mut guard = map.map_mut();
+        guard.insert("a", 1);
+        guard.insert("b", 2);
+        guard.insert("c", 3);
+        guard.remove("c");
+    }
+    assert!(!map.has_exceeded());
+
+    map.map_mut().entry("c").or_insert(3);
+    assert_eq!(map.exceeded_count(), 1);
+    *map.map_mut().get_mut("a").unwrap() = 10;
+    assert_eq!(map.exceeded_count(), 1);
+    assert_eq!(map["a"], 10);
+}
+
+#[test]
+fn test_without_callback() {
+    let mut map = WatchedLinearMap::new("small", 0);
+    let other: WatchedLinearMap<&str, u32> = WatchedLinearMap::new("other", 0);
+    map.insert("a", 1);
+    assert!(map.has_exceeded());
+    assert!(!other.has_exceeded());
+    assert!(!map.clone().has_exceeded());
+    assert_eq!(map.into_inner().len(), 1);
+}
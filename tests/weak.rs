@@ -0,0 +1,83 @@
+extern crate linear_map;
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use linear_map::weak::{ArcWeakKeyLinearMap, ArcWeakValueMap, RcWeakKeyLinearMap, RcWeakValueMap};
+
+#[test]
+fn test_rc_values() {
+    let mut map = RcWeakValueMap::new();
+    let a = Rc::new(1);
+    let b = Rc::new(2);
+    assert_eq!(map.insert("a", &a), None);
+    assert_eq!(map.insert("b", &b), None);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get("a"), Some(a.clone()));
+    assert_eq!(Rc::strong_count(&a), 1);
+
+    drop(b);
+    assert_eq!(map.get("b"), None);
+    assert!(!map.contains_key("b"));
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), [("a", 1)]);
+
+    let c = Rc::new(3);
+    assert_eq!(map.insert("b", &c), None);
+    assert_eq!(map.insert("b", &a), Some(c.clone()));
+    assert_eq!(map.remove("b"), Some(a.clone()));
+
+    drop(a);
+    assert!(map.is_empty());
+    assert_eq!(map.purge(), 1);
+    assert_eq!(map.purge(), 0);
+}
+
+#[test]
+fn test_arc_values() {
+    let mut map = ArcWeakValueMap::with_capacity(1);
+    let v = Arc::new("value".to_string());
+    map.insert(1, &v);
+    assert_eq!(map.get(&1).as_ref().map(|s| s.as_str()), Some("value"));
+    assert_eq!(format!("{:?}", map), r#"{1: "value"}"#);
+    drop(v);
+    assert_eq!(map.remove(&1), None);
+}
+
+#[test]
+fn test_weak_keys() {
+    let mut map = RcWeakKeyLinearMap::new();
+    let a = Rc::new("a".to_string());
+    let b = Rc::new("b".to_string());
+    assert_eq!(map.insert(&a, 1), None);
+    assert_eq!(map.insert(&b, 2), None);
+    assert_eq!(map.insert(&Rc::new("a".to_string()), 10), Some(1));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get("a"), Some(&10));
+    assert_eq!(Rc::strong_count(&a), 1);
+
+    *map.get_mut("b").unwrap() += 1;
+    drop(b);
+    assert_eq!(map.get("b"), None);
+    assert!(!map.contains_key("b"));
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.iter().map(|(k, &v)| (k.to_string(), v)).collect::<Vec<_>>(),
+               [("a".to_string(), 10)]);
+    assert_eq!(format!("{:?}", map), r#"{"a": 10}"#);
+
+    assert_eq!(map.purge(), 1);
+    assert_eq!(map.purge(), 0);
+    assert_eq!(map.remove("a"), Some(10));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_arc_weak_keys() {
+    let mut map = ArcWeakKeyLinearMap::with_capacity(1);
+    let k = Arc::new(7);
+    map.insert(&k, "seven");
+    assert_eq!(map.get(&7), Some(&"seven"));
+    drop(k);
+    assert!(map.is_empty());
+    assert_eq!(map.remove(&7), None);
+}